@@ -0,0 +1,16 @@
+// 把当前 git 提交的短哈希编译进程序 (ATLAS_GIT_HASH)，不在仓库中或没有 git 时跳过
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8(o.stdout).ok());
+    if let Some(hash) = hash {
+        println!("cargo:rustc-env=ATLAS_GIT_HASH={}", hash.trim());
+    }
+}
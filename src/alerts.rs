@@ -0,0 +1,148 @@
+//! 数据驱动的告警：监控任务每次采样后把指标交给 `AlertEngine`，按 Config.alerts 中的规则
+//! 判断是否发出状态通知。每条规则有冷却时间，并带回差 (ALERT_HYSTERESIS)，指标在阈值附近抖动时不会反复触发。
+
+use std::time::{Duration, Instant};
+
+use crate::config::AlertRule;
+use crate::constans::ALERT_HYSTERESIS;
+use crate::message::StatusLevel;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AlertOp {
+    Gt,
+    Lt,
+    Ge,
+    Le,
+}
+
+impl AlertOp {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim() {
+            ">" => Some(Self::Gt),
+            "<" => Some(Self::Lt),
+            ">=" => Some(Self::Ge),
+            "<=" => Some(Self::Le),
+            _ => None,
+        }
+    }
+
+    fn matches(self, v: f64, threshold: f64) -> bool {
+        match self {
+            Self::Gt => v > threshold,
+            Self::Lt => v < threshold,
+            Self::Ge => v >= threshold,
+            Self::Le => v <= threshold,
+        }
+    }
+
+    /// 已触发的规则何时解除：需越过阈值另一侧 ALERT_HYSTERESIS
+    fn cleared(self, v: f64, threshold: f64) -> bool {
+        match self {
+            Self::Gt | Self::Ge => v < threshold - ALERT_HYSTERESIS,
+            Self::Lt | Self::Le => v > threshold + ALERT_HYSTERESIS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct RuleState {
+    firing: bool,
+    last_fired: Option<Instant>,
+}
+
+/// 规则的运行状态按下标保存；规则数量变化 (热加载) 时全部重置
+#[derive(Debug, Default)]
+pub struct AlertEngine {
+    states: Vec<RuleState>,
+}
+
+impl AlertEngine {
+    /// samples 为本次采到的指标 (名称, 值)；只检查指标出现在 samples 中的规则，
+    /// 返回需要发出的通知
+    pub fn evaluate(&mut self, rules: &[AlertRule], samples: &[(&str, f64)], now: Instant) -> Vec<(String, StatusLevel)> {
+        if self.states.len() != rules.len() {
+            self.states = vec![RuleState::default(); rules.len()];
+            for rule in rules.iter().filter(|r| AlertOp::parse(&r.op).is_none()) {
+                log::warn!("Alert rule for {} has invalid op '{}', ignored", rule.metric, rule.op);
+            }
+        }
+        let mut fired = Vec::new();
+        for (rule, state) in rules.iter().zip(self.states.iter_mut()) {
+            let Some(&(_, v)) = samples.iter().find(|(m, _)| *m == rule.metric) else {
+                continue;
+            };
+            let Some(op) = AlertOp::parse(&rule.op) else {
+                continue;
+            };
+            if state.firing {
+                if op.cleared(v, rule.value) {
+                    state.firing = false;
+                }
+                continue;
+            }
+            if !op.matches(v, rule.value) {
+                continue;
+            }
+            let cooling = state
+                .last_fired
+                .is_some_and(|t| now.duration_since(t) < Duration::from_secs(rule.cooldown_secs));
+            if cooling {
+                continue;
+            }
+            state.firing = true;
+            state.last_fired = Some(now);
+            fired.push((
+                format!("Alert: {} is {:.1} ({} {})", rule.metric, v, rule.op.trim(), rule.value),
+                rule.level.clone(),
+            ));
+        }
+        fired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(metric: &str, op: &str, value: f64, cooldown_secs: u64) -> AlertRule {
+        AlertRule { metric: metric.into(), op: op.into(), value, level: StatusLevel::Warning, cooldown_secs }
+    }
+
+    #[test]
+    fn fires_once_then_waits_for_hysteresis() {
+        let rules = [rule("cpu_temp", ">", 85.0, 0)];
+        let mut engine = AlertEngine::default();
+        let t0 = Instant::now();
+        assert_eq!(engine.evaluate(&rules, &[("cpu_temp", 90.0)], t0).len(), 1);
+        // 仍在阈值之上，或只回落到回差范围内：不重复触发
+        assert!(engine.evaluate(&rules, &[("cpu_temp", 91.0)], t0).is_empty());
+        assert!(engine.evaluate(&rules, &[("cpu_temp", 84.5)], t0).is_empty());
+        assert!(engine.evaluate(&rules, &[("cpu_temp", 86.0)], t0).is_empty());
+        // 越过回差后解除，再次超过阈值时重新触发
+        assert!(engine.evaluate(&rules, &[("cpu_temp", 80.0)], t0).is_empty());
+        assert_eq!(engine.evaluate(&rules, &[("cpu_temp", 86.0)], t0).len(), 1);
+    }
+
+    #[test]
+    fn cooldown_suppresses_refire() {
+        let rules = [rule("mem_pct", ">=", 95.0, 600)];
+        let mut engine = AlertEngine::default();
+        let t0 = Instant::now();
+        assert_eq!(engine.evaluate(&rules, &[("mem_pct", 96.0)], t0).len(), 1);
+        assert!(engine.evaluate(&rules, &[("mem_pct", 50.0)], t0).is_empty());
+        assert!(engine.evaluate(&rules, &[("mem_pct", 96.0)], t0 + Duration::from_secs(60)).is_empty());
+        assert!(engine.evaluate(&rules, &[("mem_pct", 50.0)], t0 + Duration::from_secs(61)).is_empty());
+        assert_eq!(engine.evaluate(&rules, &[("mem_pct", 96.0)], t0 + Duration::from_secs(601)).len(), 1);
+    }
+
+    #[test]
+    fn missing_samples_and_invalid_ops_never_fire() {
+        let rules = [rule("battery_pct", "<", 15.0, 0), rule("disk_pct", "=>", 95.0, 0)];
+        let mut engine = AlertEngine::default();
+        let now = Instant::now();
+        // 本周期没有电池读数 (termux-api 不可用)：规则不参与判断
+        assert!(engine.evaluate(&rules, &[("cpu_temp", 40.0)], now).is_empty());
+        assert!(engine.evaluate(&rules, &[("disk_pct", 100.0)], now).is_empty());
+        assert_eq!(engine.evaluate(&rules, &[("battery_pct", 10.0)], now).len(), 1);
+    }
+}
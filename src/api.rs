@@ -0,0 +1,243 @@
+//! HTTP API 的处理逻辑，与具体 web 框架无关，可以直接测试。
+//! 路由层 (_server.rs，依赖 ntex) 尚未编入构建，这里的函数暂时只由测试调用
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::db::Database;
+use crate::message::{GlobalEvent, Reply, TaskRequest};
+use crate::prelude::GlobIO;
+use crate::ui::task_control::LogLine;
+
+/// AI 查询只允许的只读语句 (INFO / SHOW 是 SurrealQL 的写法，SQLite 中没有对应语句)
+const READ_ONLY_KEYWORDS: &[&str] = &["SELECT"];
+
+/// 查询被拒绝或执行失败的原因，序列化为结构化 JSON 错误
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum QueryRejection {
+    Empty,
+    MultiStatement,
+    NotAllowed { keyword: String },
+    QueryFailed { detail: String },
+}
+
+/// 去掉开头的空白与 SQL 注释 (`-- ...` 与 `/* ... */`)
+fn strip_leading_comments(mut s: &str) -> &str {
+    loop {
+        s = s.trim_start();
+        if let Some(rest) = s.strip_prefix("--") {
+            s = rest.split_once('\n').map(|(_, r)| r).unwrap_or("");
+        } else if let Some(rest) = s.strip_prefix("/*") {
+            s = rest.split_once("*/").map(|(_, r)| r).unwrap_or("");
+        } else {
+            return s;
+        }
+    }
+}
+
+/// 按分号拆分语句，忽略字符串字面量和注释中的分号。
+/// 只在 ASCII 字节处切分，非 ASCII 内容不会落在字符中间
+fn split_statements(sql: &str) -> Vec<&str> {
+    let bytes = sql.as_bytes();
+    let mut out = Vec::new();
+    let (mut start, mut i) = (0, 0);
+    let mut quote: Option<u8> = None;
+    while i < bytes.len() {
+        let c = bytes[i];
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == b'\'' || c == b'"' || c == b'`' => quote = Some(c),
+            None if bytes[i..].starts_with(b"--") => {
+                i = bytes[i..].iter().position(|&b| b == b'\n').map(|p| i + p).unwrap_or(bytes.len());
+                continue;
+            }
+            None if bytes[i..].starts_with(b"/*") => {
+                i = bytes[i + 2..].windows(2).position(|w| w == b"*/").map(|p| i + p + 4).unwrap_or(bytes.len());
+                continue;
+            }
+            None if c == b';' => {
+                out.push(&sql[start..i]);
+                start = i + 1;
+            }
+            None => {}
+        }
+        i += 1;
+    }
+    out.push(&sql[start..]);
+    out.into_iter().filter(|s| !strip_leading_comments(s).is_empty()).collect()
+}
+
+/// 只看每条语句的首个关键字 (大小写不敏感)，不在白名单内一律拒绝；
+/// 多条语句默认拒绝，除非 allow_multi
+pub fn classify_query(sql: &str, allow_multi: bool) -> Result<(), QueryRejection> {
+    let statements = split_statements(sql);
+    if statements.is_empty() {
+        return Err(QueryRejection::Empty);
+    }
+    if statements.len() > 1 && !allow_multi {
+        return Err(QueryRejection::MultiStatement);
+    }
+    for stmt in statements {
+        let keyword = strip_leading_comments(stmt)
+            .chars()
+            .take_while(|c| c.is_ascii_alphabetic())
+            .collect::<String>()
+            .to_ascii_uppercase();
+        if !READ_ONLY_KEYWORDS.contains(&keyword.as_str()) {
+            return Err(QueryRejection::NotAllowed { keyword });
+        }
+    }
+    Ok(())
+}
+
+/// POST /api/ai/query：通过分类检查后执行，返回按行排列的显示字符串
+pub async fn read_only_query(sql: &str) -> Result<Vec<Vec<String>>, QueryRejection> {
+    let allow_multi = Config::get().try_read().map(|c| c.api_allow_multi_statement).unwrap_or(false);
+    classify_query(sql, allow_multi)?;
+    let rows = sqlx::query(sql)
+        .fetch_all(Database::pool())
+        .await
+        .map_err(|e| QueryRejection::QueryFailed { detail: e.to_string() })?;
+    Ok(rows
+        .iter()
+        .map(|r| (0..sqlx::Row::len(r)).map(|i| crate::ui::db_view::cell_to_string(r, i)).collect())
+        .collect())
+}
+
+/// GET /api/status 的响应：{status, version, uptime (秒)}
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatusPayload {
+    pub status: String,
+    pub version: String,
+    pub uptime: u64,
+}
+
+/// GET /api/status：健康检查
+pub fn status() -> StatusPayload {
+    StatusPayload {
+        status: "online".into(),
+        version: crate::version::version_string(),
+        uptime: crate::version::uptime().as_secs(),
+    }
+}
+
+/// 日志接口默认与最多返回的行数
+pub const LOG_TAIL_DEFAULT: usize = 100;
+pub const LOG_TAIL_MAX: usize = 5000;
+/// 等待 TaskControl 组件回复的最长时间
+const TASK_REPLY_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// 日志接口的错误，序列化为结构化 JSON 错误
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "error", rename_all = "snake_case")]
+pub enum LogsError {
+    /// tail 必须是正整数
+    InvalidTail,
+    /// 没有组件持有该任务 (或 TaskControl 被禁用)
+    UnknownTask { id: String },
+}
+
+/// GET /api/tasks/{id}/logs?tail=N：任务日志的最后 N 行 (默认 LOG_TAIL_DEFAULT，最多 LOG_TAIL_MAX)。
+/// 日志保存在 TUI 的 TaskRuntime 中，经 GlobIO 总线向 TaskControl 组件查询
+pub async fn task_logs(id: &str, tail: Option<usize>) -> Result<Vec<LogLine>, LogsError> {
+    let tail = match tail.unwrap_or(LOG_TAIL_DEFAULT) {
+        0 => return Err(LogsError::InvalidTail),
+        n => n.min(LOG_TAIL_MAX),
+    };
+    let (reply, rx) = Reply::channel();
+    let _ = GlobIO::send().send(GlobalEvent::Task(TaskRequest::Logs { id: id.to_string(), tail, reply }));
+    match tokio::time::timeout(TASK_REPLY_TIMEOUT, rx).await {
+        Ok(Ok(lines)) => Ok(lines),
+        _ => Err(LogsError::UnknownTask { id: id.to_string() }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_single_select_statements_pass() {
+        assert_eq!(classify_query("SELECT 1", false), Ok(()));
+        // 大小写混写
+        assert_eq!(classify_query("SeLeCt * FROM telemetry", false), Ok(()));
+        assert_eq!(
+            classify_query("DeLeTe FROM telemetry", false),
+            Err(QueryRejection::NotAllowed { keyword: "DELETE".into() })
+        );
+        assert_eq!(classify_query("  ;  -- nothing\n", false), Err(QueryRejection::Empty));
+    }
+
+    #[test]
+    fn chained_statements_are_rejected_unless_allowed() {
+        let chained = "SELECT 1; DELETE FROM telemetry";
+        assert_eq!(classify_query(chained, false), Err(QueryRejection::MultiStatement));
+        // 允许多条语句时每一条仍要通过白名单
+        assert_eq!(
+            classify_query(chained, true),
+            Err(QueryRejection::NotAllowed { keyword: "DELETE".into() })
+        );
+        assert_eq!(classify_query("SELECT 1; SELECT 2;", true), Ok(()));
+        // 字符串里的分号不算语句分隔，非 ASCII 内容也不会切坏
+        assert_eq!(classify_query("SELECT 'a;b', '温度;°C'", false), Ok(()));
+    }
+
+    #[test]
+    fn comments_cannot_hide_the_real_keyword() {
+        // 注释前缀被跳过，真正的关键字仍被识别
+        assert_eq!(
+            classify_query("/* SELECT */ DELETE FROM telemetry", false),
+            Err(QueryRejection::NotAllowed { keyword: "DELETE".into() })
+        );
+        assert_eq!(
+            classify_query("-- SELECT\n  drop TABLE telemetry", false),
+            Err(QueryRejection::NotAllowed { keyword: "DROP".into() })
+        );
+        // 注释里的分号不拆分语句；注释后藏的第二条语句照样拒绝
+        assert_eq!(classify_query("SELECT 1 /* ; DELETE */", false), Ok(()));
+        assert_eq!(classify_query("SELECT 1 -- x\n; DELETE FROM t", false), Err(QueryRejection::MultiStatement));
+        assert_eq!(classify_query("\n\t /* a */ -- b\n select 1", false), Ok(()));
+    }
+
+    #[tokio::test]
+    async fn rejected_query_never_reaches_the_database() {
+        let _db = Database::init_for_test().await;
+        Config::init_for_test();
+        sqlx::query("CREATE TABLE IF NOT EXISTS api_guard_test (v INTEGER)").execute(Database::pool()).await.unwrap();
+        sqlx::query("INSERT INTO api_guard_test (v) VALUES (1)").execute(Database::pool()).await.unwrap();
+
+        assert!(read_only_query("SELECT v FROM api_guard_test; DELETE FROM api_guard_test").await.is_err());
+        assert_eq!(read_only_query("select count(*) from api_guard_test").await, Ok(vec![vec!["1".to_string()]]));
+        let err = read_only_query("SELECT * FROM missing_table").await.unwrap_err();
+        assert!(matches!(err, QueryRejection::QueryFailed { .. }), "{err:?}");
+        assert_eq!(serde_json::to_value(&err).unwrap()["error"], "query_failed");
+
+        sqlx::query("DROP TABLE api_guard_test").execute(Database::pool()).await.unwrap();
+    }
+
+    #[test]
+    fn status_json_round_trips() {
+        let payload = status();
+        assert!(!payload.version.is_empty());
+
+        let json = serde_json::to_string(&payload).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["status"], "online");
+        assert_eq!(value["version"], crate::version::version_string());
+        assert!(value["uptime"].is_u64(), "{json}");
+        assert_eq!(serde_json::from_str::<StatusPayload>(&json).unwrap(), payload);
+    }
+
+    #[tokio::test]
+    async fn zero_tail_is_rejected_without_asking_the_tasks() {
+        GlobIO::init_for_test();
+        let err = task_logs("any", Some(0)).await.unwrap_err();
+        assert_eq!(err, LogsError::InvalidTail);
+        assert_eq!(serde_json::to_value(&err).unwrap()["error"], "invalid_tail");
+    }
+}
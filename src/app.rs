@@ -1,10 +1,16 @@
-use crate::config::{Config, SharedConfig};
-use crate::constans::{APP_TITLE, FOOTER_LAYOUT, TabId};
+use crate::config::{Config, IdleScreen, SharedConfig, SmallTerminal};
+use crate::constans::{
+    APP_TITLE, EXIT_SAVE_RETRIES, FOOTER_LAYOUT, MIN_TERM_HEIGHT, MIN_TERM_WIDTH, NOTIFY_HISTORY_CAP,
+    REFRESH_RATE_MAX_MS, SHUTDOWN_TIMEOUT_MS,
+    TICK_IDLE_MS, TICK_MIN_MS, TabId,
+};
 // 引入新的 message 定义
-use crate::message::{GlobalEvent, Progress, StatusLevel};
+use crate::message::{ConfirmRequest, GlobalEvent, Progress, Reply, StatusLevel, TaskRequest};
+use crate::ui::task_control::TaskStatusView;
 use crate::prelude::{GlobIO, GlobRecv, GlobSend};
 use crate::ui::app_button::button_components_init;
 use crate::ui::component::Component;
+use crate::ui::render_stats::RenderStats;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
@@ -12,8 +18,10 @@ use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Tabs},
+    widgets::{Block, Borders, Clear, Paragraph, Tabs},
 };
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
 use tokio::sync::broadcast;
 use tokio::time::{Duration, Instant, Interval, MissedTickBehavior, interval};
 
@@ -26,6 +34,7 @@ pub struct App {
 
     // --- 标签页组件、索引、控制焦点 ---
     pub components: Vec<Box<dyn Component>>,
+    pub tab_ids: Vec<TabId>, // 与 components 一一对应
     pub active_tab: usize,
     // pub focus_on_content: bool,                     // 新增：焦点控制
     pub button_components: Vec<Box<dyn Component>>, // 底部通知组件
@@ -35,17 +44,41 @@ pub struct App {
     pub glob_recv: GlobRecv,
     //pub current_status: Option<ActiveStatus>, // 新增：合并后的状态显示
 
+    // --- 通知历史 ---
+    pub notify_history: VecDeque<(DateTime<Local>, String, StatusLevel)>,
+    pub show_history: bool,
+    pub history_scroll: u16,
+
+    // --- 等待确认的破坏性操作 ---
+    pub pending_confirm: Option<ConfirmRequest>,
+
     // --- 重绘标记 ---
     pub re_rend_mark: bool,
+
+    // --- 空闲屏保 ---
+    last_input: Instant,
+    // 当前显示的屏保 (None 表示正常界面) 及时钟分钟，变化时才重绘
+    idle_shown: Option<(IdleScreen, String)>,
+
+    // --- F12 渲染诊断浮层 ---
+    show_render_stats: bool,
+    render_stats: RenderStats,
 }
 
 impl Component for App {
     fn update(&mut self) -> bool {
         let mut changed = false;
         // 1. 处理新事件
-        while let Ok(event) = self.glob_recv.try_recv() {
+        while let Some(event) = GlobIO::try_recv(&mut self.glob_recv) {
             match event {
-                GlobalEvent::Status(_, _, _) => changed = true,
+                GlobalEvent::Status(msg, level, _) => {
+                    self.push_history(msg, level);
+                    changed = true;
+                }
+                GlobalEvent::Confirm(req) => {
+                    self.request_confirm(req);
+                    changed = true;
+                }
                 _ => {}
             }
         }
@@ -61,17 +94,26 @@ impl Component for App {
         //     }
         // }
 
-        // 1. 驱动所有子组件更新（确保后台数据流不堆积）
-        for comp in self.components.iter_mut() {
-            if comp.update() {
-                changed = true;
-            }
+        // 1. 驱动子组件更新：后台页按配置跳过，但有订阅的组件必须消费消息（确保后台数据流不堆积）
+        let update_background = self
+            .config
+            .try_read()
+            .map(|cfg| cfg.update_background_tabs)
+            .unwrap_or(true);
+        if tick_components(&mut self.components, self.active_tab, update_background) {
+            changed = true;
         }
         for comp in self.button_components.iter_mut() {
             if comp.update() {
                 changed = true;
             }
         }
+        // 进入/退出屏保或时钟跳到下一分钟
+        let idle = self.idle_screen().map(|s| (s, Local::now().format("%H:%M").to_string()));
+        if idle != self.idle_shown {
+            self.idle_shown = idle;
+            changed = true;
+        }
         // 2. 如果有更新，给自己打上“脏标记”
         if changed {
             self.request_render();
@@ -80,16 +122,36 @@ impl Component for App {
     }
 
     fn render(&mut self, f: &mut Frame, area: Rect) {
+        let frame_start = std::time::Instant::now();
+        if let Some((IdleScreen::Clock, clock)) = &self.idle_shown {
+            render_clock(f, area, clock);
+            return;
+        }
         // 统一布局管理
+        let (tab_rows, footer_rows, on_small) = match self.config.try_read() {
+            Ok(c) => (c.tab_bar_rows(), c.footer_rows(), c.on_small_terminal),
+            Err(_) => (3, 1, SmallTerminal::default()),
+        };
+
+        // 终端过小：提示或紧凑布局
+        let small = area.width < MIN_TERM_WIDTH || area.height < MIN_TERM_HEIGHT;
+        if small && on_small == SmallTerminal::Warn {
+            self.render_size_warning(f, area);
+            crate::ui::style::apply(f.buffer_mut());
+            return;
+        }
+        let compact = small && on_small == SmallTerminal::Compact;
+        let (tab_rows, footer_rows) = if compact { (1, 1) } else { (tab_rows, footer_rows) };
         let chunks = Layout::vertical([
-            Constraint::Length(3), // Tabs
-            Constraint::Min(0),    // Content
-            Constraint::Length(1), // Footer
+            Constraint::Length(tab_rows),    // Tabs
+            Constraint::Min(0),              // Content
+            Constraint::Length(footer_rows), // Footer
         ])
         .split(area);
 
         {
-            let titles: Vec<Line> = TabId::ALL
+            let titles: Vec<Line> = self
+                .tab_ids
                 .iter()
                 .enumerate()
                 .map(|(i, t)| {
@@ -113,34 +175,84 @@ impl Component for App {
                 .collect();
 
             let tabs = Tabs::new(titles)
-                .block(Block::default().borders(Borders::ALL).title(APP_TITLE))
+                .block(if compact {
+                    Block::default()
+                } else {
+                    Block::default().borders(Borders::ALL).title(APP_TITLE)
+                })
                 .select(self.active_tab)
                 // 这个 highlight_style 是作用于整体选中效果的补充
                 .highlight_style(Style::default().add_modifier(Modifier::UNDERLINED));
 
-            f.render_widget(tabs, chunks[0]);
+            self.render_stats.time("tabs", || f.render_widget(tabs, chunks[0]));
         }
 
-        // 转发渲染请求给当前活动的子组件
+        // 转发渲染请求给当前活动的子组件 (按标签名计时)
+        let name = self.tab_ids.get(self.active_tab).map(|t| t.name()).unwrap_or("?");
         if let Some(comp) = self.components.get_mut(self.active_tab) {
-            comp.render(f, chunks[1]);
+            self.render_stats.time(name, || comp.render(f, chunks[1]));
         }
 
         // --- 3. 渲染底部状态栏 ---
         let footer_chunks = Layout::horizontal(FOOTER_LAYOUT).split(chunks[2]);
 
-        // 按照固定索引渲染：0-Hint, 1-Notify, 2-Progress
-        if self.button_components.len() >= 3 {
-            self.button_components[0].render(f, footer_chunks[0]);
-            self.button_components[1].render(f, footer_chunks[1]);
-            self.button_components[2].render(f, footer_chunks[2]);
+        // 按照固定索引渲染：0-Hint, 1-Notify, 2-Progress；紧凑布局只保留通知
+        self.render_stats.time("footer", || {
+            if compact {
+                if let Some(notify) = self.button_components.get_mut(1) {
+                    notify.render(f, chunks[2]);
+                }
+            } else if self.button_components.len() >= 3 {
+                self.button_components[0].render(f, footer_chunks[0]);
+                self.button_components[1].render(f, footer_chunks[1]);
+                self.button_components[2].render(f, footer_chunks[2]);
+            }
+        });
+
+        if self.show_history {
+            self.render_history(f, chunks[1]);
+        }
+        if let Some(req) = &self.pending_confirm {
+            render_confirm(f, chunks[1], &req.prompt);
+        }
+
+        if let Some((IdleScreen::Dim, _)) = &self.idle_shown {
+            dim_buffer(f.buffer_mut());
+        }
+        self.render_stats.record("total", frame_start.elapsed());
+        if self.show_render_stats {
+            render_stats_overlay(f, chunks[1], self.render_stats.lines());
         }
+        // 单色模式统一在最后剥离颜色
+        crate::ui::style::apply(f.buffer_mut());
     }
 
     // --- app.rs ---
     fn handle_key(&mut self, key: KeyEvent) -> bool {
         use crossterm::event::{KeyCode, KeyModifiers};
 
+        // 屏保期间的第一个按键只用于唤醒，不传给组件
+        if self.wake() {
+            return true;
+        }
+
+        // F12：渲染诊断浮层，每次打开重新统计
+        if key.code == KeyCode::F(12) {
+            self.show_render_stats = !self.show_render_stats;
+            self.render_stats.reset();
+            self.request_render();
+            return true;
+        }
+
+        // 0. Ctrl + Shift + ←/→ : 调整当前标签页的位置
+        if key.modifiers.contains(KeyModifiers::CONTROL | KeyModifiers::SHIFT) {
+            match key.code {
+                KeyCode::Left => return self.move_active_tab(-1),
+                KeyCode::Right => return self.move_active_tab(1),
+                _ => {}
+            }
+        }
+
         // 1. 最高优先级：全局标签页切换 (Alt + Arrows / Alt + Digits)
         if key.modifiers.contains(KeyModifiers::ALT) {
             match key.code {
@@ -164,15 +276,36 @@ impl Component for App {
             }
         }
 
-        // 2. 底部组件处理 (例如 Esc 键清除错误弹窗)
-        for btn in self.button_components.iter_mut() {
-            if btn.handle_key(key) {
-                self.request_render();
-                return true;
+        // 1.4 确认弹窗独占按键：y 执行，n / Esc 取消
+        if let Some(req) = self.pending_confirm.take() {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    let _ = self.glob_send.send(*req.action);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {}
+                _ => self.pending_confirm = Some(req),
             }
+            self.request_render();
+            return true;
+        }
+
+        // 1.5 通知历史浮层打开时独占按键
+        if self.show_history {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('n') => self.show_history = false,
+                KeyCode::Char('c') => {
+                    self.notify_history.clear();
+                    self.history_scroll = 0;
+                }
+                KeyCode::Up => self.history_scroll = self.history_scroll.saturating_sub(1),
+                KeyCode::Down => self.history_scroll = self.history_scroll.saturating_add(1),
+                _ => return false,
+            }
+            self.request_render();
+            return true;
         }
 
-        // 3. 直接分发给当前激活的子组件 (不再判断 focus_on_content)
+        // 2. 直接分发给当前激活的子组件 (不再判断 focus_on_content)
         // 现在的逻辑是：除非是 Alt 组合键，否则所有按键都交给内容区处理
         if let Some(comp) = self.components.get_mut(self.active_tab) {
             if comp.handle_key(key) {
@@ -181,6 +314,23 @@ impl Component for App {
             }
         }
 
+        // 3. 子组件没有使用的按键才交给底部组件 (例如 Esc / 'c' 确认错误)，
+        // 输入框、编辑器中的 'c' 和 Esc 不会被错误弹窗吞掉
+        for btn in self.button_components.iter_mut() {
+            if btn.handle_key(key) {
+                self.request_render();
+                return true;
+            }
+        }
+
+        // 4. 子组件没有使用的 'n'：打开通知历史 (避免抢占输入框中的字符)
+        if key.code == KeyCode::Char('n') && key.modifiers.is_empty() {
+            self.show_history = true;
+            self.history_scroll = 0;
+            self.request_render();
+            return true;
+        }
+
         false
     }
     fn init() -> Self
@@ -188,41 +338,92 @@ impl Component for App {
         Self: Sized,
     {
         // 3. 初始化标签页 , 由constants.rs定义内所有标签页
-        let components: Vec<Box<dyn Component>> = TabId::init();
+        let (saved_order, enabled_names) = Config::get()
+            .try_read()
+            .map(|cfg| (cfg.ui_state.tab_order.clone(), cfg.enabled_tabs.clone()))
+            .unwrap_or_else(|_| (Vec::new(), Config::default().enabled_tabs));
+        let (enabled, unknown_tabs) = TabId::parse_enabled(&enabled_names);
+        let tab_ids = TabId::ordered(&saved_order, &enabled);
+        let components: Vec<Box<dyn Component>> = TabId::init(&tab_ids);
         let button_components: Vec<Box<dyn Component>> =
             button_components_init();
+        // 底部通知组件订阅之后再提示，否则消息会丢失
+        if crate::db::Database::is_read_only() {
+            GlobIO::error("Another Atlas instance is running: database writes are disabled (read-only)");
+        }
+        if let Some(notice) = Config::read_only_notice() {
+            GlobIO::warn(notice);
+        }
+        if Config::safe_mode() {
+            GlobIO::info("Safe mode: task execution and scripts are disabled");
+        }
+        if !unknown_tabs.is_empty() {
+            GlobIO::warn(format!("Unknown enabled_tabs ignored: {}", unknown_tabs.join(", ")));
+        }
+        if tab_ids.contains(&TabId::Info) {
+            crate::ui::info::InfoComponent::warn_unknown_disk_columns();
+        }
 
-        {
-            // 模拟一个全局计时器同步广播
-            // 测试用进度条，正式程序需要移除
-            // let tx_clone = glob_send.clone();
-            // tokio::spawn(async move {
-            //     let mut p = 0;
-            //     loop {
-            //         p = (p + 1) % 101;
-            //         let _ = tx_clone.send(GlobalEvent::Status(Default::default(), StatusLevel::Info, Some(Progress::Percent(p))));
-            //         // let _ = tx_clone.send(GlobalEvent::SyncProgress(ProgressType::Percentage(p)));
-            //         tokio::time::sleep(std::time::Duration::from_millis(8)).await;
-            //     }
-            // });
+        // 测试用进度条：只有 `--demo` 启动时才广播，正常运行不占用总线
+        if demo_requested(std::env::args()) {
+            spawn_demo_progress();
         }
 
+        Self::with_components(Config::get(), tab_ids, components, button_components)
+    }
+}
+impl App {
+    /// 由已构造的标签页 (与 tab_ids 一一对应) 和底部组件组装 App
+    fn with_components(
+        config: SharedConfig,
+        tab_ids: Vec<TabId>,
+        components: Vec<Box<dyn Component>>,
+        button_components: Vec<Box<dyn Component>>,
+    ) -> Self {
         Self {
-            config:Config::get(),
+            config,
             components,
+            tab_ids,
             active_tab: 0,
             // focus_on_content: false,
             re_rend_mark: true,
+            last_input: Instant::now(),
+            idle_shown: None,
+            show_render_stats: false,
+            render_stats: RenderStats::default(),
+            notify_history: VecDeque::with_capacity(NOTIFY_HISTORY_CAP),
+            show_history: false,
+            history_scroll: 0,
+            pending_confirm: None,
             glob_send:GlobIO::send(),
             glob_recv:GlobIO::recv(),
             button_components,
         }
     }
-}
-impl App {
 
     // --- 渲染相关 ---
 
+    /// 主循环节拍：当前标签页与底部组件请求的最短间隔，没有请求时空闲为 TICK_IDLE_MS
+    pub fn tick_interval(&self) -> Duration {
+        self.components
+            .get(self.active_tab)
+            .into_iter()
+            .chain(self.button_components.iter())
+            .filter_map(|c| c.desired_tick_interval())
+            .min()
+            .unwrap_or(Duration::from_millis(TICK_IDLE_MS))
+            .max(Duration::from_millis(self.min_tick_ms()))
+    }
+
+    /// 节拍下限取自 Config.refresh_rate_ms (加载时已校验)，配置被占用时退回 TICK_MIN_MS
+    fn min_tick_ms(&self) -> u64 {
+        self.config
+            .try_read()
+            .map(|cfg| cfg.refresh_rate_ms)
+            .unwrap_or(TICK_MIN_MS)
+            .clamp(TICK_MIN_MS, REFRESH_RATE_MAX_MS)
+    }
+
     pub fn request_render(&mut self) {
         self.re_rend_mark = true;
     }
@@ -249,5 +450,592 @@ impl App {
         self.request_render(); // 必须加入
     }
 
+    /// 退出前停止所有任务，最多等待 SHUTDOWN_TIMEOUT_MS；
+    /// 主循环此时已不再驱动 update，这里手动调用以处理请求
+    pub async fn shutdown(&mut self) {
+        let _ = self.glob_send.send(GlobalEvent::Task(TaskRequest::StopAll));
+        self.update();
+
+        let deadline = Instant::now() + Duration::from_millis(SHUTDOWN_TIMEOUT_MS);
+        while Instant::now() < deadline {
+            let (reply, rx) = Reply::channel();
+            let _ = self.glob_send.send(GlobalEvent::Task(TaskRequest::List(reply)));
+            self.update();
+            // TaskControl 被禁用时没有人回复，不能无限等待
+            let all_stopped = match tokio::time::timeout_at(deadline, rx).await {
+                Ok(Ok(tasks)) => tasks.iter().all(|t| !matches!(t.status, TaskStatusView::Running { .. })),
+                Ok(Err(_)) | Err(_) => true,
+            };
+            if all_stopped {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+
+        // 写入队列中尚未落盘的遥测记录
+        crate::ui::info::TelemetryRecord::flush().await;
+    }
+
+    /// 帧画完后由主循环调用，只在诊断浮层打开时统计 (需要保留上一帧的副本)
+    pub fn record_frame(&mut self, buf: &ratatui::buffer::Buffer) {
+        if self.show_render_stats {
+            self.render_stats.record_frame(buf);
+        }
+    }
 
+    /// 记录一次用户输入 (按键、Resize)；正处于屏保时返回 true 并立即恢复界面
+    pub fn wake(&mut self) -> bool {
+        self.last_input = Instant::now();
+        if self.idle_shown.take().is_some() {
+            self.request_render();
+            return true;
+        }
+        false
+    }
+
+    /// 空闲超过 idle_dim_secs 时应显示的屏保
+    fn idle_screen(&self) -> Option<IdleScreen> {
+        let (secs, screen) = self.config.try_read().map(|c| (c.idle_dim_secs, c.idle_screen)).ok()?;
+        (secs > 0 && self.last_input.elapsed() >= Duration::from_secs(secs)).then_some(screen)
+    }
+
+    /// 恢复终端之前调用：save_on_exit 开启时同步写回配置和界面状态。
+    /// 运行时内不能 blocking_write，退出时基本无人竞争，短暂重试即可；
+    /// 终端即将关闭，失败只写日志
+    pub fn on_exit(&self) {
+        for _ in 0..EXIT_SAVE_RETRIES {
+            if let Ok(mut cfg) = self.config.try_write() {
+                if !cfg.save_on_exit {
+                    return;
+                }
+                cfg.ui_state.tab_order = self.tab_ids.clone();
+                if let Err(e) = cfg.save() {
+                    log::error!("Failed to save config on exit: {}", e);
+                }
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        log::warn!("Config is locked, skipped saving on exit");
+    }
+
+    /// 记录一条通知；忽略纯进度更新 (空文本) 和连续重复的消息
+    fn push_history(&mut self, msg: String, level: StatusLevel) {
+        if msg.is_empty() {
+            return;
+        }
+        if self.notify_history.back().is_some_and(|(_, m, l)| *m == msg && *l == level) {
+            return;
+        }
+        self.notify_history.push_back((Local::now(), msg, level));
+        while self.notify_history.len() > NOTIFY_HISTORY_CAP {
+            self.notify_history.pop_front();
+        }
+    }
+
+    /// 破坏性操作的统一入口：confirm_destructive 关闭时立即执行，否则弹窗等待 'y'
+    pub fn request_confirm(&mut self, req: ConfirmRequest) {
+        let confirm = self.config.try_read().map(|c| c.confirm_destructive).unwrap_or(true);
+        if confirm {
+            self.pending_confirm = Some(req);
+            self.request_render();
+        } else {
+            let _ = self.glob_send.send(*req.action);
+        }
+    }
+
+    /// 终端过小时的提示画面
+    fn render_size_warning(&self, f: &mut Frame, area: Rect) {
+        let lines = vec![
+            Line::from(Span::styled("Terminal too small", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
+            Line::from(format!("{}x{} (need {}x{})", area.width, area.height, MIN_TERM_WIDTH, MIN_TERM_HEIGHT)),
+        ];
+        let top = area.height.saturating_sub(lines.len() as u16) / 2;
+        let [_, center] = Layout::vertical([Constraint::Length(top), Constraint::Min(0)]).areas(area);
+        f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), center);
+    }
+
+    /// 通知历史浮层：最新的在最上面
+    fn render_history(&self, f: &mut Frame, area: Rect) {
+        let bus = GlobIO::stats();
+        let popup = area.inner(ratatui::layout::Margin { horizontal: area.width / 10, vertical: 1 });
+        let lines: Vec<Line> = self
+            .notify_history
+            .iter()
+            .rev()
+            .map(|(t, msg, level)| {
+                let color = match level {
+                    StatusLevel::Info => Color::Cyan,
+                    StatusLevel::Success => Color::Green,
+                    StatusLevel::Warning => Color::Yellow,
+                    StatusLevel::Error => Color::Red,
+                };
+                Line::from(vec![
+                    Span::styled(t.format("%H:%M:%S ").to_string(), Style::default().fg(Color::DarkGray)),
+                    Span::styled(msg.clone(), Style::default().fg(color)),
+                ])
+            })
+            .collect();
+
+        f.render_widget(Clear, popup);
+        f.render_widget(
+            Paragraph::new(if lines.is_empty() { vec![Line::from(" (no notifications) ")] } else { lines })
+                .scroll((self.history_scroll, 0))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(Color::Yellow))
+                        .title(format!(" 🔔 Notifications ({}) | ↑↓ scroll | 'c' clear | Esc close ", self.notify_history.len()))
+                        .title_bottom(format!(
+                            " bus: {} rx, cap {}, dropped {} ",
+                            bus.receivers, bus.capacity, bus.lagged
+                        )),
+                ),
+            popup,
+        );
+    }
+
+    /// 将当前标签与相邻标签交换位置 (components 与 tab_ids 同步)，并持久化顺序
+    pub fn move_active_tab(&mut self, dir: isize) -> bool {
+        let Some(target) = self.active_tab.checked_add_signed(dir) else {
+            return false;
+        };
+        if target >= self.components.len() {
+            return false;
+        }
+        self.components.swap(self.active_tab, target);
+        self.tab_ids.swap(self.active_tab, target);
+        self.active_tab = target;
+        self.request_render();
+
+        if let Ok(mut cfg) = self.config.try_write() {
+            cfg.ui_state.tab_order = self.tab_ids.clone();
+            if let Err(e) = cfg.save() {
+                GlobIO::error(format!("Failed to save tab order: {}", e));
+            }
+        }
+        true
+    }
+}
+
+/// 屏保时钟：黑底居中显示，只占一行，避免长时间点亮大面积像素
+fn render_clock(f: &mut Frame, area: Rect, clock: &str) {
+    f.render_widget(Clear, area);
+    let [_, row, _] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1), Constraint::Fill(1)]).areas(area);
+    f.render_widget(
+        Paragraph::new(clock.to_string())
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::DarkGray)),
+        row,
+    );
+}
+
+/// 屏保 Dim：整帧降为暗灰色并去掉背景色
+fn dim_buffer(buf: &mut ratatui::buffer::Buffer) {
+    for cell in buf.content.iter_mut() {
+        cell.set_fg(Color::DarkGray);
+        cell.set_bg(Color::Reset);
+        cell.modifier.insert(Modifier::DIM);
+    }
+}
+
+/// 驱动标签页组件：所有页都消费订阅 (drain)，后台页的 update 按 update_background_tabs 跳过；
+/// 切换到某页后它每拍都会 update，后台期间的数据已由 drain 记录，不会丢失
+fn tick_components(components: &mut [Box<dyn Component>], active: usize, update_background: bool) -> bool {
+    let mut changed = false;
+    for (i, comp) in components.iter_mut().enumerate() {
+        if comp.drain() {
+            changed = true;
+        }
+        if (update_background || i == active) && comp.update() {
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// F12 诊断浮层：内容区右上角
+fn render_stats_overlay(f: &mut Frame, area: Rect, lines: Vec<Line<'static>>) {
+    let width = lines.iter().map(|l| l.width() as u16).max().unwrap_or(0).saturating_add(2).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect::new(area.right().saturating_sub(width), area.y, width, height);
+    f.render_widget(Clear, popup);
+    f.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Magenta))
+                .title(" Render (F12) "),
+        ),
+        popup,
+    );
+}
+
+/// 居中的确认弹窗
+fn render_confirm(f: &mut Frame, area: Rect, prompt: &str) {
+    let width = (prompt.chars().count() as u16 + 6).clamp(30, area.width);
+    let [popup] = Layout::horizontal([Constraint::Length(width)]).flex(ratatui::layout::Flex::Center).areas(area);
+    let [popup] = Layout::vertical([Constraint::Length(4)]).flex(ratatui::layout::Flex::Center).areas(popup);
+    f.render_widget(Clear, popup);
+    f.render_widget(
+        Paragraph::new(vec![
+            Line::from(prompt.to_string()),
+            Line::from(Span::styled("[y] confirm   [n/Esc] cancel", Style::default().fg(Color::DarkGray))),
+        ])
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Red))
+                .title(" ⚠ Confirm "),
+        ),
+        popup,
+    );
+}
+
+/// 命令行 (含程序名) 中是否带有 `--demo`
+fn demo_requested(args: impl IntoIterator<Item = String>) -> bool {
+    args.into_iter().skip(1).any(|a| a == "--demo")
+}
+
+/// `--demo`：循环广播假进度 (每 DEMO_PROGRESS_MS 一步)，用于调试底部进度条
+fn spawn_demo_progress() {
+    const DEMO_PROGRESS_MS: u64 = 100;
+    tokio::spawn(async move {
+        let glob_send = GlobIO::send();
+        let mut p = 0;
+        loop {
+            p = (p + 1) % 101;
+            let _ = glob_send.send(GlobalEvent::Status(Default::default(), StatusLevel::Info, Some(Progress::Percent(p))));
+            tokio::time::sleep(Duration::from_millis(DEMO_PROGRESS_MS)).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::DynamicPayload;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const PROBE_KEY: &str = "tick_probe";
+
+    /// 不含标签页的 App，底部组件照常订阅总线
+    fn app(config: Config) -> App {
+        GlobIO::init_for_test();
+        App::with_components(Arc::new(tokio::sync::RwLock::new(config)), Vec::new(), Vec::new(), button_components_init())
+    }
+
+    /// 记录收到的探测消息数和 update 次数
+    struct Probe {
+        recv: GlobRecv,
+        drained: Arc<AtomicUsize>,
+        updates: Arc<AtomicUsize>,
+        tick: Option<Duration>,
+    }
+
+    impl Component for Probe {
+        fn init() -> Self {
+            Self {
+                recv: GlobIO::recv(),
+                drained: Arc::default(),
+                updates: Arc::default(),
+                tick: None,
+            }
+        }
+
+        fn drain(&mut self) -> bool {
+            while let Some(ev) = GlobIO::try_recv(&mut self.recv) {
+                if matches!(ev, GlobalEvent::Data { key: PROBE_KEY, .. }) {
+                    self.drained.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            false
+        }
+
+        fn update(&mut self) -> bool {
+            self.updates.fetch_add(1, Ordering::Relaxed);
+            false
+        }
+
+        fn render(&mut self, _f: &mut Frame, _area: Rect) {}
+
+        fn handle_key(&mut self, _key: KeyEvent) -> bool {
+            false
+        }
+
+        fn desired_tick_interval(&self) -> Option<Duration> {
+            self.tick
+        }
+    }
+
+    #[test]
+    fn inactive_tabs_are_drained_but_not_updated() {
+        GlobIO::init_for_test();
+        let probes = [Probe::init(), Probe::init()];
+        let counters: Vec<_> = probes.iter().map(|p| (p.drained.clone(), p.updates.clone())).collect();
+        let mut components: Vec<Box<dyn Component>> =
+            probes.into_iter().map(|p| Box::new(p) as Box<dyn Component>).collect();
+        let count = |i: usize| (counters[i].0.load(Ordering::Relaxed), counters[i].1.load(Ordering::Relaxed));
+
+        for _ in 0..3 {
+            let _ = GlobIO::send().send(GlobalEvent::Data { key: PROBE_KEY, data: DynamicPayload(Arc::new(())) });
+        }
+        tick_components(&mut components, 0, false);
+        assert_eq!(count(0), (3, 1));
+        assert_eq!(count(1), (3, 0), "background tab must still consume its subscription");
+
+        tick_components(&mut components, 1, false);
+        assert_eq!(count(0), (3, 1));
+        assert_eq!(count(1), (3, 1));
+
+        tick_components(&mut components, 1, true);
+        assert_eq!(count(0), (3, 2));
+        assert_eq!(count(1), (3, 2));
+    }
+
+    #[test]
+    fn notification_history_trims_oldest() {
+        let mut app = app(Config::default());
+        for i in 0..NOTIFY_HISTORY_CAP + 5 {
+            app.push_history(format!("msg {}", i), StatusLevel::Info);
+        }
+        assert_eq!(app.notify_history.len(), NOTIFY_HISTORY_CAP);
+        assert_eq!(app.notify_history.front().unwrap().1, "msg 5");
+        let last = format!("msg {}", NOTIFY_HISTORY_CAP + 4);
+        assert_eq!(app.notify_history.back().unwrap().1, last);
+
+        // 连续重复的消息和纯进度更新不入历史
+        app.push_history(last.clone(), StatusLevel::Info);
+        app.push_history(String::new(), StatusLevel::Info);
+        assert_eq!(app.notify_history.back().unwrap().1, last);
+        assert_eq!(app.notify_history.len(), NOTIFY_HISTORY_CAP);
+        app.push_history(last.clone(), StatusLevel::Error);
+        assert_eq!(app.notify_history.back().unwrap().2, StatusLevel::Error);
+        assert_eq!(app.notify_history.front().unwrap().1, "msg 6");
+    }
+
+    /// 渲染到 TestBackend，按行返回文本
+    fn screen(app: &mut App, width: u16, height: u16) -> Vec<String> {
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| app.render(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        (0..height).map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect()).collect()
+    }
+
+    #[test]
+    fn small_terminal_warns_or_switches_to_compact() {
+        let tabbed = |on_small_terminal| {
+            GlobIO::init_for_test();
+            let config = Config { on_small_terminal, ..Config::default() };
+            let tabs: Vec<Box<dyn Component>> = vec![Box::new(Probe::init())];
+            App::with_components(Arc::new(tokio::sync::RwLock::new(config)), vec![TabId::Welcome], tabs, button_components_init())
+        };
+
+        let mut warn = tabbed(SmallTerminal::Warn);
+        let rows = screen(&mut warn, 40, 10);
+        assert!(rows.iter().any(|r| r.contains("Terminal too small")));
+        assert!(rows.iter().any(|r| r.contains("40x10")));
+        assert!(!rows.iter().any(|r| r.contains("Welcome")));
+
+        // 紧凑布局：标签栏只占一行且没有边框
+        let mut compact = tabbed(SmallTerminal::Compact);
+        let rows = screen(&mut compact, 40, 10);
+        assert!(rows[0].contains("Welcome"));
+        assert!(!rows.iter().any(|r| r.contains("Terminal too small") || r.contains(APP_TITLE.trim())));
+
+        // 尺寸足够时两种设置都按正常布局渲染
+        let rows = screen(&mut warn, MIN_TERM_WIDTH, MIN_TERM_HEIGHT);
+        assert!(rows[0].contains(APP_TITLE.trim()));
+        assert!(rows[1].contains("Welcome"));
+    }
+
+    #[test]
+    fn active_tab_sets_the_tick_interval() {
+        GlobIO::init_for_test();
+        let animated = Probe { tick: Some(Duration::from_millis(100)), ..Probe::init() };
+        let tabs: Vec<Box<dyn Component>> = vec![Box::new(animated), Box::new(Probe::init())];
+        let config = Config { refresh_rate_ms: TICK_MIN_MS, ..Config::default() };
+        let mut app = App::with_components(
+            Arc::new(tokio::sync::RwLock::new(config)),
+            vec![TabId::Welcome, TabId::Settings],
+            tabs,
+            button_components_init(),
+        );
+
+        assert_eq!(app.tick_interval(), Duration::from_millis(100));
+        app.active_tab = 1;
+        assert_eq!(app.tick_interval(), Duration::from_millis(TICK_IDLE_MS));
+
+        // 请求的间隔不会低于 refresh_rate_ms
+        app.active_tab = 0;
+        app.config.try_write().unwrap().refresh_rate_ms = 250;
+        assert_eq!(app.tick_interval(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn destructive_action_waits_for_y_only_when_confirming() {
+        const KEY: &str = "confirm_probe";
+        let request = || ConfirmRequest {
+            prompt: "Drop table?".into(),
+            action: Box::new(GlobalEvent::Data { key: KEY, data: DynamicPayload(Arc::new(())) }),
+        };
+        let executed = |recv: &mut GlobRecv| {
+            let mut n = 0;
+            while let Some(ev) = GlobIO::try_recv(recv) {
+                n += matches!(ev, GlobalEvent::Data { key: KEY, .. }) as usize;
+            }
+            n
+        };
+
+        let mut fast = app(Config { confirm_destructive: false, ..Config::default() });
+        let mut recv = GlobIO::recv();
+        fast.request_confirm(request());
+        assert!(fast.pending_confirm.is_none());
+        assert_eq!(executed(&mut recv), 1);
+
+        let mut safe = app(Config { confirm_destructive: true, ..Config::default() });
+        safe.request_confirm(request());
+        assert_eq!(executed(&mut recv), 0);
+        // 其它按键不关闭弹窗，y 才执行
+        assert!(safe.handle_key(KeyEvent::from(KeyCode::Char('x'))));
+        assert!(safe.pending_confirm.is_some());
+        assert_eq!(executed(&mut recv), 0);
+        assert!(safe.handle_key(KeyEvent::from(KeyCode::Char('y'))));
+        assert!(safe.pending_confirm.is_none());
+        assert_eq!(executed(&mut recv), 1);
+
+        // n 取消且不执行
+        safe.request_confirm(request());
+        assert!(safe.handle_key(KeyEvent::from(KeyCode::Char('n'))));
+        assert!(safe.pending_confirm.is_none());
+        assert_eq!(executed(&mut recv), 0);
+    }
+
+    #[test]
+    fn demo_progress_is_opt_in() {
+        let args = |list: &[&str]| list.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert!(!demo_requested(args(&["atlas_prime"])));
+        assert!(!demo_requested(args(&["atlas_prime", "--once", "--json"])));
+        assert!(!demo_requested(args(&["--demo"])), "program name is not a flag");
+        assert!(demo_requested(args(&["atlas_prime", "--safe-mode", "--demo"])));
+    }
+
+    #[test]
+    fn exit_writes_the_dirty_config() {
+        use crate::prelude::AtlasPath;
+        let paths = AtlasPath::init_for_test();
+        let _file_lock = AtlasPath::config_file_lock();
+        let file = AtlasPath::get_config_path();
+        assert!(file.starts_with(&paths.config_dir));
+        let _ = std::fs::remove_file(&file);
+
+        let mut skip = app(Config { save_on_exit: false, refresh_rate_ms: 40, ..Config::default() });
+        skip.tab_ids = vec![TabId::Settings];
+        skip.on_exit();
+        assert!(!file.exists(), "save_on_exit = false must not write");
+
+        // 运行时改过的设置和标签顺序在退出时落盘
+        let mut dirty = app(Config::default());
+        dirty.config.try_write().unwrap().refresh_rate_ms = 40;
+        dirty.tab_ids = vec![TabId::Settings, TabId::Welcome];
+        dirty.on_exit();
+        let saved: Config = serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(saved.refresh_rate_ms, 40);
+        assert_eq!(saved.ui_state.tab_order, vec![TabId::Settings, TabId::Welcome]);
+        let _ = std::fs::remove_file(&file);
+    }
+
+    #[test]
+    fn idle_screensaver_shows_after_threshold_and_reverts_on_input() {
+        let idle_app = |idle_screen| {
+            GlobIO::init_for_test();
+            let config = Config { idle_dim_secs: 60, idle_screen, ..Config::default() };
+            let tabs: Vec<Box<dyn Component>> = vec![Box::new(Probe::init())];
+            App::with_components(Arc::new(tokio::sync::RwLock::new(config)), vec![TabId::Welcome], tabs, button_components_init())
+        };
+        let (w, h) = (MIN_TERM_WIDTH, MIN_TERM_HEIGHT);
+
+        let mut app = idle_app(IdleScreen::Clock);
+        app.update();
+        assert!(app.idle_shown.is_none(), "not idle yet");
+        assert!(screen(&mut app, w, h)[1].contains("Welcome"));
+
+        app.last_input = Instant::now() - Duration::from_secs(61);
+        assert!(app.update());
+        let (shown, clock) = app.idle_shown.clone().expect("screensaver");
+        assert_eq!(shown, IdleScreen::Clock);
+        let rows = screen(&mut app, w, h);
+        assert!(!rows.iter().any(|r| r.contains("Welcome")));
+        assert!(rows.iter().any(|r| r.trim() == clock), "{rows:#?}");
+
+        // 任意按键只用于唤醒，不会传给组件
+        assert!(app.handle_key(KeyEvent::from(KeyCode::Char('q'))));
+        assert!(app.idle_shown.is_none());
+        app.update();
+        assert!(app.idle_shown.is_none());
+        assert!(screen(&mut app, w, h)[1].contains("Welcome"));
+
+        // Dim：保留原界面，整帧变暗
+        let mut app = idle_app(IdleScreen::Dim);
+        app.last_input = Instant::now() - Duration::from_secs(61);
+        app.update();
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(w, h)).unwrap();
+        terminal.draw(|f| app.render(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        assert!(buf.content.iter().all(|c| c.modifier.contains(Modifier::DIM)));
+        assert!(app.wake());
+        assert!(!app.wake());
+    }
+
+    #[test]
+    fn moving_tabs_swaps_neighbours_and_saves_the_order() {
+        crate::prelude::AtlasPath::init_for_test();
+        let _file_lock = crate::prelude::AtlasPath::config_file_lock();
+        GlobIO::init_for_test();
+        // 用不同的 tick 标记每个组件，检查组件与 tab_ids 同步移动
+        let probe = |ms| Box::new(Probe { tick: Some(Duration::from_millis(ms)), ..Probe::init() }) as Box<dyn Component>;
+        let mut app = App::with_components(
+            Arc::new(tokio::sync::RwLock::new(Config::default())),
+            vec![TabId::Welcome, TabId::Info, TabId::Settings],
+            vec![probe(1), probe(2), probe(3)],
+            button_components_init(),
+        );
+        let ticks = |app: &App| -> Vec<u128> {
+            app.components.iter().map(|c| c.desired_tick_interval().unwrap().as_millis()).collect()
+        };
+        let saved = |app: &App| app.config.try_read().unwrap().ui_state.tab_order.clone();
+
+        // 最左端不能再左移，什么都不变
+        assert!(!app.move_active_tab(-1));
+        assert_eq!(app.tab_ids, [TabId::Welcome, TabId::Info, TabId::Settings]);
+        assert_eq!(app.active_tab, 0);
+
+        // 右移：当前标签跟着移动，顺序被保存
+        assert!(app.move_active_tab(1));
+        assert_eq!(app.tab_ids, [TabId::Info, TabId::Welcome, TabId::Settings]);
+        assert_eq!(ticks(&app), [2, 1, 3]);
+        assert_eq!(app.active_tab, 1);
+        assert_eq!(saved(&app), app.tab_ids);
+
+        assert!(app.move_active_tab(1));
+        assert_eq!(app.tab_ids, [TabId::Info, TabId::Settings, TabId::Welcome]);
+        assert_eq!(app.active_tab, 2);
+
+        // 最右端不能再右移
+        assert!(!app.move_active_tab(1));
+        assert_eq!(app.active_tab, 2);
+
+        // 左移回到中间
+        assert!(app.move_active_tab(-1));
+        assert_eq!(app.tab_ids, [TabId::Info, TabId::Welcome, TabId::Settings]);
+        assert_eq!(ticks(&app), [2, 1, 3]);
+        assert_eq!(app.active_tab, 1);
+        assert_eq!(saved(&app), [TabId::Info, TabId::Welcome, TabId::Settings]);
+        let file = crate::prelude::AtlasPath::get_config_path();
+        let on_disk: Config = serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(on_disk.ui_state.tab_order, [TabId::Info, TabId::Welcome, TabId::Settings]);
+        let _ = std::fs::remove_file(&file);
+    }
 }
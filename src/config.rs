@@ -1,4 +1,3 @@
-use directories::ProjectDirs;
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 use smart_default::SmartDefault;
@@ -10,10 +9,22 @@ use std::sync::{Arc, OnceLock};
 use std::env;
 use std::path::{Path, PathBuf};
 
+use crate::constans::{GOLDEN_RATIO_PC, LEGACY_TABS, REFRESH_RATE_MAX_MS, TICK_MIN_MS, TabId};
+use crate::message::StatusLevel;
 use crate::prelude::*;
 
 pub type SharedConfig = Arc<RwLock<Config>>;
 
+// 配置文件不可写的提示 (见 Config::note_read_only)
+static CONFIG_READ_ONLY: OnceLock<String> = OnceLock::new();
+// 安全模式在启动时确定，运行中修改配置不会关闭它 (见 Config::init_safe_mode)
+static SAFE_MODE: OnceLock<bool> = OnceLock::new();
+#[cfg(test)]
+thread_local! {
+    // 测试中按线程覆盖安全模式，避免影响并行运行的其它测试
+    static SAFE_MODE_TEST: std::cell::Cell<Option<bool>> = const { std::cell::Cell::new(None) };
+}
+
 
 
 /*
@@ -72,14 +83,439 @@ impl std::fmt::Display for AppColor {
     }
 }
 
+/// Info 页内存/交换区的"当前值"展示方式
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum MemWidgetStyle {
+    #[default]
+    Sparkline,
+    Gauge,
+}
+
+// 无法识别的值回退为 Sparkline，而不是让整个配置解析失败
+impl<'de> Deserialize<'de> for MemWidgetStyle {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let v = serde_json::Value::deserialize(d)?;
+        Ok(match v.as_str() {
+            Some("Gauge") => Self::Gauge,
+            _ => Self::Sparkline,
+        })
+    }
+}
+
+/// Sparkline 使用的字符集：八级方块 (默认)、盲文点阵、纯 ASCII (字体缺少方块字形时使用)
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SparklineStyle {
+    #[default]
+    Blocks,
+    Braille,
+    Ascii,
+}
+
+// 无法识别的值回退为 Blocks
+impl<'de> Deserialize<'de> for SparklineStyle {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let v = serde_json::Value::deserialize(d)?;
+        Ok(match v.as_str() {
+            Some("Braille") => Self::Braille,
+            Some("Ascii") => Self::Ascii,
+            _ => Self::Blocks,
+        })
+    }
+}
+
+/// 空闲时的显示方式：Dim 整屏变暗，Clock 只显示居中的时钟
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdleScreen {
+    Dim,
+    #[default]
+    Clock,
+}
+
+// 无法识别的值回退为 Clock
+impl<'de> Deserialize<'de> for IdleScreen {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let v = serde_json::Value::deserialize(d)?;
+        Ok(match v.as_str() {
+            Some("Dim") => Self::Dim,
+            _ => Self::Clock,
+        })
+    }
+}
+
+/// Info 页启动时获得焦点的窗格
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InfoPane {
+    #[default]
+    Mounts,
+    Dirs,
+    Ips,
+}
+
+impl InfoPane {
+    /// 对应 InfoComponent.focus_index
+    pub fn index(self) -> usize {
+        self as usize
+    }
+}
+
+// 无法识别的值回退为 Mounts
+impl<'de> Deserialize<'de> for InfoPane {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let v = serde_json::Value::deserialize(d)?;
+        Ok(match v.as_str() {
+            Some("Dirs") => Self::Dirs,
+            Some("Ips") => Self::Ips,
+            _ => Self::Mounts,
+        })
+    }
+}
+
+/// 挂载点列表的排序方式，Auto 保持平台默认 (Windows 按盘符，其余按容量)
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DiskSort {
+    #[default]
+    Auto,
+    Size,
+    Mount,
+    Usage,
+}
+
+impl<'de> Deserialize<'de> for DiskSort {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let v = serde_json::Value::deserialize(d)?;
+        Ok(match v.as_str() {
+            Some("Size") => Self::Size,
+            Some("Mount") => Self::Mount,
+            Some("Usage") => Self::Usage,
+            _ => Self::Auto,
+        })
+    }
+}
+
+/// CPU 频率的显示单位
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FreqUnit {
+    #[default]
+    GHz,
+    MHz,
+}
+
+impl FreqUnit {
+    /// 按单位格式化频率 (输入为 GHz)，例如 "2.4" / "2400"
+    pub fn value(&self, ghz: f32) -> String {
+        match self {
+            Self::GHz => format!("{:.1}", ghz),
+            Self::MHz => format!("{:.0}", ghz * 1000.0),
+        }
+    }
+
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Self::GHz => "GHz",
+            Self::MHz => "MHz",
+        }
+    }
+}
+
+/// 终端小于 MIN_TERM_WIDTH x MIN_TERM_HEIGHT 时的处理方式
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SmallTerminal {
+    /// 只显示尺寸提示，放大后恢复
+    #[default]
+    Warn,
+    /// 切换为紧凑布局：无边框单行标签栏，底部只保留通知
+    Compact,
+}
+
+/// 配置文件不可写 (只读，或符号链接指向只读文件) 时的处理方式
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadOnlyConfig {
+    /// 修改只在本次运行中生效，不写回磁盘
+    #[default]
+    Session,
+    /// 改为写入程序目录下的 atlas_cfg_override.json (之后启动会优先加载它)
+    Override,
+}
+
+// 无法识别的值回退为 Session
+impl<'de> Deserialize<'de> for ReadOnlyConfig {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let v = serde_json::Value::deserialize(d)?;
+        Ok(match v.as_str() {
+            Some("Override") => Self::Override,
+            _ => Self::Session,
+        })
+    }
+}
+
+/// crash.log 中记录的堆栈详细程度 (环境变量 ATLAS_BACKTRACE 优先)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CrashBacktrace {
+    None,
+    /// 只保留最靠近 panic 的若干帧
+    #[default]
+    Short,
+    Full,
+}
+
+impl CrashBacktrace {
+    /// 解析 ATLAS_BACKTRACE 的取值：none/0, short/1, full/2 (不区分大小写)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "none" | "off" | "0" => Some(Self::None),
+            "short" | "1" => Some(Self::Short),
+            "full" | "2" => Some(Self::Full),
+            _ => None,
+        }
+    }
+}
+
+/// Info 页健康度评分中各维度的权重 (不必归一化)
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, SmartDefault)]
+#[serde(default)]
+pub struct HealthWeights {
+    #[default(0.3)]
+    pub cpu_temp: f64,
+    #[default(0.3)]
+    pub memory: f64,
+    #[default(0.25)]
+    pub disk: f64,
+    #[default(0.15)]
+    pub battery: f64,
+}
+
+/// 告警规则：metric (cpu_temp / mem_pct / battery_pct / disk_pct) op (> < >= <=) value 时
+/// 以 level 发出状态通知；同一规则在 cooldown_secs 内不重复触发。
+/// 读不到的指标 (无温度传感器、termux-api 不可用) 本次不参与判断；
+/// disk_pct 只统计可写的普通磁盘 (不含只读、loop、squashfs 等始终 100% 的挂载)
+#[derive(Serialize, Deserialize, Debug, Clone, SmartDefault)]
+#[serde(default)]
+pub struct AlertRule {
+    pub metric: String,
+    #[default(">".to_string())]
+    pub op: String,
+    pub value: f64,
+    #[default(StatusLevel::Warning)]
+    pub level: StatusLevel,
+    #[default(600)]
+    pub cooldown_secs: u64,
+}
+
+fn legacy_known_tabs() -> Vec<String> {
+    LEGACY_TABS.iter().map(|s| s.to_string()).collect()
+}
+
+// 无法识别的标签名 (新版本的标签页、手误) 直接忽略，不让整个配置解析失败
+fn lenient_tab_order<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Vec<TabId>, D::Error> {
+    let v = serde_json::Value::deserialize(d)?;
+    Ok(v.as_array()
+        .map(|names| names.iter().filter_map(|n| n.as_str().and_then(TabId::from_name)).collect())
+        .unwrap_or_default())
+}
+
+impl AlertRule {
+    fn new(metric: &str, op: &str, value: f64, level: StatusLevel, cooldown_secs: u64) -> Self {
+        Self { metric: metric.into(), op: op.into(), value, level, cooldown_secs }
+    }
+}
+
+/// 运行时产生、需要跨次启动保留的界面状态
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct UiState {
+    /// 用户调整后的标签页顺序 (Ctrl+Shift+←/→)
+    #[serde(deserialize_with = "lenient_tab_order")]
+    pub tab_order: Vec<TabId>,
+    /// Info 页使用紧凑的文字摘要代替 sparkline ('v' 切换)
+    pub info_summary: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, SmartDefault)]
+#[serde(default)] // 旧配置文件缺少的字段用默认值补全
 pub struct Config {
     #[default(AppColor::Black)]
     pub background_color: AppColor,
     #[default(AppColor::White)]
     pub theme_color: AppColor,
+    /// 渲染节拍下限 (毫秒)，加载和在设置页修改时限制在 8–1000
     #[default(8)]
     pub refresh_rate_ms: u64,
+    /// 任务日志中 stderr 行的颜色
+    #[default(AppColor::Red)]
+    pub stderr_color: AppColor,
+    /// 任务日志去掉 ANSI 转义只显示纯文本 (程序输出的颜色在当前终端显示异常时开启)
+    pub strip_ansi: bool,
+    /// Info 页 IP 窗格中 v4 / v6 地址的颜色
+    #[default(AppColor::Cyan)]
+    pub ipv4_color: AppColor,
+    #[default(AppColor::Green)]
+    pub ipv6_color: AppColor,
+    /// v6 地址按规范形式压缩 (连续 0 段写成 ::) 并去掉 %zone 后缀
+    #[default(true)]
+    pub ipv6_abbreviate: bool,
+    /// DB 标签页自动刷新表统计的间隔 (秒)
+    #[default(16)]
+    pub db_refresh_secs: u64,
+    /// 强制单色渲染 (等同于设置 NO_COLOR)
+    pub force_monochrome: bool,
+    /// 焦点窗格标题加 `▸` 前缀并反色，不只依赖边框颜色 (色弱/单色模式下仍可辨认)
+    #[default(true)]
+    pub focus_marker: bool,
+    /// 列表/表格选中行前的标记，例如 "→ " 或 "● "
+    #[default(">> ".to_string())]
+    pub selection_symbol: String,
+    /// 后台标签页是否也调用 update；关闭时只更新当前页 (切换过来时补一次)，
+    /// 广播订阅 (Component::drain) 无论如何都会被消费以免 Lagged
+    #[default(true)]
+    pub update_background_tabs: bool,
+    /// Ctrl+S 屏幕快照时额外输出带颜色的 .ans 文件
+    #[default(true)]
+    pub screen_dump_ansi: bool,
+    /// 手动启动任务后把列表选中项移到该任务
+    #[default(true)]
+    pub focus_on_start: bool,
+    /// focus_on_start 时同时切换到该任务的全屏日志
+    pub log_on_start: bool,
+    /// Info 页启动时聚焦的窗格 (Mounts / Dirs / Ips)，无效值按 Mounts 处理
+    pub info_default_focus: InfoPane,
+    /// 挂载点列表排序 (Auto / Size / Mount / Usage)
+    pub disk_sort: DiskSort,
+    /// 任务启动后在此时间内退出视为"秒退"；连续 flapping_threshold 次秒退即判定为 flapping，
+    /// 不再自动重启 (即使 RestartPolicy::Always)
+    #[default(2000)]
+    pub flapping_window_ms: u64,
+    #[default(3)]
+    pub flapping_threshold: u32,
+    /// 任务的 stderr 与 stdout 共用一个管道 (2>&1)：输出严格按时间顺序，但不再区分来源
+    /// (没有 [ERR] 标记和 stderr 颜色，flapping 报告也取不到 stderr 尾部)。仅 Unix 生效
+    pub merge_task_output: bool,
+    /// UI 看门狗：主循环超过 N 秒没有心跳时写日志，0 表示关闭
+    #[default(15)]
+    pub watchdog_timeout_secs: u64,
+    /// 看门狗触发时以 panic 退出 (写 crash.log)，否则只记录日志
+    #[default(true)]
+    pub watchdog_abort: bool,
+    /// 退出时把当前配置和界面状态 (标签顺序等) 写回配置文件，避免运行中的修改丢失
+    #[default(true)]
+    pub save_on_exit: bool,
+    /// 监控任务每次采样后按规则检查，详见 AlertRule；清空即关闭告警
+    #[default(vec![
+        AlertRule::new("cpu_temp", ">", 85.0, StatusLevel::Warning, 600),
+        AlertRule::new("mem_pct", ">=", 95.0, StatusLevel::Warning, 600),
+        AlertRule::new("battery_pct", "<", 15.0, StatusLevel::Warning, 900),
+        AlertRule::new("disk_pct", ">=", 95.0, StatusLevel::Error, 3600),
+    ])]
+    pub alerts: Vec<AlertRule>,
+    /// 自动启动任务之间的间隔 (毫秒)，避免启动时 CPU/IO 峰值
+    #[default(500)]
+    pub autostart_stagger_ms: u64,
+    pub ui_state: UiState,
+    /// 启用的标签页及默认顺序：Welcome, TaskControl, Info, SQL, Settings。
+    /// 可选页 Processes 默认不启用，加入此列表即可开启。
+    /// 禁用 TaskControl 时不会加载任务，也不会自动启动任何进程
+    #[default(TabId::defaults().map(|t| t.name().to_string()).collect())]
+    pub enabled_tabs: Vec<String>,
+    /// 已经见过的标签页：新版本加入的默认标签页只会自动加入 enabled_tabs 一次，之后移除不会再加回
+    #[default(TabId::all().map(|t| t.name().to_string()).collect())]
+    #[serde(default = "legacy_known_tabs")]
+    pub known_tabs: Vec<String>,
+    pub mem_widget_style: MemWidgetStyle,
+    /// Sparkline 字符集：Blocks / Braille / Ascii
+    pub sparkline_style: SparklineStyle,
+    /// 超过 N 秒没有按键后进入屏保 (减少 OLED 烧屏)，0 表示关闭；后台采集不受影响
+    pub idle_dim_secs: u64,
+    /// 屏保样式：Dim / Clock
+    pub idle_screen: IdleScreen,
+    /// Info 页 CPU 频率单位：GHz / MHz
+    pub freq_unit: FreqUnit,
+    /// 行数、容量等数字是否显示千分位分隔符
+    #[default(true)]
+    pub group_digits: bool,
+    /// 全局事件总线容量，任务日志很多时可调大以减少 Lagged 丢消息 (启动时生效)
+    #[default(1024)]
+    pub event_bus_capacity: usize,
+    /// 公网 IP 在此秒数内仍未获取到则显示 "Unavailable (timeout)"，0 表示一直等待。
+    /// 后台仍按退避继续重试，IP 面板中按 'p' 可立即重试
+    #[default(30)]
+    pub public_ip_timeout_secs: u64,
+    /// 同时进行的出站网络请求上限 (公网 IP、反向 DNS)，其余排队 (启动时生效)
+    #[default(2)]
+    pub max_net_concurrency: usize,
+    /// Info 页 CPU 温度曲线的纵轴范围 [下限, 上限] (°C)，超出部分贴边显示
+    #[default((10.0, 90.0))]
+    pub cpu_temp_range: (f64, f64),
+    /// Info 页电池温度曲线的纵轴范围 [下限, 上限] (°C)
+    #[default((20.0, 50.0))]
+    pub batt_temp_range: (f64, f64),
+    /// 视为 CPU 温度的 thermal_zone type 关键字 (不区分大小写)，取匹配分区的最大值
+    #[default(vec!["cpu".into(), "soc".into(), "tsens".into()])]
+    pub thermal_zone_types: Vec<String>,
+    /// Info 页 IP 列表中隐藏的网卡 (前缀匹配，支持 '*' 通配)
+    #[default(vec!["lo".into(), "docker".into(), "veth".into(), "br-".into()])]
+    pub hide_interfaces: Vec<String>,
+    /// Info 页 IP 列表合并相同的地址 (别名网卡、重复的链路本地 v6)，显示为 "eth0, eth0:1: ip"
+    #[default(true)]
+    pub ip_dedup: bool,
+    /// Info 页 IP 列表按网卡分组：同一网卡的地址相邻显示，只在第一条显示网卡名
+    #[default(false)]
+    pub ip_group_by_interface: bool,
+    /// 把本地/公网 IP 的变化写入 ip_history 表 (只在变化时记录)，Info 页 IP 窗格按 'H' 查看
+    #[default(true)]
+    pub ip_history: bool,
+    /// 自定义欢迎页 logo 的文本文件路径，缺失或无法读取时使用内置 logo
+    pub custom_logo_path: Option<String>,
+    /// Info 页磁盘列表显示的列及顺序：bar, pct, total, used, avail, mount, fs, name
+    #[default(vec!["bar".into(), "pct".into(), "total".into(), "mount".into(), "name".into()])]
+    pub disk_columns: Vec<String>,
+    /// 遥测记录批量写入 SQLite 的间隔 (秒)，减少闪存写入次数
+    #[default(300)]
+    pub db_flush_secs: u64,
+    /// 维护时把早于 N 天的遥测记录移入 telemetry_archive，归档表中早于 M 天的记录删除
+    #[default(30)]
+    pub telemetry_retain_days: u32,
+    #[default(365)]
+    pub telemetry_archive_days: u32,
+    /// 覆盖欢迎页的副标题与帮助提示 (未设置时使用内置文字)
+    pub welcome_message: Option<String>,
+    pub help_prompt: Option<String>,
+    /// 欢迎页 logo 所在的上半部分占比 (%)，范围 10-90
+    #[default(GOLDEN_RATIO_PC)]
+    pub golden_ratio_pc: u16,
+    /// 顶部标签栏与底部状态栏的高度 (行)
+    #[default(3)]
+    pub tab_bar_height: u16,
+    #[default(1)]
+    pub footer_height: u16,
+    pub health_weights: HealthWeights,
+    /// scripts 目录下 .ts 脚本的 deno 参数，`{script}` 替换为脚本路径
+    #[default(vec!["run".into(), "-A".into(), "--unstable-kv".into(), "--unstable-cron".into(), "{script}".into()])]
+    pub deno_args: Vec<String>,
+    /// 首次创建 script 目录时写入示例脚本 hello.ts 和 README (目录已存在或非空时不写)
+    #[default(true)]
+    pub seed_scripts: bool,
+    /// 任务列表的分组顺序 (例如 ["Scripts", "System"])，组内保持原顺序；
+    /// 未列出的分组按字母序排在后面，留空则保持加载顺序 (脚本在最后)
+    pub task_group_order: Vec<String>,
+    /// 检测到另一个 Atlas 实例正在使用同一数据目录时，本实例不再写入数据库
+    #[default(true)]
+    pub second_instance_read_only: bool,
+    /// 安全模式：只做只读监控，禁止启动任务进程 (含 autostart)、不扫描 Deno 脚本、
+    /// 不注册写入 / AI 查询接口。也可用 `--safe-mode` 启动；仅在启动时读取
+    pub safe_mode: bool,
+    /// AI 查询接口是否允许一次提交多条 (只读) 语句
+    pub api_allow_multi_statement: bool,
+    /// 终端过小时的行为：Warn (提示) / Compact (紧凑布局)
+    pub on_small_terminal: SmallTerminal,
+    /// 删除 / VACUUM / 停止任务等破坏性操作前是否弹窗确认 ('y' 执行)
+    #[default(true)]
+    pub confirm_destructive: bool,
+    /// 写入 atlas.log 的最低级别：off / error / warn / info / debug / trace
+    #[default("info".to_string())]
+    pub log_level: String,
+    /// panic 时写入 crash.log 的堆栈：None / Short / Full
+    pub crash_backtrace: CrashBacktrace,
+    /// 配置文件不可写 (符号链接按其目标判断) 时：Session (只在本次运行生效) / Override (写入 override 文件)
+    pub on_readonly_config: ReadOnlyConfig,
     //pub cpu_affinity: Option<usize>,
 
     // 扩展参数（弱类型，用于存储动态增加或插件化的配置）
@@ -111,17 +547,13 @@ fn default_refresh_rate() -> u64 { 30 }
 */
 
 impl Config {
-    // fn get_path() -> Option<PathBuf> {
-    //     ProjectDirs::from("", "", "atlas").map(|p| {
-    //         let data_dir = p.data_dir().to_path_buf();
-    //         // 确保数据目录存在（如果不存在则创建）
-    //         let _ = fs::create_dir_all(&data_dir);
-    //         data_dir.join("config.json")
-    //     })
-    // }
-
     /// 损坏处理：备份并重置
     fn handle_broken_config(path: &PathBuf) {
+        // 只读/符号链接的文件不改名，避免破坏共享的配置
+        if let Some(reason) = Self::write_blocker(path) {
+            log::error!("Config {:?} is broken but {}, leaving it untouched", path, reason);
+            return;
+        }
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .map(|d| d.as_secs())
@@ -153,7 +585,13 @@ impl Config {
             Ok(content) => {
                 match serde_json::from_str::<Self>(&content) {
                     Ok(mut config) => {
-                        // 可以在这里对加载后的配置做一些运行时校验
+                        config.validate();
+                        if let Some(reason) = Self::write_blocker(path) {
+                            Self::note_read_only(path, reason, config.on_readonly_config);
+                        }
+                        if config.adopt_new_tabs() {
+                            let _ = config.save();
+                        }
                         config
                     }
                     Err(e) => {
@@ -161,7 +599,7 @@ impl Config {
                         if !path.to_string_lossy().contains("override") {
                             Self::handle_broken_config(path);
                         }
-                        eprintln!("Config error at {:?}: {}", path, e);
+                        log::error!("Config error at {:?}: {}", path, e);
                         Self::default()
                     }
                 }
@@ -171,7 +609,28 @@ impl Config {
     }
 
 
+    /// 热重载用：只读取并解析配置文件，没有任何副作用。解析失败时返回错误信息，
+    /// 调用方保留当前配置 (编辑器保存到一半、手误都不应触发 handle_broken_config)
+    pub fn reload_from_disk() -> Result<Self, String> {
+        Self::reload_from(&AtlasPath::get_config_path())
+    }
+
+    /// 从指定文件读取并校验配置，失败时返回带路径的错误描述
+    pub fn reload_from(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut config =
+            serde_json::from_str::<Self>(&content).map_err(|e| format!("{}: {}", path.display(), e))?;
+        config.validate();
+        Ok(config)
+    }
+
     fn backup_and_recreate(path: &PathBuf) -> std::io::Result<()> {
+        if let Some(reason) = Self::write_blocker(path) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!("config file is {}", reason),
+            ));
+        }
         let timestamp = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -190,8 +649,141 @@ impl Config {
 
         Ok(())
     }
+
+    /// 把旧配置没见过的默认标签页 (如 Settings) 追加到 enabled_tabs，并记入 known_tabs；
+    /// 有变化时返回 true (调用方负责保存)
+    pub fn adopt_new_tabs(&mut self) -> bool {
+        let mut changed = false;
+        for id in TabId::all() {
+            let name = id.name();
+            if self.known_tabs.iter().any(|k| k.eq_ignore_ascii_case(name)) {
+                continue;
+            }
+            if id.spec().default_enabled && !self.enabled_tabs.iter().any(|t| t.eq_ignore_ascii_case(name)) {
+                log::info!("New tab '{}' added to enabled_tabs", name);
+                self.enabled_tabs.push(name.to_string());
+            }
+            self.known_tabs.push(name.to_string());
+            changed = true;
+        }
+        changed
+    }
+
+    /// 加载后的运行时校验：超出范围的值被修正并写日志，返回是否有修改
+    pub fn validate(&mut self) -> bool {
+        let mut changed = false;
+        let rate = self.refresh_rate_ms.clamp(TICK_MIN_MS, REFRESH_RATE_MAX_MS);
+        if rate != self.refresh_rate_ms {
+            log::warn!(
+                "refresh_rate_ms {} out of range {}-{}, using {}",
+                self.refresh_rate_ms, TICK_MIN_MS, REFRESH_RATE_MAX_MS, rate
+            );
+            self.refresh_rate_ms = rate;
+            changed = true;
+        }
+        // 温度范围必须是有效的 [下限, 上限]，否则回退默认值
+        let defaults = Self::default();
+        for (name, range, default) in [
+            ("cpu_temp_range", &mut self.cpu_temp_range, defaults.cpu_temp_range),
+            ("batt_temp_range", &mut self.batt_temp_range, defaults.batt_temp_range),
+        ] {
+            if !(range.0.is_finite() && range.1.is_finite() && range.0 < range.1) {
+                log::warn!("{} {:?} is not a valid [low, high] range, using {:?}", name, range, default);
+                *range = default;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// 布局参数统一在这里限制到合理范围，避免配置错误导致界面不可用
+    pub fn golden_ratio(&self) -> u16 {
+        self.golden_ratio_pc.clamp(10, 90)
+    }
+
+    pub fn tab_bar_rows(&self) -> u16 {
+        self.tab_bar_height.clamp(3, 5) // 带边框的 Tabs 至少需要 3 行
+    }
+
+    pub fn footer_rows(&self) -> u16 {
+        self.footer_height.clamp(1, 3)
+    }
+
+    /// 配置文件 (符号链接则为其目标) 无法以写方式打开时返回原因；文件不存在视为可写。
+    /// 实际尝试打开而不是看权限位，这样文件属主和只读挂载也会被考虑到
+    fn write_blocker(path: &Path) -> Option<&'static str> {
+        let err = fs::OpenOptions::new().write(true).open(path).err()?;
+        if err.kind() == std::io::ErrorKind::NotFound {
+            return None;
+        }
+        let is_link = fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink());
+        Some(if is_link { "a symlink to a read-only file" } else { "read-only" })
+    }
+
+    /// 记录一次配置不可写的提示 (日志 + 启动后的通知)，同一次运行只记录一次
+    fn note_read_only(path: &Path, reason: &str, mode: ReadOnlyConfig) {
+        CONFIG_READ_ONLY.get_or_init(|| {
+            let msg = match mode {
+                ReadOnlyConfig::Session => {
+                    format!("Config {} is {}: changes are kept for this session only", path.display(), reason)
+                }
+                ReadOnlyConfig::Override => format!(
+                    "Config {} is {}: changes are saved to {}",
+                    path.display(),
+                    reason,
+                    AtlasPath::get_config_override_path().display()
+                ),
+            };
+            log::warn!("{}", msg);
+            msg
+        });
+    }
+
+    /// 配置文件不可写时的提示 (没有则为 None)，App 启动后显示在通知栏
+    pub fn read_only_notice() -> Option<&'static str> {
+        CONFIG_READ_ONLY.get().map(String::as_str)
+    }
+
+    /// 启动时确定是否进入安全模式 (`--safe-mode` 或 Config.safe_mode)，须在 Config::init 之后调用
+    pub fn init_safe_mode(cli_flag: bool) {
+        let on = cli_flag || Config::get().try_read().map(|c| c.safe_mode).unwrap_or(false);
+        if SAFE_MODE.set(on).is_ok() && on {
+            log::info!("Safe mode: process execution is disabled");
+        }
+    }
+
+    /// 是否处于安全模式；未初始化时按关闭处理
+    pub fn safe_mode() -> bool {
+        #[cfg(test)]
+        if let Some(on) = SAFE_MODE_TEST.get() {
+            return on;
+        }
+        SAFE_MODE.get().copied().unwrap_or(false)
+    }
+
+    #[cfg(test)]
+    pub fn set_safe_mode_for_test(on: bool) {
+        SAFE_MODE_TEST.set(Some(on));
+    }
+
+    /// 写回磁盘；配置文件不可写时按 on_readonly_config 处理，
+    /// Session 模式下直接返回 Ok (修改保留在内存中)
     pub fn save(&self) -> std::io::Result<()> {
-        let path = AtlasPath::get_config_path();
+        let mut path = AtlasPath::get_config_path();
+        if let Some(reason) = Self::write_blocker(&path) {
+            Self::note_read_only(&path, reason, self.on_readonly_config);
+            let override_path = AtlasPath::get_config_override_path();
+            // override 文件本身不可写时只能保留在内存中
+            if self.on_readonly_config == ReadOnlyConfig::Session || override_path == path {
+                return Ok(());
+            }
+            path = override_path;
+        }
+        self.write_to(&path)
+    }
+
+    /// 以 pretty JSON 写入指定文件 (父目录不存在时创建)
+    pub fn write_to(&self, path: &Path) -> std::io::Result<()> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -201,3 +793,101 @@ impl Config {
 
 
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_readonly_mode_falls_back_to_session() {
+        let cfg: Config = serde_json::from_str(r#"{"on_readonly_config": "Bogus"}"#).unwrap();
+        assert_eq!(cfg.on_readonly_config, ReadOnlyConfig::Session);
+        let cfg: Config = serde_json::from_str(r#"{"on_readonly_config": "Override"}"#).unwrap();
+        assert_eq!(cfg.on_readonly_config, ReadOnlyConfig::Override);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn writable_symlink_is_not_blocked() {
+        use std::os::unix::fs::{PermissionsExt, symlink};
+        let dir = std::env::temp_dir().join(format!("atlas_write_blocker_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("dotfiles_cfg.json");
+        let link = dir.join("atlas_cfg.json");
+        fs::write(&target, "{}").unwrap();
+        symlink(&target, &link).unwrap();
+
+        assert_eq!(Config::write_blocker(&dir.join("missing.json")), None);
+        assert_eq!(Config::write_blocker(&target), None);
+        assert_eq!(Config::write_blocker(&link), None);
+
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o444)).unwrap();
+        // root 不受权限位限制，此时文件确实可写
+        if fs::OpenOptions::new().write(true).open(&target).is_err() {
+            assert_eq!(Config::write_blocker(&target), Some("read-only"));
+            assert_eq!(Config::write_blocker(&link), Some("a symlink to a read-only file"));
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn unknown_tab_in_saved_order_is_ignored() {
+        let state: UiState =
+            serde_json::from_str(r#"{"tab_order": ["Info", "FutureTab", "procs", "Welcome"]}"#).unwrap();
+        assert_eq!(state.tab_order, vec![TabId::Info, TabId::Welcome]);
+    }
+
+    #[test]
+    fn old_config_gains_new_default_tabs_once() {
+        let mut cfg: Config = serde_json::from_str(r#"{"enabled_tabs": ["Info", "SQL"]}"#).unwrap();
+        assert!(cfg.adopt_new_tabs());
+        // 旧版本已有的 Welcome/TaskControl 是用户主动移除的，不加回；可选页不自动启用
+        assert_eq!(cfg.enabled_tabs, vec!["Info", "SQL", "Settings"]);
+        // 用户之后移除 Settings 不会再被加回
+        cfg.enabled_tabs.retain(|t| t != "Settings");
+        assert!(!cfg.adopt_new_tabs());
+        assert_eq!(cfg.enabled_tabs, vec!["Info", "SQL"]);
+    }
+
+    #[test]
+    fn fresh_config_has_nothing_to_adopt() {
+        assert!(!Config::default().adopt_new_tabs());
+    }
+
+    #[test]
+    fn unknown_mem_widget_style_falls_back_to_sparkline() {
+        for raw in [r#""Bars""#, r#""gauge""#, "3", "null"] {
+            let cfg: Config = serde_json::from_str(&format!(r#"{{"mem_widget_style": {}}}"#, raw)).unwrap();
+            assert_eq!(cfg.mem_widget_style, MemWidgetStyle::Sparkline, "{}", raw);
+        }
+        let cfg: Config = serde_json::from_str(r#"{"mem_widget_style": "Gauge"}"#).unwrap();
+        assert_eq!(cfg.mem_widget_style, MemWidgetStyle::Gauge);
+    }
+
+    #[test]
+    fn layout_values_are_clamped_and_default_to_constants() {
+        let cfg = Config::default();
+        assert_eq!(cfg.golden_ratio(), GOLDEN_RATIO_PC);
+        assert_eq!((cfg.tab_bar_rows(), cfg.footer_rows()), (3, 1));
+
+        let cfg = Config { golden_ratio_pc: 250, tab_bar_height: 0, footer_height: 9, ..Default::default() };
+        assert_eq!((cfg.golden_ratio(), cfg.tab_bar_rows(), cfg.footer_rows()), (90, 3, 3));
+        let cfg = Config { golden_ratio_pc: 0, tab_bar_height: 40, footer_height: 0, ..Default::default() };
+        assert_eq!((cfg.golden_ratio(), cfg.tab_bar_rows(), cfg.footer_rows()), (10, 5, 1));
+    }
+
+    #[test]
+    fn out_of_range_refresh_rate_is_clamped_on_load() {
+        let loaded = |raw: &str| {
+            let mut cfg: Config = serde_json::from_str(&format!(r#"{{"refresh_rate_ms": {}}}"#, raw)).unwrap();
+            let changed = cfg.validate();
+            (cfg.refresh_rate_ms, changed)
+        };
+        assert_eq!(loaded("0"), (TICK_MIN_MS, true));
+        assert_eq!(loaded("86400000"), (REFRESH_RATE_MAX_MS, true));
+        assert_eq!(loaded("100"), (100, false));
+        assert_eq!(Config::default().refresh_rate_ms, TICK_MIN_MS);
+        assert!(!Config::default().validate());
+    }
+}
@@ -1,69 +1,119 @@
 use crossterm::event::KeyCode;
-use directories::{ProjectDirs, UserDirs};
 use ratatui::style::Color;
 
 use ratatui::layout::Constraint;
+use serde::{Deserialize, Serialize};
 
 use crate::config::SharedConfig;
 use crate::ui::component::Component;
 use crate::ui::db_view::DatabaseComponent;
+use crate::ui::settings::SettingsComponent;
 use crate::ui::info::InfoComponent;
+use crate::ui::processes::ProcessesComponent;
 use crate::ui::task_control::TaskControlComponent;
 use crate::ui::welcome::WelcomeComponent;
 
 
 /// 2. 标签页唯一标识
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TabId {
     Welcome,
     Info,
     TaskControl, // Sessions,
     SQL,
+    Processes,
+    Settings,
 }
 
-/// 3. 页面注册信息
+// 3. 页面注册表
+/// 新增标签页只需在 TabId 中加一个变体并在 TABS 中登记一条 TabSpec，
+/// 默认顺序、标题、配置名与组件构造都从这里派生
+pub struct TabSpec {
+    pub id: TabId,
+    /// 配置中使用的名称 (Config.enabled_tabs)
+    pub name: &'static str,
+    /// 导航栏显示的标题
+    pub title: &'static str,
+    /// 是否包含在默认的 Config.enabled_tabs 中 (可选页需要用户手动启用)
+    pub default_enabled: bool,
+    pub factory: fn() -> Box<dyn Component>,
+}
+
+pub const TABS: &[TabSpec] = &[
+    TabSpec { id: TabId::Welcome, name: "Welcome", title: " Welcome ", default_enabled: true, factory: || Box::new(WelcomeComponent::init()) },
+    TabSpec { id: TabId::TaskControl, name: "TaskControl", title: " Task ", default_enabled: true, factory: || Box::new(TaskControlComponent::init()) },
+    TabSpec { id: TabId::Info, name: "Info", title: " System ", default_enabled: true, factory: || Box::new(InfoComponent::init()) },
+    TabSpec { id: TabId::SQL, name: "SQL", title: " DB ", default_enabled: true, factory: || Box::new(DatabaseComponent::init()) },
+    TabSpec { id: TabId::Settings, name: "Settings", title: " Settings ", default_enabled: true, factory: || Box::new(SettingsComponent::init()) },
+    TabSpec { id: TabId::Processes, name: "Processes", title: " Procs ", default_enabled: false, factory: || Box::new(ProcessesComponent::init()) },
+    // TabSpec { id: TabId::Sessions, name: "Sessions", title: " [2] Session Manager ", ... },
+];
+
 impl TabId {
-    /// 获取所有标签页的顺序列表
-    pub const ALL: &[Self] = &[
-        // Self::Info,
-        Self::Welcome,
-        Self::TaskControl,
-        Self::Info,
-        Self::SQL
-        
-        // Self::Sessions
-    ];
+    /// 注册表中的条目 (每个变体都必须登记，否则视为编程错误)
+    pub fn spec(&self) -> &'static TabSpec {
+        TABS.iter()
+            .find(|s| s.id == *self)
+            .unwrap_or_else(|| panic!("TabId::{:?} is not registered in TABS", self))
+    }
+
+    /// 所有标签页的默认顺序 (即注册顺序)
+    pub fn all() -> impl Iterator<Item = TabId> {
+        TABS.iter().map(|s| s.id)
+    }
+
+    /// 默认启用的标签页 (Config.enabled_tabs 的默认值)
+    pub fn defaults() -> impl Iterator<Item = TabId> {
+        TABS.iter().filter(|s| s.default_enabled).map(|s| s.id)
+    }
 
     /// 对应的显示标题
     pub fn title(&self) -> &'static str {
-        match self {
-            Self::Welcome => " Welcome ",
-            Self::Info => " System ",
-            Self::TaskControl => " Task ",
-            Self::SQL=>" DB ",
+        self.spec().title
+    }
 
-            // Self::Sessions => " [2] Session Manager ",
-        }
-    } 
+    pub fn init(order: &[TabId]) -> Vec<Box<dyn Component>> {
+        order.iter().map(|id| (id.spec().factory)()).collect()
+    }
 
-    pub fn init() -> Vec<Box<dyn Component>> {
-        let mut output = vec![];
-        for id in TabId::ALL.iter() {
-            let comp = id.gen_component();
-            output.push(comp);
-        }
-        output
+    /// 配置中使用的名称 (Config.enabled_tabs)
+    pub fn name(&self) -> &'static str {
+        self.spec().name
     }
-    fn gen_component(&self) -> Box<dyn Component> {
-        match self {
-            Self::Welcome => Box::new(WelcomeComponent::init()),
-            Self::Info => Box::new(InfoComponent::init()),
-            Self::TaskControl => Box::new(TaskControlComponent::init()),
-            Self::SQL=>Box::new(DatabaseComponent::init()),
-            // Self::Sessions => " [2] Session Manager ",
+
+    /// 按配置名查找标签页 (不区分大小写)
+    pub fn from_name(name: &str) -> Option<TabId> {
+        TABS.iter().find(|s| s.name.eq_ignore_ascii_case(name.trim())).map(|s| s.id)
+    }
+
+    /// 解析 Config.enabled_tabs (不区分大小写)，返回 (启用的标签, 无法识别的名称)；
+    /// 结果为空时回退到 Welcome，保证至少有一个标签页
+    pub fn parse_enabled(names: &[String]) -> (Vec<TabId>, Vec<String>) {
+        let mut enabled = Vec::new();
+        let mut unknown = Vec::new();
+        for name in names {
+            match Self::from_name(name) {
+                Some(id) if !enabled.contains(&id) => enabled.push(id),
+                Some(_) => {}
+                None => unknown.push(name.clone()),
+            }
         }
+        if enabled.is_empty() {
+            enabled.push(Self::Welcome);
+        }
+        (enabled, unknown)
     }
 
+    /// 应用保存的标签顺序：只保留启用的标签，忽略重复条目，其余启用的标签按配置顺序补在末尾
+    pub fn ordered(saved: &[TabId], enabled: &[TabId]) -> Vec<TabId> {
+        let mut order: Vec<TabId> = Vec::with_capacity(enabled.len());
+        for id in saved.iter().chain(enabled.iter()) {
+            if enabled.contains(id) && !order.contains(id) {
+                order.push(*id);
+            }
+        }
+        order
+    }
 }
 
 // 2. 界面文字内容
@@ -71,6 +121,40 @@ pub const APP_TITLE: &str = " ATLAS PRIME ";
 
 pub const WELCOME_MSG: &str = "Next-generation Compute Platform";
 pub const HELP_PROMPT: &str = "Press 'h' to toggle help & controls";
+/// 首次创建 script 目录时写入的示例 (Config.seed_scripts)
+pub const SAMPLE_SCRIPT_NAME: &str = "hello.ts";
+pub const SAMPLE_SCRIPT: &str = r#"// Atlas 示例任务：在 Task 页选中 "🦕 hello" 后按 x 启动，Enter 查看日志。
+// stdout 正常显示，stderr 以 [ERR] 标记并着色，ANSI 颜色会被保留。
+// 日志视图中直接输入文字并回车会写入本脚本的 stdin。
+
+console.log("hello from Atlas 👋");
+console.log("\x1b[32mcolored output works too\x1b[0m");
+console.error("this line goes to stderr");
+
+const decoder = new TextDecoder();
+console.log("type something in the log view and press Enter, or type exit to finish");
+for await (const chunk of Deno.stdin.readable) {
+  const text = decoder.decode(chunk).trim();
+  if (text === "exit") break;
+  console.log(`echo: ${text}`);
+}
+console.log("bye");
+"#;
+pub const SAMPLE_SCRIPT_README: &str = r#"# Atlas scripts
+
+Every `*.ts` file in this directory becomes a task in the Task tab (group "Scripts"),
+started with `deno` and the arguments from `deno_args` in the config
+(`{script}` is replaced by the script path).
+
+- stdout and stderr are captured into the task log (stderr is marked `[ERR]`).
+- Lines typed in the log view are sent to the script's stdin.
+- A `name.atlas.json` file next to `name.ts` can override the arguments:
+  `{ "args": ["run", "--allow-net", "{script}"] }`
+
+Delete `hello.ts` once you have your own scripts; it is only written when this
+directory is first created (`seed_scripts` in the config).
+"#;
+
 pub const ART_LOGO: &str = r#"
      █████  ████████ ██        █████  ███████
     ██   ██    ██    ██       ██   ██ ██     
@@ -79,10 +163,16 @@ pub const ART_LOGO: &str = r#"
     ██   ██    ██    ████████ ██   ██ ███████
         "#;
 // 2. 帮助区域内容（数组形式，方便翻页）
-pub const ART_LOGO_HEIGHT: u16 = 6;
 pub const HELP_CONTENT: &[&str] = &[
     "Esc               : Clear notifications or close popups",
     "Ctrl + C          : Force quit Atlas (Safety Exit)",
+    "Ctrl + Shift + ←→ : Move the current tab left / right",
+    "Ctrl + S          : Save the current screen to reports/ (bug reports)",
+    "n                 : Notification history",
+    "v (Info)          : Toggle charts / compact text summary",
+    "H (Info, IP pane) : Show recorded IP address changes",
+    "r (Info)          : Collect all metrics now",
+    "R (Info)          : Reset session min / avg / max (was r)",
 ];
 
 // 3. 布局比例 (黄金分割)
@@ -94,6 +184,58 @@ pub const INFO_UPDATE_INTERVAL_SLOW_TIMES: u64 = 8;
 pub const INFO_UPDATE_INTERVAL_SLOWEST: u64 = 30;
 pub const HISTORY_CAP: usize = 1024;
 
+/// DB 标签页自动刷新的下限与步进 (秒)，避免频繁对大表执行 COUNT(*)
+pub const DB_REFRESH_MIN_SECS: u64 = 5;
+pub const DB_REFRESH_STEP_SECS: u64 = 5;
+
+/// 正常布局所需的最小终端尺寸，低于此值按 Config.on_small_terminal 处理
+pub const MIN_TERM_WIDTH: u16 = 60;
+pub const MIN_TERM_HEIGHT: u16 = 20;
+
+/// 主循环节拍：组件请求的最短间隔 (约 120FPS 上限)，以及没有组件需要节拍时的空闲间隔
+pub const TICK_MIN_MS: u64 = 8;
+pub const TICK_IDLE_MS: u64 = 500;
+/// 进程页的刷新间隔下限与最多显示的行数，枚举全部进程开销不小
+pub const PROC_REFRESH_MS: u64 = 2000;
+pub const PROC_TOP_N: usize = 100;
+/// Config.refresh_rate_ms 的上限：超过 1 秒界面明显迟钝，下限即 TICK_MIN_MS (0 会变成忙循环)
+pub const REFRESH_RATE_MAX_MS: u64 = 1000;
+/// 窗口拖动调整期间的重绘节拍 (约 60FPS)，连续的 Resize 事件在一拍内合并
+pub const RESIZE_REDRAW_MS: u64 = 16;
+
+/// directories::ProjectDirs 的限定名 (qualifier, organization, application)，
+/// 所有项目路径 (配置、数据、缓存) 都由 AtlasPath 用它解析
+pub const PROJECT_QUALIFIER: (&str, &str, &str) = ("org", "oelabs", "atlas");
+
+/// Ctrl+S 屏幕快照的输出子目录 (位于数据目录下)
+pub const SCREEN_DUMP_DIR: &str = "reports";
+
+/// UI 看门狗检查心跳的间隔
+pub const WATCHDOG_POLL_MS: u64 = 1000;
+
+/// Ctrl+C 退出时等待任务停止的最长时间
+pub const SHUTDOWN_TIMEOUT_MS: u64 = 3000;
+
+/// 告警回差：已触发的规则要等指标回落到阈值另一侧超过该值才重新生效，避免在阈值附近反复通知
+pub const ALERT_HYSTERESIS: f64 = 1.0;
+
+/// 配置热加载完成后广播的 GlobalEvent::Data key (设置页据此重建表单)
+pub const CONFIG_RELOADED: &str = "config_reloaded";
+/// 开始记录 Config.known_tabs 之前就已存在的标签页；旧配置文件缺少该字段时视为已见过这些
+pub const LEGACY_TABS: &[&str] = &["Welcome", "TaskControl", "Info", "SQL"];
+
+/// 退出保存配置时等待配置锁的重试次数 (每次 10ms)，热加载恰好持有写锁时不会卡住退出
+pub const EXIT_SAVE_RETRIES: u32 = 20;
+
+/// 通知历史 (按 'n' 查看) 最多保留的条数
+pub const NOTIFY_HISTORY_CAP: usize = 200;
+
+/// 公网 IP：成功后的刷新周期、失败重试的指数退避 (1s, 2s, 4s ... 封顶)
+pub const PUBLIC_IP_REFRESH_SECS: u64 = 15 * 60;
+pub const PUBLIC_IP_BACKOFF_MAX_SECS: u64 = 300;
+/// 等待期间检查本地网卡是否变化的间隔
+pub const NET_CHANGE_POLL_SECS: u64 = 30;
+
 /// 底部状态栏的横向布局约束
 /// 0: 按键提示 (Left)
 /// 1: 文字通知 (Center)
@@ -119,6 +261,11 @@ pub const MAIN_LAYOUT: [Constraint; 3] = [
 
 
 pub const DATABASE_FILE : &str = "atlas_prime.db";
+/// 缓存目录中的日志文件，超过上限时轮转为 atlas.log.1
+pub const LOG_FILE: &str = "atlas.log";
+pub const LOG_ROTATE_BYTES: u64 = 2 * 1024 * 1024;
+/// 数据目录中的实例锁文件 (内容为持有者 PID)
+pub const LOCK_FILE: &str = "atlas.lock";
 
 
 pub const TASK_RAW_JSON: &str = r#"[
@@ -133,7 +280,8 @@ pub const TASK_RAW_JSON: &str = r#"[
     "autostart": false,
     "group": "HEAVY",
     "restart_policy": "Warn",
-    "log_limit": 500
+    "log_limit": 500,
+    "nice": 10
   },
   {
     "id": "miniserve",
@@ -166,3 +314,68 @@ pub const TASK_RAW_JSON: &str = r#"[
     "log_limit": 500
   }
 ]"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按声明顺序列出所有变体；新增变体时 match 不完整会编译失败，提醒在这里补上
+    fn every_tab() -> Vec<TabId> {
+        let mut ids = Vec::new();
+        let mut next = Some(TabId::Welcome);
+        while let Some(id) = next {
+            ids.push(id);
+            next = match id {
+                TabId::Welcome => Some(TabId::Info),
+                TabId::Info => Some(TabId::TaskControl),
+                TabId::TaskControl => Some(TabId::SQL),
+                TabId::SQL => Some(TabId::Processes),
+                TabId::Processes => Some(TabId::Settings),
+                TabId::Settings => None,
+            };
+        }
+        ids
+    }
+
+    #[test]
+    fn every_tab_is_registered_once() {
+        let ids = every_tab();
+        assert_eq!(TABS.len(), ids.len());
+        for id in ids {
+            assert_eq!(TABS.iter().filter(|s| s.id == id).count(), 1, "{:?}", id);
+            assert_eq!(id.spec().id, id);
+            assert_eq!(TabId::from_name(id.name()), Some(id));
+        }
+    }
+
+    #[test]
+    fn nav_titles_and_names_follow_the_registry() {
+        let titles: Vec<&str> = TabId::all().map(|id| id.title()).collect();
+        assert_eq!(titles, TABS.iter().map(|s| s.title).collect::<Vec<_>>());
+        let mut names: Vec<&str> = TABS.iter().map(|s| s.name).collect();
+        names.sort_unstable();
+        names.dedup();
+        assert_eq!(names.len(), TABS.len(), "config names must be unique");
+        assert!(TabId::defaults().all(|id| id.spec().default_enabled));
+    }
+
+    #[test]
+    fn restricted_tab_list_yields_exactly_those_tabs() {
+        let names: Vec<String> = ["settings", "Welcome", "Bogus", "Settings"].map(String::from).into();
+        let (enabled, unknown) = TabId::parse_enabled(&names);
+        assert_eq!(enabled, vec![TabId::Settings, TabId::Welcome]);
+        assert_eq!(unknown, vec!["Bogus".to_string()]);
+
+        // 保存的顺序中未启用的标签被丢弃，其余按保存顺序排列
+        let order = TabId::ordered(&[TabId::Info, TabId::Welcome], &enabled);
+        assert_eq!(order, vec![TabId::Welcome, TabId::Settings]);
+
+        crate::prelude::GlobIO::init_for_test();
+        crate::config::Config::init_for_test();
+        assert_eq!(TabId::init(&order).len(), 2);
+
+        // 全部无效时至少保留 Welcome
+        let (fallback, _) = TabId::parse_enabled(&["nope".to_string()]);
+        assert_eq!(fallback, vec![TabId::Welcome]);
+    }
+}
@@ -1,10 +1,15 @@
 use sqlx::{sqlite::SqliteConnectOptions, SqlitePool, Row};
 use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::str::FromStr;
-use crate::constans::DATABASE_FILE;
+use crate::constans::{DATABASE_FILE, LOCK_FILE};
 use crate::prelude::AtlasPath;
 
 static SQLITE_POOL: OnceLock<SqlitePool> = OnceLock::new();
+// 另一个实例持有锁时进入只读模式，不再写入数据库
+static READ_ONLY: AtomicBool = AtomicBool::new(false);
+// 本进程是否创建了锁文件 (只有持有者才能删除)
+static LOCK_OWNED: AtomicBool = AtomicBool::new(false);
 
 pub struct Database;
 
@@ -30,6 +35,11 @@ impl Database {
         SQLITE_POOL.get().expect("Database NOT initialized.")
     }
 
+    /// 连接池未初始化 (数据库启动失败、--once 模式) 时返回 None，调用方退回内存状态
+    pub fn try_pool() -> Option<&'static SqlitePool> {
+        SQLITE_POOL.get()
+    }
+
     /// [核心接口] 允许模块注册自己的表结构
     pub async fn setup_table(ddl: &str) -> Result<(), String> {
         sqlx::query(ddl)
@@ -38,4 +48,160 @@ impl Database {
             .map(|_| ())
             .map_err(|e| e.to_string())
     }   
-}
\ No newline at end of file
+
+    /// 启动时获取实例锁 (`atlas.lock`，内容为 PID)。
+    /// 锁已被存活的进程持有时返回该 PID，并按配置进入只读模式；残留的过期锁删除后重新获取
+    pub fn acquire_lock(read_only_on_conflict: bool) -> Option<u32> {
+        Self::acquire_lock_at(&AtlasPath::get().proj_dir.join(LOCK_FILE), read_only_on_conflict)
+    }
+
+    fn acquire_lock_at(path: &std::path::Path, read_only_on_conflict: bool) -> Option<u32> {
+        match try_lock(path) {
+            LockState::Acquired => LOCK_OWNED.store(true, Ordering::Relaxed),
+            LockState::Held(pid) => {
+                READ_ONLY.store(read_only_on_conflict, Ordering::Relaxed);
+                return Some(pid);
+            }
+            LockState::Unavailable => {}
+        }
+        None
+    }
+
+    /// 释放实例锁 (正常退出与 panic hook 中调用)；非持有者不做任何事
+    pub fn release_lock() {
+        if LOCK_OWNED.swap(false, Ordering::Relaxed)
+            && let Some(paths) = AtlasPath::try_get()
+        {
+            let _ = std::fs::remove_file(paths.proj_dir.join(LOCK_FILE));
+        }
+    }
+
+    /// 是否处于只读模式 (检测到另一个实例)
+    pub fn is_read_only() -> bool {
+        READ_ONLY.load(Ordering::Relaxed)
+    }
+
+    /// 测试用：整个测试进程共用一个内存数据库。
+    /// 返回的锁让用到数据库的测试串行执行，各自统计的行数不会互相干扰
+    #[cfg(test)]
+    pub async fn init_for_test() -> tokio::sync::MutexGuard<'static, ()> {
+        static DB_TEST_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+        let guard = DB_TEST_LOCK.lock().await;
+        if SQLITE_POOL.get().is_none() {
+            let pool = SqlitePool::connect("sqlite::memory:").await.expect("open test database");
+            SQLITE_POOL.set(pool).ok();
+        }
+        guard
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum LockState {
+    Acquired,
+    /// 被另一个存活的进程持有
+    Held(u32),
+    /// 无法创建锁文件 (目录不可写等)，不加锁继续运行
+    Unavailable,
+}
+
+/// 锁文件刚被另一个实例创建、PID 尚未写入时，读取前的等待次数与间隔
+const LOCK_READ_RETRIES: u32 = 5;
+const LOCK_READ_DELAY_MS: u64 = 20;
+
+/// 用 create_new 原子地创建锁文件，两个同时启动的实例只有一个能成功；
+/// 已存在时检查持有者，只有确认过期 (进程已退出) 才删除并重试
+fn try_lock(path: &std::path::Path) -> LockState {
+    use std::io::Write;
+    for _ in 0..2 {
+        match std::fs::OpenOptions::new().write(true).create_new(true).open(path) {
+            Ok(mut f) => {
+                let _ = write!(f, "{}", std::process::id());
+                return LockState::Acquired;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(_) => return LockState::Unavailable,
+        }
+        let content = read_lock_holder(path);
+        if let Some(pid) = content.filter(|pid| *pid != std::process::id() && pid_alive(*pid)) {
+            return LockState::Held(pid);
+        }
+        // 删除前再确认一次内容没变，避免删掉另一个实例刚创建的新锁
+        if read_lock_holder(path) == content {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+    match read_lock_holder(path) {
+        Some(pid) if pid != std::process::id() && pid_alive(pid) => LockState::Held(pid),
+        _ => LockState::Unavailable,
+    }
+}
+
+/// 读取锁文件中的 PID；内容为空时可能是对方还没写完，稍等再读
+fn read_lock_holder(path: &std::path::Path) -> Option<u32> {
+    for _ in 0..LOCK_READ_RETRIES {
+        match std::fs::read_to_string(path) {
+            Ok(s) if s.trim().is_empty() => std::thread::sleep(std::time::Duration::from_millis(LOCK_READ_DELAY_MS)),
+            Ok(s) => return s.trim().parse().ok(),
+            Err(_) => return None,
+        }
+    }
+    None
+}
+
+/// PID 是否仍然存活：Linux/Android 检查 /proc，其它平台保守地视为存活
+fn pid_alive(pid: u32) -> bool {
+    if cfg!(any(target_os = "linux", target_os = "android")) {
+        std::path::Path::new("/proc").join(pid.to_string()).exists()
+    } else {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock_path(name: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("atlas_lock_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn fresh_lock_is_created_with_own_pid() {
+        let path = lock_path("fresh");
+        assert_eq!(try_lock(&path), LockState::Acquired);
+        assert_eq!(read_lock_holder(&path), Some(std::process::id()));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[tokio::test]
+    async fn live_holder_means_read_only_and_stale_lock_is_taken_over() {
+        let path = lock_path("live");
+        let mut child = std::process::Command::new("sleep").arg("5").spawn().expect("spawn sleep");
+        std::fs::write(&path, child.id().to_string()).unwrap();
+        assert_eq!(try_lock(&path), LockState::Held(child.id()));
+        // 持有者的锁不能被改写
+        assert_eq!(read_lock_holder(&path), Some(child.id()));
+
+        // 配置为冲突时只读：返回持有者 PID 并进入只读模式；关闭该选项时保持可写
+        {
+            // READ_ONLY 是全局状态，持有数据库测试锁，避免写库的测试在此期间被跳过
+            let _db = Database::init_for_test().await;
+            assert_eq!(Database::acquire_lock_at(&path, false), Some(child.id()));
+            assert!(!Database::is_read_only());
+            assert_eq!(Database::acquire_lock_at(&path, true), Some(child.id()));
+            assert!(Database::is_read_only());
+            READ_ONLY.store(false, Ordering::Relaxed);
+            assert!(!LOCK_OWNED.load(Ordering::Relaxed));
+        }
+
+        // 进程退出后锁过期，接管并写入自己的 PID
+        let _ = child.kill();
+        let _ = child.wait();
+        assert_eq!(try_lock(&path), LockState::Acquired);
+        assert_eq!(read_lock_holder(&path), Some(std::process::id()));
+        let _ = std::fs::remove_file(&path);
+    }
+}
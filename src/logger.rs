@@ -0,0 +1,104 @@
+//! 文件日志：raw mode 下 stderr 不可见，warn/error 等信息统一写入 cache_dir/atlas.log，便于事后排查。
+//! UI 通知 (GlobIO::error 等) 保持不变，这里只负责持久化。
+
+use log::{LevelFilter, Log, Metadata, Record};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use crate::constans::{LOG_FILE, LOG_ROTATE_BYTES};
+use crate::prelude::AtlasPath;
+
+struct FileLogger {
+    file: Mutex<Option<File>>,
+}
+
+static LOGGER: FileLogger = FileLogger { file: Mutex::new(None) };
+
+impl Log for FileLogger {
+    fn enabled(&self, meta: &Metadata) -> bool {
+        // 第三方库 (sqlx 等) 只记录 warn 以上，避免每条 SQL 都写进日志
+        meta.level() <= log::max_level()
+            && (meta.target().starts_with(env!("CARGO_CRATE_NAME")) || meta.level() <= log::Level::Warn)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        if let Ok(mut guard) = self.file.lock()
+            && let Some(file) = guard.as_mut()
+        {
+            let _ = writeln!(
+                file,
+                "{} {:<5} [{}] {}",
+                chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut guard) = self.file.lock()
+            && let Some(file) = guard.as_mut()
+        {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// 在终端初始化之前调用 (需要 AtlasPath 已初始化)；超过 LOG_ROTATE_BYTES 时保留一份 .1 备份
+pub fn init() {
+    let dir = &AtlasPath::get().cache_dir;
+    let _ = std::fs::create_dir_all(dir);
+    let path = dir.join(LOG_FILE);
+    if std::fs::metadata(&path).is_ok_and(|m| m.len() > LOG_ROTATE_BYTES) {
+        let _ = std::fs::rename(&path, path.with_extension("log.1"));
+    }
+    install(&path);
+}
+
+/// 以追加方式打开日志文件并注册为全局 logger
+fn install(path: &Path) {
+    if let Ok(mut guard) = LOGGER.file.lock() {
+        *guard = OpenOptions::new().create(true).append(true).open(path).ok();
+    }
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(LevelFilter::Info);
+    }
+}
+
+/// 应用 Config.log_level (off/error/warn/info/debug/trace)，无法识别时保持不变
+pub fn set_level(level: &str) {
+    match LevelFilter::from_str(level.trim()) {
+        Ok(filter) => log::set_max_level(filter),
+        Err(_) => log::warn!("Unknown log_level {:?}, keeping {}", level, log::max_level()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logged_error_lands_in_the_file() {
+        let path = std::env::temp_dir().join(format!("atlas_log_{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        install(&path);
+        set_level("warn");
+
+        log::error!("disk write failed: marker-e");
+        log::info!("below threshold: marker-i");
+        log::logger().flush();
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        let line = text.lines().find(|l| l.contains("marker-e")).expect("error line");
+        assert!(line.contains("ERROR") && line.contains(env!("CARGO_CRATE_NAME")), "{line}");
+        assert!(!text.contains("marker-i"));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,27 +1,34 @@
+mod alerts;
+mod api;
 mod app;
+mod clipboard;
 mod config;
 mod constans;
 mod db;
+mod logger;
 mod message;
+mod net;
 // mod server;
 mod ui;
+mod version;
 // mod utils;
 mod prelude;
+mod watchdog;
+#[cfg(feature = "reverse-dns")]
+mod rdns;
 
 use crossterm::event::KeyModifiers;
 use notify::{RecursiveMode, Watcher};
 use ratatui::widgets::{Block, Paragraph};
 use ratatui_image::Resize;
 use std::error::Error;
-use std::path::Path;
 use tokio::sync::broadcast;
 
-use crate::config::SharedConfig;
-use crate::message::{GlobalEvent, Progress, StatusLevel};
+use crate::config::{CrashBacktrace, SharedConfig};
+use crate::message::{DynamicPayload, GlobalEvent, Progress, StatusLevel};
 
 use crate::prelude::{AtlasPath, GlobIO};
 use crate::{app::App, config::Config, ui::component::Component};
-use backtrace::Backtrace;
 use crossterm::{
     event::{self, Event, EventStream, KeyCode},
     execute,
@@ -31,14 +38,13 @@ use futures::StreamExt;
 use ratatui::{Terminal, backend::CrosstermBackend};
 use std::{
     fs::OpenOptions,
-    io::{self, Write},
+    io::{self, IsTerminal, Write},
     panic,
-    sync::Arc,
-    time::Duration,
+    sync::{Arc, OnceLock},
 };
 use tokio::{
     sync::{RwLock, mpsc},
-    time::{MissedTickBehavior, interval},
+    time::{MissedTickBehavior, interval, interval_at},
 };
 use ratatui::prelude::*;
 use std::time::Instant;
@@ -52,37 +58,55 @@ pub async fn setup_config_watcher(
     tokio::spawn(async move {
         let (tx, mut rx) = tokio::sync::mpsc::channel(1);
 
-        // 创建文件监听器
+        // 与 Config::load_from_disk / save 使用同一个路径；监听所在目录，
+        // 编辑器"写临时文件再改名"的保存方式也能捕获到
+        let config_path = AtlasPath::get_config_path();
+        let file_name = config_path.file_name().map(|n| n.to_os_string());
         let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
-            if let Ok(e) = res {
-                if e.kind.is_modify() {
-                    let _ = tx.blocking_send(());
-                }
+            if let Ok(e) = res
+                && (e.kind.is_modify() || e.kind.is_create())
+                && e.paths.iter().any(|p| p.file_name().map(|n| n.to_os_string()) == file_name)
+            {
+                let _ = tx.try_send(());
             }
         })
         .expect("Failed to create watcher");
 
-        watcher
-            .watch(Path::new("config.toml"), RecursiveMode::NonRecursive)
-            .ok();
+        if let Some(dir) = config_path.parent()
+            && let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive)
+        {
+            log::warn!("Config hot-reload disabled, cannot watch {:?}: {}", dir, e);
+        }
 
-        while let Some(_) = rx.recv().await {
-            // 1. 读取新配置
-            let new_conf = Config::load_from_disk();
+        while rx.recv().await.is_some() {
+            match reload_if_external(&shared_config, &config_path).await {
+                Ok(true) => {}
+                Ok(false) => continue,
+                // 解析失败保留当前配置，绝不改名或重建用户正在编辑的文件
+                Err(e) => {
+                    log::warn!("Config reload skipped: {}", e);
+                    GlobIO::warn(format!("Config not reloaded (keeping current): {}", e));
+                    continue;
+                }
+            }
 
-            // 2. 写入共享内存
+            // 通知各组件
             {
-                let mut guard = shared_config.write().await;
-                *guard = new_conf;
-                //println!("Config hot-reloaded!");
-
                 let _ = glob_send.send(GlobalEvent::Status(
                     "Config Hot-Reloaded".into(),
                     StatusLevel::Info,
                     None,
                 ));
+                let _ = glob_send.send(GlobalEvent::Data {
+                    key: crate::constans::CONFIG_RELOADED,
+                    data: DynamicPayload(Arc::new(())),
+                });
                 // let _ = render_tx.send(()).await;
             }
+            crate::ui::style::init();
+            if let Ok(cfg) = shared_config.try_read() {
+                logger::set_level(&cfg.log_level);
+            }
 
             // 3. 强制触发全局重绘
             // let _ = render_tx.send(()).await;
@@ -91,15 +115,69 @@ pub async fn setup_config_watcher(
     });
 }
 
+/// 磁盘上的配置与内存中的不同 (外部修改) 时读入并替换 config，返回是否重新加载。
+/// 本程序自己 save() 写入的内容与内存中一致，不算外部修改
+async fn reload_if_external(config: &SharedConfig, path: &std::path::Path) -> Result<bool, String> {
+    let on_disk = std::fs::read_to_string(path).unwrap_or_default();
+    let current = serde_json::to_string_pretty(&*config.read().await).unwrap_or_default();
+    if on_disk == current {
+        return Ok(false);
+    }
+    let new_conf = Config::reload_from(path)?;
+    *config.write().await = new_conf;
+    Ok(true)
+}
+
+/// Short 模式保留的帧数
+const CRASH_SHORT_FRAMES: usize = 16;
+/// panic 时配置可能不可读 (锁被持有)，因此在启动时把级别固定下来
+static CRASH_BACKTRACE: OnceLock<CrashBacktrace> = OnceLock::new();
+
+/// 堆栈级别：ATLAS_BACKTRACE 环境变量 > Config.crash_backtrace > Short
+fn crash_backtrace_level() -> CrashBacktrace {
+    std::env::var("ATLAS_BACKTRACE")
+        .ok()
+        .and_then(|v| CrashBacktrace::parse(&v))
+        .or_else(|| CRASH_BACKTRACE.get().copied())
+        .unwrap_or_default()
+}
+
+/// 按级别格式化堆栈；None 时返回 None，Short 只符号化前 CRASH_SHORT_FRAMES 帧
+fn format_backtrace(level: CrashBacktrace) -> Option<String> {
+    let limit = match level {
+        CrashBacktrace::None => return None,
+        CrashBacktrace::Short => CRASH_SHORT_FRAMES,
+        CrashBacktrace::Full => usize::MAX,
+    };
+    let mut out = String::new();
+    let mut idx = 0;
+    backtrace::trace(|frame| {
+        backtrace::resolve_frame(frame, |sym| {
+            let name = sym.name().map(|n| n.to_string()).unwrap_or_else(|| "<unknown>".into());
+            out.push_str(&format!("{:4}: {}\n", idx, name));
+            if let (Some(file), Some(line)) = (sym.filename(), sym.lineno()) {
+                out.push_str(&format!("          at {}:{}\n", file.display(), line));
+            }
+        });
+        idx += 1;
+        idx < limit
+    });
+    if idx >= limit && level == CrashBacktrace::Short {
+        out.push_str("      ... (set ATLAS_BACKTRACE=full for the complete trace)\n");
+    }
+    Some(out)
+}
+
 fn setup_panic_hook() {
     panic::set_hook(Box::new(|panic_info| {
         // 1. 立即恢复终端，防止界面错乱
         let _ = disable_raw_mode();
         let mut stdout = io::stdout();
         let _ = execute!(stdout, LeaveAlternateScreen, crossterm::cursor::Show);
+        crate::db::Database::release_lock();
 
-        // 2. 获取当前的堆栈信息
-        let bt = Backtrace::new();
+        // 2. 获取当前的堆栈信息 (详细程度见 crash_backtrace_level)
+        let bt = format_backtrace(crash_backtrace_level());
         let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
 
         // 3. 构造错误日志
@@ -112,10 +190,15 @@ fn setup_panic_hook() {
             .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
             .unwrap_or_default();
 
-        let log_msg = format!(
-            "--- PANIC AT {} ---\nLocation: {}\nError: {}\nStack Trace:\n{:?}\n\n",
-            timestamp, location, payload, bt
+        let mut log_msg = format!(
+            "--- PANIC AT {} ---\nLocation: {}\nError: {}\n",
+            timestamp, location, payload
         );
+        if let Some(bt) = bt {
+            log_msg.push_str("Stack Trace:\n");
+            log_msg.push_str(&bt);
+        }
+        log_msg.push('\n');
 
         // 4. 写入 crash.log
         if let Ok(mut file) = OpenOptions::new()
@@ -126,6 +209,9 @@ fn setup_panic_hook() {
             let _ = file.write_all(log_msg.as_bytes());
         }
 
+        log::error!("Panic at {}: {}", location, payload);
+        log::logger().flush();
+
         // 5. 在终端打印简短提示
         eprintln!("程序发生致命错误，详细信息已保存至 crash.log");
         eprintln!("错误摘要: {} at {}", payload, location);
@@ -133,10 +219,44 @@ fn setup_panic_hook() {
 }
 
 fn main() {
+    version::mark_start();
+    // 脚本模式：采集一次快照输出 JSON 后退出，不接触终端、不启动运行时
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--once") {
+        std::process::exit(print_snapshot(args.iter().any(|a| a == "--json")));
+    }
+
+    // 非交互环境 (stdout 被重定向、CI 脚本) 下 raw mode / 备用屏幕会失败或污染输出流，直接退出
+    if !io::stdout().is_terminal() || !io::stdin().is_terminal() {
+        eprintln!("atlas: stdin/stdout is not a terminal; the TUI needs an interactive terminal.");
+        std::process::exit(2);
+    }
+
     setup_panic_hook();    
     AtlasPath::init(); 
-    GlobIO::init();
+    logger::init(); // 先于 Config，配置解析错误也能记录
     Config::init();// check
+    Config::init_safe_mode(args.iter().any(|a| a == "--safe-mode"));
+    if let Ok(cfg) = Config::get().try_read() {
+        logger::set_level(&cfg.log_level);
+    }
+    let _ = CRASH_BACKTRACE.set(
+        Config::get()
+            .try_read()
+            .map(|c| c.crash_backtrace)
+            .unwrap_or_default(),
+    );
+    GlobIO::init(); // 容量来自配置，必须在 Config::init 之后
+    let read_only_on_conflict = Config::get()
+        .try_read()
+        .map(|c| c.second_instance_read_only)
+        .unwrap_or(true);
+    let lock_holder = crate::db::Database::acquire_lock(read_only_on_conflict);
+    if let Some(pid) = lock_holder {
+        eprintln!("atlas: another instance (PID {}) is using this data directory.", pid);
+        log::warn!("Another instance (PID {}) holds the data directory lock", pid);
+    }
+    ui::style::init();
 
     // std::thread::spawn(|| { // ntex server
     //     let _ = crate::server::run_server();
@@ -155,15 +275,19 @@ fn main() {
             // 2. 初始化全局数据库连接池 (唯一一次)
             if let Err(e) = crate::db::Database::init().await {
                 eprintln!("🔥 数据库启动失败: {}", e);
+                log::error!("Database init failed: {}", e);
                 return;
             }
 
             if let Err(e) = run_app().await {
                 eprintln!("应用逻辑错误: {}", e);
+                log::error!("Application error: {}", e);
             }
         });
     }));
 
+    crate::db::Database::release_lock();
+
     if result.is_err() {
         // 这里可以执行某些恢复后的后续操作
         eprintln!("运行时异常已捕获，终端环境已恢复。");
@@ -179,9 +303,29 @@ fn main() {
 事件流	reader (Stream)	交互点：用户的键盘或终端缩放事件。	随机（由用户操作决定）
 */
 
+/// `atlas --once --json`：返回进程退出码
+fn print_snapshot(json: bool) -> i32 {
+    if !json {
+        eprintln!("atlas: --once currently only supports --json output");
+        return 2;
+    }
+    AtlasPath::init();
+    logger::init();
+    Config::init(); // 温度分区匹配规则来自配置
+    let snapshot = ui::info::InfoComponent::snapshot_json();
+    match serde_json::to_string_pretty(&snapshot) {
+        Ok(s) => {
+            println!("{}", s);
+            0
+        }
+        Err(e) => {
+            eprintln!("atlas: failed to serialize snapshot: {}", e);
+            1
+        }
+    }
+}
+
 async fn run_app() -> Result<(), Box<dyn std::error::Error>> {
-    // 初始化共享配置
-    let shared_config = Arc::new(RwLock::new(Config::load_from_disk()));
     // 4. 初始化 App
     // let mut app = App::new(shared_config.clone()).await;
     // let (glob_send, glob_recv) = broadcast::channel(100);
@@ -193,7 +337,8 @@ async fn run_app() -> Result<(), Box<dyn std::error::Error>> {
     let watchertx = app.glob_send.clone();
     tokio::spawn(async move {
         // let _ = setup_config_watcher(shared_config.clone(), render_tx.clone(), watchertx).await;
-        let _ = setup_config_watcher(shared_config.clone(), watchertx).await;
+        // 与各组件共用 Config::get()，外部修改才能生效，应用内的保存也不会被误判为外部修改
+        let _ = setup_config_watcher(Config::get(), watchertx).await;
     });
 
     // --- 终端初始化 ---
@@ -250,9 +395,31 @@ async fn run_app() -> Result<(), Box<dyn std::error::Error>> {
     
     let mut reader = EventStream::new(); // 将 crossterm 事件转为异步流
 
-    let mut render_clock = interval(Duration::from_millis(8)); // 约 60FPS，用于平滑渲染 ,glob
+    // 节拍由组件的 desired_tick_interval 决定：只有静态页时空闲更久，切换标签后重新计算
+    let mut tick = app.tick_interval();
+    let mut render_clock = interval(tick);
+    render_clock.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let mut dump_pending = false;
+    let mut resize = ResizeCoalescer::default();
+    let (wd_timeout, wd_abort) = Config::get()
+        .try_read()
+        .map(|c| (c.watchdog_timeout_secs, c.watchdog_abort))
+        .unwrap_or((15, true));
+    watchdog::spawn(wd_timeout, wd_abort);
 
     loop {
+        // 有未处理的 Resize 时临时加快节拍，拖动过程中以 RESIZE_REDRAW_MS 的节奏重绘
+        let mut want = app.tick_interval();
+        if resize.is_pending() {
+            want = want.min(std::time::Duration::from_millis(crate::constans::RESIZE_REDRAW_MS));
+        }
+        if want != tick {
+            tick = want;
+            // 不立即触发：合并这一拍内到达的所有 Resize
+            render_clock = interval_at(tokio::time::Instant::now() + tick, tick);
+            render_clock.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        }
+
         tokio::select! {
             /*
             如果后台数据更新极快（比如一个高频传感器每秒发 1000 次数据），background_rx 会不停地往 render_tx 塞任务，导致 CPU 依然爆表
@@ -263,13 +430,29 @@ async fn run_app() -> Result<(), Box<dyn std::error::Error>> {
             // 每一帧(16ms)都检查是否需要重绘
             _ = render_clock.tick() =>
             {
+                // 两拍之间的多次 Resize 只在这里合并成一次重绘
+                if resize.take().is_some() {
+                    app.request_render();
+                }
+                app.update();
                 // should_draw 应该检查:
                 // 1. 之前有没有 request_render()
                 // 2. 或者有没有后台数据更新标记
                 if app.should_draw() {
-                    terminal.draw(|f| app.render(f, f.area()))?;
+                    let frame = terminal.draw(|f| app.render(f, f.area()))?;
+                    app.record_frame(frame.buffer);
+                    // Ctrl+S：保存刚画完的这一帧，与屏幕上看到的完全一致
+                    if dump_pending {
+                        dump_pending = false;
+                        let with_ansi = Config::get().try_read().map(|c| c.screen_dump_ansi).unwrap_or(true);
+                        match ui::screen_dump::dump(frame.buffer, with_ansi) {
+                            Ok(path) => GlobIO::success(format!("Screen saved to {}", path.display())),
+                            Err(e) => GlobIO::error(format!("Screen dump failed: {}", e)),
+                        }
+                    }
                     app.clear_render_request();
                 }
+                watchdog::beat();
             }
 
             // 2. 真正的异步按键流：完全不使用 sleep    分支 A：交互事件
@@ -279,23 +462,32 @@ async fn run_app() -> Result<(), Box<dyn std::error::Error>> {
                 {
                     Some(Ok(Event::Key(key))) =>
                     {
-                        // 1. 只有绝对全局的退出键（如 Ctrl+C 或特定 Q）在这里拦截
-                        // 如果你想让子组件也能处理 'q'，就把这一行也删掉，全部交给 app.handle_key
-                        // 1. 捕获 Ctrl + C 退出
-                        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+                        // 1. 只有绝对全局的退出键在这里拦截：Ctrl + C 退出
+                        // 不带修饰键的 'c' 由组件处理 (Info 捕获参考快照、通知历史清空)，两者互不影响
+                        if is_quit_key(&key) {
+                            app.shutdown().await;
                             break;
                         }
+                        if is_dump_key(&key) {
+                            dump_pending = true;
+                            app.request_render();
+                            render_clock.reset_immediately();
+                            continue;
+                        }
 
                         // 2. 【核心逻辑】将所有按键事件交给 App 处理
                         // app.handle_key 内部会处理：Alt+方向键、Tab切换、以及子组件的输入
                         if app.handle_key(key) {
-                            // 如果 App 处理了该事件（返回 true），标记需要重绘
+                            // 如果 App 处理了该事件（返回 true），标记需要重绘，并让下一拍立即到来
                             app.request_render();
+                            render_clock.reset_immediately();
                         }
                     },
-                    Some(Ok(Event::Resize(_, _))) => {
-                        // 窗口大小变了，必须强制重绘
-                        app.request_render();
+                    Some(Ok(Event::Resize(w, h))) => {
+                        app.wake();
+                        // 拖动调整窗口会连续触发大量 Resize：只记录最新尺寸，
+                        // 由下一次渲染节拍统一重绘，重绘频率不超过节拍
+                        resize.push(w, h);
                     },
                         _ => {}
                 }
@@ -317,7 +509,8 @@ async fn run_app() -> Result<(), Box<dyn std::error::Error>> {
                         // App::tick 会让每个组件去 try_recv 它们自己的 event_rx
                         app.update();
                     }
-                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                        GlobIO::record_lag(n);
                         // 如果落后了，强制 tick 一次来清空缓冲区
                         app.update();
                     }
@@ -330,6 +523,8 @@ async fn run_app() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
     // --- 清理 ---
+    watchdog::stop();
+    app.on_exit();
     disable_raw_mode()?;
     execute!(io::stdout(), LeaveAlternateScreen, crossterm::cursor::Show)?;
     Ok(())
@@ -344,6 +539,37 @@ Internal MPSC,info.rx (如果有),内部任务 -> 组件,组件私有流。用
 
 */
 
+/// 合并连续的 Resize 事件：两次渲染节拍之间无论收到多少次，只保留最后的尺寸
+#[derive(Default)]
+struct ResizeCoalescer {
+    pending: Option<(u16, u16)>,
+}
+
+impl ResizeCoalescer {
+    fn push(&mut self, w: u16, h: u16) {
+        self.pending = Some((w, h));
+    }
+
+    fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// 渲染节拍调用：有待处理的尺寸变化时返回它并清空
+    fn take(&mut self) -> Option<(u16, u16)> {
+        self.pending.take()
+    }
+}
+
+/// Ctrl + C：退出 Atlas (HELP_CONTENT 中的 Safety Exit)
+fn is_quit_key(key: &crossterm::event::KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL) && matches!(key.code, KeyCode::Char('c') | KeyCode::Char('C'))
+}
+
+/// Ctrl + S：把当前屏幕保存为文本 (及 ANSI) 文件
+fn is_dump_key(key: &crossterm::event::KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::CONTROL) && matches!(key.code, KeyCode::Char('s') | KeyCode::Char('S'))
+}
+
 fn show_splash<B: Backend>(terminal: &mut Terminal<B>) -> Result<(), Box<dyn Error>>
 where
     B::Error: 'static,
@@ -416,3 +642,84 @@ where
 // 使用泛型 B 并返回通用的 Box<dyn Error>
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEvent;
+
+    #[test]
+    fn ctrl_c_quits_but_plain_c_does_not() {
+        assert!(is_quit_key(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+        // Caps Lock / Shift 下终端报告大写字母
+        assert!(is_quit_key(&KeyEvent::new(KeyCode::Char('C'), KeyModifiers::CONTROL | KeyModifiers::SHIFT)));
+        assert!(!is_quit_key(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE)));
+        assert!(!is_quit_key(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE)));
+        assert!(!is_quit_key(&KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn backtrace_format_follows_level() {
+        assert!(format_backtrace(CrashBacktrace::None).is_none());
+        let short = format_backtrace(CrashBacktrace::Short).unwrap();
+        let full = format_backtrace(CrashBacktrace::Full).unwrap();
+        assert!(short.trim_start().starts_with("0: "));
+        // 一帧内联展开后可能对应多个符号，按帧序号判断
+        let max_index = |bt: &str| {
+            bt.lines()
+                .filter_map(|l| l.trim_start().split_once(": ")?.0.parse::<usize>().ok())
+                .max()
+                .unwrap_or(0)
+        };
+        assert!(max_index(&short) < CRASH_SHORT_FRAMES);
+        assert!(max_index(&full) >= max_index(&short));
+        if max_index(&full) >= CRASH_SHORT_FRAMES {
+            assert!(short.ends_with("(set ATLAS_BACKTRACE=full for the complete trace)\n"));
+            assert!(!full.contains("ATLAS_BACKTRACE"));
+        }
+
+        assert_eq!(CrashBacktrace::parse(" FULL "), Some(CrashBacktrace::Full));
+        assert_eq!(CrashBacktrace::parse("0"), Some(CrashBacktrace::None));
+        assert_eq!(CrashBacktrace::parse("verbose"), None);
+    }
+
+    #[test]
+    fn rapid_resizes_collapse_to_one_redraw() {
+        let mut resize = ResizeCoalescer::default();
+        assert!(!resize.is_pending());
+        for w in 80..120 {
+            resize.push(w, 24 + w % 3);
+        }
+        assert!(resize.is_pending());
+        // 一个渲染节拍只重绘一次，使用最后的尺寸
+        assert_eq!(resize.take(), Some((119, 26)));
+        assert_eq!(resize.take(), None);
+        assert!(!resize.is_pending());
+    }
+
+    #[tokio::test]
+    async fn saving_from_inside_the_app_does_not_reload() {
+        let path = std::env::temp_dir().join(format!("atlas_watch_{}.json", std::process::id()));
+        let config: SharedConfig = Arc::new(RwLock::new(Config::default()));
+
+        // 应用内修改后保存：磁盘内容与内存一致，不重新加载
+        {
+            let mut cfg = config.write().await;
+            cfg.group_digits = !cfg.group_digits;
+            cfg.write_to(&path).unwrap();
+        }
+        assert_eq!(reload_if_external(&config, &path).await, Ok(false));
+
+        // 外部修改：读入新值
+        Config::default().write_to(&path).unwrap();
+        assert_eq!(reload_if_external(&config, &path).await, Ok(true));
+        assert_eq!(config.read().await.group_digits, Config::default().group_digits);
+        assert_eq!(reload_if_external(&config, &path).await, Ok(false));
+
+        // 无法解析时保留当前配置
+        std::fs::write(&path, "{ not json").unwrap();
+        assert!(reload_if_external(&config, &path).await.is_err());
+        assert_eq!(config.read().await.group_digits, Config::default().group_digits);
+        let _ = std::fs::remove_file(&path);
+    }
+}
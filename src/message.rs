@@ -1,6 +1,13 @@
-use std::{any::Any, collections::HashMap, sync::Arc};
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::oneshot;
 
 use crate::prelude::GlobIO;
+use crate::ui::task_control::{LogLine, TaskSnapshot};
 
 #[derive(Clone, Debug)]
 pub enum GlobalEvent {
@@ -14,6 +21,12 @@ pub enum GlobalEvent {
     /// 状态反馈：用于 Footer 渲染 (Async Task -> App Footer)
     /// 参数：内容, 等级, 可选进度
     Status(String, StatusLevel, Option<Progress>),
+
+    /// 任务控制：延迟启动、自动重启、退出前停止等请求由 TaskControlComponent 在 update 中处理
+    Task(TaskRequest),
+
+    /// 破坏性操作的确认请求：由 App 统一弹窗 (Config.confirm_destructive)，确认后广播 action
+    Confirm(ConfirmRequest),
     // 全局指令：改变应用行为 (Component/Deno -> App) 如果需要，在Data 里
     // Action(AppAction),
 }
@@ -26,7 +39,74 @@ impl std::fmt::Debug for DynamicPayload {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
+pub struct ConfirmRequest {
+    /// 弹窗中显示的提示，例如 "Stop task 'x'?"
+    pub prompt: String,
+    /// 确认后发送的事件
+    pub action: Box<GlobalEvent>,
+}
+
+impl GlobIO {
+    /// 请求执行破坏性操作：是否弹窗确认由 App 按配置决定
+    pub fn confirm<S: Into<String>>(prompt: S, action: GlobalEvent) {
+        let _ = Self::send().send(GlobalEvent::Confirm(ConfirmRequest {
+            prompt: prompt.into(),
+            action: Box::new(action),
+        }));
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum TaskRequest {
+    /// 启动指定 id 的任务（已在运行则忽略）；focus 为 true 表示由用户发起，
+    /// 按 focus_on_start 选中该任务。延迟启动、自动重启等后台启动不抢焦点
+    Start { id: String, focus: bool },
+    /// 停止指定 id 的任务（未运行则忽略）
+    Stop(String),
+    /// 查询所有任务的状态快照
+    List(Reply<Vec<TaskSnapshot>>),
+    /// 停止所有运行中的任务 (退出前调用)
+    StopAll,
+    /// 读取任务日志的最后 tail 行 (已去除 ANSI 转义)。
+    /// 只有持有该任务的组件回复，没有人回复时由请求方超时处理
+    Logs {
+        id: String,
+        tail: usize,
+        reply: Reply<Vec<LogLine>>,
+    },
+}
+
+/// 一次性回复通道：oneshot::Sender 不能 Clone，包一层才能放进广播消息
+pub struct Reply<T>(Arc<Mutex<Option<oneshot::Sender<T>>>>);
+
+impl<T> Clone for Reply<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> std::fmt::Debug for Reply<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Reply(oneshot)")
+    }
+}
+
+impl<T> Reply<T> {
+    pub fn channel() -> (Self, oneshot::Receiver<T>) {
+        let (tx, rx) = oneshot::channel();
+        (Self(Arc::new(Mutex::new(Some(tx)))), rx)
+    }
+
+    /// 只有第一个调用者的回复会被送达
+    pub fn send(&self, value: T) {
+        if let Some(tx) = self.0.lock().ok().and_then(|mut g| g.take()) {
+            let _ = tx.send(value);
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum StatusLevel {
     Info,
     Success,
@@ -0,0 +1,60 @@
+//! 所有出站网络请求 (公网 IP、反向 DNS 等) 共用的并发上限，
+//! 移动网络/计费流量下请求排队执行，而不是同时发出一批
+
+use std::sync::OnceLock;
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::config::Config;
+
+static NET_LIMIT: OnceLock<Semaphore> = OnceLock::new();
+
+/// 发起网络请求前获取许可，持有期间计入并发数 (drop 即释放)。
+/// 容量取自 Config.max_net_concurrency (首次使用时读取，至少为 1)
+pub async fn permit() -> SemaphorePermit<'static> {
+    let limit = NET_LIMIT.get_or_init(|| {
+        let n = Config::get().try_read().map(|c| c.max_net_concurrency).unwrap_or(2);
+        limiter(n)
+    });
+    // 信号量从不 close，acquire 不会失败
+    limit.acquire().await.expect("net semaphore closed")
+}
+
+/// 配置为 0 时仍允许一个请求，否则所有网络功能都会永远等待
+fn limiter(max_concurrency: usize) -> Semaphore {
+    Semaphore::new(max_concurrency.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::{Duration, Instant};
+
+    /// 两个同时发起的假请求 (各 100ms)，返回 (最大并发数, 总耗时)
+    async fn two_fetches(limit: &Semaphore) -> (usize, Duration) {
+        let (active, peak) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        let fetch = || async {
+            let _permit = limit.acquire().await.unwrap();
+            let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            active.fetch_sub(1, Ordering::SeqCst);
+        };
+        let start = Instant::now();
+        tokio::join!(fetch(), fetch());
+        (peak.load(Ordering::SeqCst), start.elapsed())
+    }
+
+    #[tokio::test]
+    async fn single_permit_serializes_fetches() {
+        for n in [0, 1] {
+            let (peak, took) = two_fetches(&limiter(n)).await;
+            assert_eq!(peak, 1, "limit {n}");
+            assert!(took >= Duration::from_millis(200), "limit {n}: {took:?}");
+        }
+        let (peak, took) = two_fetches(&limiter(2)).await;
+        assert_eq!(peak, 2);
+        assert!(took < Duration::from_millis(200), "{took:?}");
+    }
+}
@@ -2,11 +2,13 @@
 
 use directories::{BaseDirs, ProjectDirs, UserDirs};
 use tokio::sync::{RwLock, broadcast};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, OnceLock};
 use std::{env, fs};
 
 use crate::config::Config;
+use crate::constans::PROJECT_QUALIFIER;
 use crate::message::GlobalEvent;
 
 pub static ATLAS_PATHS: OnceLock<AtlasPath> = OnceLock::new();
@@ -18,7 +20,9 @@ pub struct AtlasPath {
     pub current_dir: PathBuf,
 
     // Project Dirs (基于 atlas 名称)
-    // pub config_file: PathBuf,
+    pub config_dir: PathBuf, // atlas_cfg.json 所在目录
+    config_file: PathBuf,    // 启动时确定的配置文件 (迁移失败时仍指向旧路径)
+    config_migration: Option<Result<PathBuf, String>>, // 旧配置迁移结果，logger 就绪后再记录
     pub proj_dir: PathBuf,
     pub cache_dir: PathBuf,
     pub state_dir: Option<PathBuf>,
@@ -55,29 +59,32 @@ impl AtlasPath {
             let exe_dir = exe_path.parent().unwrap_or(&exe_path).to_path_buf();
             let current_dir = env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
 
-            let proj = ProjectDirs::from("org", "oelabs", "atlas")
+            // 所有项目路径只在这里解析，配置与数据使用同一个 ProjectDirs 限定名
+            let proj = ProjectDirs::from(PROJECT_QUALIFIER.0, PROJECT_QUALIFIER.1, PROJECT_QUALIFIER.2)
                 .expect("Failed to get project directories");
             let base = BaseDirs::new().expect("Failed to get base directories");
             let user = UserDirs::new().expect("Failed to get user directories");
 
-            // 业务路径：脚本存放在 Home/script，数据库存放在 Data/db
-            let script_dir = base.home_dir().join("script");
-            let db_dir = proj.data_dir().join("db");
+            let atlas_home = env::var_os("ATLAS_HOME").filter(|v| !v.is_empty()).map(PathBuf::from);
+            let xdg_config = env::var_os("XDG_CONFIG_HOME").map(PathBuf::from);
+            let (config_dir, proj_dir, cache_dir, state_dir) =
+                Self::resolve_dirs(&proj, atlas_home.as_deref(), xdg_config.as_deref());
 
-            // 自动创建核心业务目录
-            let _ = fs::create_dir_all(&script_dir);
-            let _ = fs::create_dir_all(&db_dir);
-            let _ = fs::create_dir_all(proj.config_dir());
+            Self::create_core_dirs(&config_dir, &proj_dir);
+            let (config_file, config_migration) =
+                Self::migrate_legacy_config(&config_dir, base.config_dir(), atlas_home.is_some());
 
             Self {
                 exe_dir,
                 current_dir,
 
-                // config_file: proj.config_dir().join("atlas_cfg.json"),
-                proj_dir: proj.data_dir().to_path_buf(),
-                cache_dir: proj.cache_dir().to_path_buf(),
-                state_dir: proj.state_dir().map(|p| p.to_path_buf()),
-                preference_dir: proj.preference_dir().to_path_buf(),
+                preference_dir: if atlas_home.is_some() { config_dir.clone() } else { proj.preference_dir().to_path_buf() },
+                config_dir,
+                config_file,
+                config_migration,
+                proj_dir,
+                cache_dir,
+                state_dir,
 
                 home_dir: base.home_dir().to_path_buf(),
                 base_config_dir: base.config_dir().to_path_buf(),
@@ -98,32 +105,168 @@ impl AtlasPath {
         });
     }
 
+    /// 解析 (配置, 数据, 缓存, 状态) 目录。
+    /// ATLAS_HOME 把配置、数据、缓存集中到一个目录下 (便携安装 / 多实例)；
+    /// 否则配置目录额外尊重 XDG_CONFIG_HOME (directories 只在 Linux 上读取它)
+    fn resolve_dirs(
+        proj: &ProjectDirs,
+        atlas_home: Option<&Path>,
+        xdg_config: Option<&Path>,
+    ) -> (PathBuf, PathBuf, PathBuf, Option<PathBuf>) {
+        match atlas_home {
+            Some(home) => (home.join("config"), home.join("data"), home.join("cache"), Some(home.join("state"))),
+            None => (
+                xdg_config
+                    .filter(|p| p.is_absolute())
+                    .map(|p| p.join(PROJECT_QUALIFIER.2))
+                    .unwrap_or_else(|| proj.config_dir().to_path_buf()),
+                proj.data_dir().to_path_buf(),
+                proj.cache_dir().to_path_buf(),
+                proj.state_dir().map(|p| p.to_path_buf()),
+            ),
+        }
+    }
+
+    /// 自动创建核心业务目录：配置目录与 Data/db。
+    /// 脚本目录 (Home/script) 不在这里创建，由 get_script_dir 首次使用时创建，才能判断是否需要写入示例
+    fn create_core_dirs(config_dir: &Path, proj_dir: &Path) {
+        let _ = fs::create_dir_all(proj_dir.join("db"));
+        let _ = fs::create_dir_all(config_dir);
+    }
+
+    /// 测试用：会写入测试配置文件的测试持有这把锁串行执行，互不覆盖
+    #[cfg(test)]
+    pub fn config_file_lock() -> std::sync::MutexGuard<'static, ()> {
+        static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        LOCK.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 测试用：所有路径都放在 <临时目录>/atlas_test_<pid> 下，不接触真实的用户目录
+    #[cfg(test)]
+    pub fn init_for_test() -> &'static AtlasPath {
+        ATLAS_PATHS.get_or_init(|| {
+            let root = env::temp_dir().join(format!("atlas_test_{}", std::process::id()));
+            let config_dir = root.join("config");
+            Self::create_core_dirs(&config_dir, &root.join("data"));
+            Self {
+                exe_dir: root.clone(),
+                current_dir: root.clone(),
+                preference_dir: config_dir.clone(),
+                config_file: config_dir.join("atlas_cfg.json"),
+                config_migration: None,
+                config_dir,
+                proj_dir: root.join("data"),
+                cache_dir: root.join("cache"),
+                state_dir: Some(root.join("state")),
+                home_dir: root.join("home"),
+                base_config_dir: root.join("base_config"),
+                base_data_dir: root.join("base_data"),
+                base_cache_dir: root.join("base_cache"),
+                runtime_dir: None,
+                desktop: None,
+                document: None,
+                download: None,
+                audio: None,
+                picture: None,
+                video: None,
+                public: None,
+                font: None,
+                template: None,
+            }
+        })
+    }
+
+    /// 旧版本配置写在 <系统配置目录>/atlas 下 (macOS/Windows 上与项目目录不同)：
+    /// 新路径不存在时复制过来 (不移动，旧版本或其它实例仍可继续使用原文件)。
+    /// 设置了 ATLAS_HOME 的独立实例从不迁移；复制失败时继续使用旧文件
+    fn migrate_legacy_config(
+        config_dir: &Path,
+        base_config_dir: &Path,
+        isolated: bool,
+    ) -> (PathBuf, Option<Result<PathBuf, String>>) {
+        let path = config_dir.join("atlas_cfg.json");
+        let legacy = base_config_dir.join("atlas/atlas_cfg.json");
+        if isolated || path.exists() || legacy == path || !legacy.is_file() {
+            return (path, None);
+        }
+        match fs::copy(&legacy, &path) {
+            Ok(_) => (path, Some(Ok(legacy))),
+            Err(e) => (legacy, Some(Err(e.to_string()))),
+        }
+    }
+
+    /// 在 logger 初始化之后调用，记录启动时的配置迁移结果
+    pub fn report_config_migration() {
+        match &Self::get().config_migration {
+            Some(Ok(from)) => log::info!("Copied config from {:?} to {:?}", from, Self::get().config_file),
+            Some(Err(e)) => log::warn!("Failed to migrate legacy config, using it in place: {}", e),
+            None => {}
+        }
+    }
+
     pub fn get() -> &'static AtlasPath {
         ATLAS_PATHS.get().expect("AtlasPath not initialized! Call init() in main.")
     }
 
-    /// 获取配置数据文件路径 (支持 override 检查)
+    /// 不 panic 的版本，供 panic hook 等可能早于 init 的地方使用
+    pub fn try_get() -> Option<&'static AtlasPath> {
+        ATLAS_PATHS.get()
+    }
+
+    /// 程序目录下的 override 配置文件，存在时优先于系统配置目录中的配置
+    pub fn get_config_override_path() -> PathBuf {
+        Self::get().exe_dir.join("atlas_cfg_override.json")
+    }
+
+    /// 获取配置数据文件路径 (支持 override 检查)。加载、保存与热加载监听都只通过这里取路径
     pub fn get_config_path() -> PathBuf {
         let p = Self::get();
-        let override_path = p.exe_dir.join("atlas_cfg_override.json");
+        let override_path = Self::get_config_override_path();
         if override_path.exists() {
-            override_path
-        } else {
-            // 注意：这里使用 base_config_dir 可能更符合 ProjectDirs 逻辑
-            let path = p.base_config_dir.join("atlas/atlas_cfg.json");
-            if let Some(parent) = path.parent() { let _ = fs::create_dir_all(parent); }
-            path
+            return override_path;
         }
+        // 旧配置迁移只在 AtlasPath::init 中进行一次，这里没有文件系统副作用
+        p.config_file.clone()
     }
 
     /// 获取脚本存放目录 (关联函数)
     pub fn get_script_dir() -> PathBuf {
         let p = Self::get();
         let path = p.home_dir.join("script");
-        let _ = fs::create_dir_all(&path);
+        let seed = GLOBAL_CONFIG
+            .get()
+            .and_then(|c| c.try_read().ok().map(|cfg| cfg.seed_scripts))
+            .unwrap_or(true);
+        Self::ensure_script_dir(&path, seed);
         path
     }
 
+    /// 创建脚本目录；只有本次新建 (且 seed 开启) 时才写入示例，已有目录保持不动
+    fn ensure_script_dir(path: &Path, seed: bool) {
+        let created = !path.exists();
+        let _ = fs::create_dir_all(path);
+        if created && seed {
+            Self::seed_script_dir(path);
+        }
+    }
+
+    /// 给刚创建的空目录写入示例脚本，让 Task 页首次打开就有一个可运行的任务
+    fn seed_script_dir(dir: &Path) {
+        let empty = fs::read_dir(dir).map(|mut d| d.next().is_none()).unwrap_or(false);
+        if !empty {
+            return;
+        }
+        let files = [
+            (crate::constans::SAMPLE_SCRIPT_NAME, crate::constans::SAMPLE_SCRIPT),
+            ("README.md", crate::constans::SAMPLE_SCRIPT_README),
+        ];
+        for (name, content) in files {
+            if let Err(e) = fs::write(dir.join(name), content) {
+                log::warn!("Failed to write sample script {}: {}", name, e);
+            }
+        }
+    }
+
     /// 获取数据库存放目录 (关联函数)
     pub fn get_db_dir() -> PathBuf {
         let p = Self::get();
@@ -132,51 +275,68 @@ impl AtlasPath {
         path
     }
 
-    pub fn collect_dirs() -> Vec<String> {
+    /// Info 页目录窗格的内容：(显示文本, 路径)，分组标题与空行的路径为 None，
+    /// 只有带路径的行可以被选中 (Enter 复制到剪贴板)
+    pub fn collect_dirs() -> Vec<(String, Option<PathBuf>)> {
         let p = Self::get();
-        let mut list = Vec::new();
+        let mut list: Vec<(String, Option<PathBuf>)> = Vec::new();
+        let header = |list: &mut Vec<(String, Option<PathBuf>)>, title: &str| {
+            if !list.is_empty() {
+                list.push((String::new(), None));
+            }
+            list.push((format!("--- [ {} ] ---", title), None));
+        };
+        let entry = |label: &str, path: &PathBuf| (format!("{} {:?}", label, path), Some(path.clone()));
 
         // 1. 程序基础环境
-        list.push("--- [ Runtime Context ] ---".to_string());
-        list.push(format!("Executable Dir: {:?}", p.exe_dir));
-        list.push(format!("Working Dir:    {:?}", p.current_dir));
+        header(&mut list, "Runtime Context");
+        list.push(entry("Executable Dir:", &p.exe_dir));
+        list.push(entry("Working Dir:   ", &p.current_dir));
 
         // 2. 项目标准路径 (ProjectDirs)
-        list.push("\n--- [ Project Standard Dirs ] ---".to_string());
-        list.push(format!("Data Root:   {:?}", p.proj_dir));
-        list.push(format!("Cache Root:  {:?}", p.cache_dir));
-        list.push(format!("Preferences: {:?}", p.preference_dir));
+        header(&mut list, "Project Standard Dirs");
+        list.push(entry("Config Root:", &p.config_dir));
+        list.push(entry("Data Root:  ", &p.proj_dir));
+        list.push(entry("Cache Root: ", &p.cache_dir));
+        list.push(entry("Preferences:", &p.preference_dir));
         if let Some(state) = &p.state_dir {
-            list.push(format!("State Root:  {:?}", state));
+            list.push(entry("State Root: ", state));
         }
 
         // 3. 基础系统路径 (BaseDirs)
-        list.push("\n--- [ System Base Dirs ] ---".to_string());
-        list.push(format!("Home:        {:?}", p.home_dir));
-        list.push(format!("Base Config: {:?}", p.base_config_dir));
-        list.push(format!("Base Data:   {:?}", p.base_data_dir));
-        list.push(format!("Base Cache:  {:?}", p.base_cache_dir));
+        header(&mut list, "System Base Dirs");
+        list.push(entry("Home:       ", &p.home_dir));
+        list.push(entry("Base Config:", &p.base_config_dir));
+        list.push(entry("Base Data:  ", &p.base_data_dir));
+        list.push(entry("Base Cache: ", &p.base_cache_dir));
         if let Some(runtime) = &p.runtime_dir {
-            list.push(format!("Runtime:     {:?}", runtime));
+            list.push(entry("Runtime:    ", runtime));
         }
 
         // 4. 业务逻辑生成的路径 (Dynamic Paths)
-        list.push("\n--- [ Resolved Business Paths ] ---".to_string());
-        list.push(format!("Config File: {:?}", Self::get_config_path()));
-        list.push(format!("Scripts Dir: {:?}", Self::get_script_dir()));
-        list.push(format!("Database Dir: {:?}", Self::get_db_dir()));
+        header(&mut list, "Resolved Business Paths");
+        list.push(entry("Config File:", &Self::get_config_path()));
+        list.push(entry("Scripts Dir:", &Self::get_script_dir()));
+        list.push(entry("Database Dir:", &Self::get_db_dir()));
 
         // 5. 用户常用目录 (UserDirs - 筛选展示)
-        list.push("\n--- [ User Content Dirs ] ---".to_string());
-        if let Some(d) = &p.download { list.push(format!("Downloads: {:?}", d)); }
-        if let Some(d) = &p.document { list.push(format!("Documents: {:?}", d)); }
-        if let Some(d) = &p.desktop  { list.push(format!("Desktop:   {:?}", d)); }
-        if let Some(d) = &p.picture  { list.push(format!("Pictures:  {:?}", d)); }
-        if let Some(d) = &p.video    { list.push(format!("Videos:    {:?}", d)); }
-        if let Some(d) = &p.audio    { list.push(format!("Audios:    {:?}", d)); }
-        if let Some(d) = &p.public    { list.push(format!("Public:   {:?}", d)); }
-        if let Some(d) = &p.font    { list.push(format!("Fonts:     {:?}", d)); }
-        if let Some(d) = &p.template    { list.push(format!("Template:  {:?}", d)); }
+        header(&mut list, "User Content Dirs");
+        let user_dirs = [
+            ("Downloads:", &p.download),
+            ("Documents:", &p.document),
+            ("Desktop:  ", &p.desktop),
+            ("Pictures: ", &p.picture),
+            ("Videos:   ", &p.video),
+            ("Audios:   ", &p.audio),
+            ("Public:   ", &p.public),
+            ("Fonts:    ", &p.font),
+            ("Template: ", &p.template),
+        ];
+        for (label, dir) in user_dirs {
+            if let Some(d) = dir {
+                list.push(entry(label, d));
+            }
+        }
 
         list
     }
@@ -219,17 +379,67 @@ pub struct GlobIO;
 
 // 全局静态实例
 static GLOB_SENDER: OnceLock<GlobSend> = OnceLock::new();
+static GLOB_CAPACITY: AtomicUsize = AtomicUsize::new(0);
+/// 所有接收端累计因落后 (Lagged) 丢弃的消息数
+static GLOB_LAGGED: AtomicU64 = AtomicU64::new(0);
+
+/// 总线运行状况，用于排查丢消息
+#[derive(Debug, Clone, Copy)]
+pub struct GlobStats {
+    pub capacity: usize,
+    pub receivers: usize,
+    pub lagged: u64,
+}
 
 impl GlobIO {
-    /// 初始化通信总线，需在 main 启动早期调用
-    /// capacity: 缓冲区大小，例如 100
+    /// 初始化通信总线，需在 main 启动早期 (Config::init 之后) 调用
+    /// 容量取自 Config.event_bus_capacity
     pub fn init() {
         GLOB_SENDER.get_or_init(|| {
+            let capacity = Config::get()
+                .try_read()
+                .map(|c| c.event_bus_capacity)
+                .unwrap_or(1024)
+                .max(16);
+            GLOB_CAPACITY.store(capacity, Ordering::Relaxed);
+            let (tx, _) = broadcast::channel(capacity);
+            tx
+        });
+    }
+
+    /// 非阻塞接收：跳过 Lagged 并计数，只有通道为空/关闭时返回 None
+    pub fn try_recv(rx: &mut GlobRecv) -> Option<GlobalEvent> {
+        loop {
+            match rx.try_recv() {
+                Ok(ev) => return Some(ev),
+                Err(broadcast::error::TryRecvError::Lagged(n)) => Self::record_lag(n),
+                Err(_) => return None,
+            }
+        }
+    }
+
+    /// 测试用：不读取 Config，直接以默认容量初始化总线
+    #[cfg(test)]
+    pub fn init_for_test() {
+        GLOB_SENDER.get_or_init(|| {
+            GLOB_CAPACITY.store(1024, Ordering::Relaxed);
             let (tx, _) = broadcast::channel(1024);
             tx
         });
     }
 
+    pub fn record_lag(n: u64) {
+        GLOB_LAGGED.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub fn stats() -> GlobStats {
+        GlobStats {
+            capacity: GLOB_CAPACITY.load(Ordering::Relaxed),
+            receivers: GLOB_SENDER.get().map(|tx| tx.receiver_count()).unwrap_or(0),
+            lagged: GLOB_LAGGED.load(Ordering::Relaxed),
+        }
+    }
+
     /// 获取发送端句柄 (Clone 是廉价的)
     pub fn send() -> GlobSend {
         GLOB_SENDER
@@ -260,9 +470,16 @@ static GLOBAL_CONFIG: OnceLock<SharedConfig> = OnceLock::new();
 impl Config {
     /// 初始化全局配置
     pub fn init() {
+        AtlasPath::report_config_migration();
         GLOBAL_CONFIG.get_or_init(|| {Arc::new(RwLock::new(Config::load_from_disk()))});
     }
 
+    /// 测试用：以默认值初始化全局配置，不读取磁盘
+    #[cfg(test)]
+    pub fn init_for_test() {
+        GLOBAL_CONFIG.get_or_init(|| Arc::new(RwLock::new(Config::default())));
+    }
+
     /// 获取全局配置句柄
     pub fn get() -> SharedConfig {
         GLOBAL_CONFIG
@@ -281,10 +498,136 @@ impl Config {
     }
 }
 
+/// 整数千分位分组，例如 1234567 -> "1,234,567"
+pub fn format_count(n: u64) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
 
+/// 按 Config.group_digits 决定是否分组，供各标签页统一显示数字
+pub fn display_count(n: u64) -> String {
+    let group = Config::get().try_read().map(|c| c.group_digits).unwrap_or(true);
+    if group { format_count(n) } else { n.to_string() }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constans::{SAMPLE_SCRIPT, SAMPLE_SCRIPT_NAME};
 
+    fn scratch(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("atlas_scripts_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
 
+    #[test]
+    fn fresh_script_dir_is_seeded() {
+        let dir = scratch("fresh");
+        AtlasPath::ensure_script_dir(&dir, true);
+        assert_eq!(fs::read_to_string(dir.join(SAMPLE_SCRIPT_NAME)).unwrap(), SAMPLE_SCRIPT);
+        assert!(dir.join("README.md").is_file());
+        let _ = fs::remove_dir_all(&dir);
+    }
 
+    #[test]
+    fn existing_or_opted_out_dirs_are_left_alone() {
+        let dir = scratch("existing");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("mine.ts"), "console.log(1)").unwrap();
+        AtlasPath::ensure_script_dir(&dir, true);
+        let names: Vec<_> = fs::read_dir(&dir).unwrap().flatten().map(|e| e.file_name()).collect();
+        assert_eq!(names, ["mine.ts"]);
+        let _ = fs::remove_dir_all(&dir);
+
+        // 用户清空过的目录不再重新写入示例
+        let dir = scratch("emptied");
+        fs::create_dir_all(&dir).unwrap();
+        AtlasPath::ensure_script_dir(&dir, true);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = fs::remove_dir_all(&dir);
+
+        let dir = scratch("opt_out");
+        AtlasPath::ensure_script_dir(&dir, false);
+        assert!(dir.is_dir());
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn first_use_after_init_seeds_the_script_dir() {
+        // 与 init 相同的启动流程 (create_core_dirs) 不能提前创建脚本目录，否则示例永远不会写入
+        Config::init_for_test();
+        let paths = AtlasPath::init_for_test();
+        assert!(paths.proj_dir.join("db").is_dir());
+        let dir = AtlasPath::get_script_dir();
+        assert_eq!(dir, paths.home_dir.join("script"));
+        assert_eq!(fs::read_to_string(dir.join(SAMPLE_SCRIPT_NAME)).unwrap(), SAMPLE_SCRIPT);
+        assert!(dir.join("README.md").is_file());
+    }
 
+    #[test]
+    fn sample_script_uses_escape_text_not_raw_bytes() {
+        assert!(!SAMPLE_SCRIPT.contains('\x1b'));
+        assert!(SAMPLE_SCRIPT.contains(r"\x1b[32m"));
+    }
 
+    #[test]
+    fn format_count_groups_thousands() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(999), "999");
+        assert_eq!(format_count(1000), "1,000");
+        assert_eq!(format_count(123_456), "123,456");
+        assert_eq!(format_count(1_234_567), "1,234,567");
+        assert_eq!(format_count(u64::MAX), "18,446,744,073,709,551,615");
+    }
+
+    #[test]
+    fn flooded_receiver_reports_lag() {
+        let (tx, mut rx) = broadcast::channel::<GlobalEvent>(4);
+        for i in 0..10 {
+            let _ = tx.send(GlobalEvent::Status(i.to_string(), crate::message::StatusLevel::Info, None));
+        }
+        let before = GlobIO::stats().lagged;
+        let mut received = Vec::new();
+        while let Some(GlobalEvent::Status(msg, ..)) = GlobIO::try_recv(&mut rx) {
+            received.push(msg);
+        }
+        // 只剩最新的 4 条，丢弃的 6 条计入全局计数 (其它测试可能同时累加)
+        assert_eq!(received, ["6", "7", "8", "9"]);
+        assert!(GlobIO::stats().lagged >= before + 6);
+    }
+
+    #[test]
+    fn config_and_data_resolve_under_the_same_base() {
+        let proj = ProjectDirs::from(PROJECT_QUALIFIER.0, PROJECT_QUALIFIER.1, PROJECT_QUALIFIER.2).unwrap();
+
+        // ATLAS_HOME：配置与数据都在同一个目录下，且优先于 XDG_CONFIG_HOME
+        let home = Path::new("/opt/atlas_home");
+        let (config, data, cache, state) = AtlasPath::resolve_dirs(&proj, Some(home), Some(Path::new("/xdg")));
+        assert_eq!(config.parent(), Some(home));
+        assert_eq!(data.parent(), Some(home));
+        assert_eq!(cache.parent(), Some(home));
+        assert_eq!(state.as_deref().and_then(Path::parent), Some(home));
+
+        // 默认：配置与数据来自同一个 ProjectDirs 限定名，末级目录一致
+        let (config, data, ..) = AtlasPath::resolve_dirs(&proj, None, None);
+        assert_eq!(config, proj.config_dir());
+        assert_eq!(data, proj.data_dir());
+        assert_eq!(config.file_name(), data.file_name());
+
+        // XDG_CONFIG_HOME 只改变配置的基础目录，项目名不变；相对路径被忽略
+        let (config, ..) = AtlasPath::resolve_dirs(&proj, None, Some(Path::new("/xdg")));
+        assert_eq!(config, Path::new("/xdg").join(PROJECT_QUALIFIER.2));
+        assert_eq!(config.file_name(), data.file_name());
+        let (config, ..) = AtlasPath::resolve_dirs(&proj, None, Some(Path::new("relative")));
+        assert_eq!(config, proj.config_dir());
+    }
+}
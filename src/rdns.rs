@@ -0,0 +1,123 @@
+//! 最小化的反向 DNS (PTR) 查询：单个 UDP 请求，不引入额外的解析库
+
+use std::net::IpAddr;
+use std::time::Duration;
+
+use tokio::net::UdpSocket;
+
+const DNS_TIMEOUT: Duration = Duration::from_secs(3);
+const FALLBACK_NAMESERVER: &str = "1.1.1.1";
+
+/// 查询 ip 的 PTR 记录，任何失败 (超时、无记录、格式错误) 都返回 None
+pub async fn reverse_lookup(ip: IpAddr) -> Option<String> {
+    let server = format!("{}:53", nameserver());
+    let id = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u16)
+        .unwrap_or(0x1234);
+    let query = build_query(id, &ptr_name(ip));
+
+    let _permit = crate::net::permit().await;
+    let bind = if server.starts_with('[') || server.matches(':').count() > 1 { "[::]:0" } else { "0.0.0.0:0" };
+    let sock = UdpSocket::bind(bind).await.ok()?;
+    sock.connect(&server).await.ok()?;
+    sock.send(&query).await.ok()?;
+
+    let mut buf = [0u8; 512];
+    let n = tokio::time::timeout(DNS_TIMEOUT, sock.recv(&mut buf)).await.ok()?.ok()?;
+    parse_ptr_answer(&buf[..n], id)
+}
+
+/// 取 /etc/resolv.conf 中的第一个 nameserver (Android 上通常不存在，使用公共 DNS)
+fn nameserver() -> String {
+    std::fs::read_to_string("/etc/resolv.conf")
+        .ok()
+        .and_then(|s| {
+            s.lines()
+                .filter_map(|l| l.trim().strip_prefix("nameserver"))
+                .map(|v| v.trim().to_string())
+                .find(|v| v.parse::<IpAddr>().is_ok())
+        })
+        .map(|v| if v.contains(':') { format!("[{}]", v) } else { v })
+        .unwrap_or_else(|| FALLBACK_NAMESERVER.to_string())
+}
+
+/// 1.2.3.4 -> 4.3.2.1.in-addr.arpa ；IPv6 按半字节倒序 -> ip6.arpa
+fn ptr_name(ip: IpAddr) -> String {
+    match ip {
+        IpAddr::V4(v4) => {
+            let o = v4.octets();
+            format!("{}.{}.{}.{}.in-addr.arpa", o[3], o[2], o[1], o[0])
+        }
+        IpAddr::V6(v6) => {
+            let mut parts: Vec<String> = Vec::with_capacity(33);
+            for b in v6.octets().iter().rev() {
+                parts.push(format!("{:x}", b & 0x0f));
+                parts.push(format!("{:x}", b >> 4));
+            }
+            parts.push("ip6.arpa".into());
+            parts.join(".")
+        }
+    }
+}
+
+fn build_query(id: u16, name: &str) -> Vec<u8> {
+    let mut q = Vec::with_capacity(64);
+    q.extend_from_slice(&id.to_be_bytes());
+    q.extend_from_slice(&[0x01, 0x00]); // RD
+    q.extend_from_slice(&[0, 1, 0, 0, 0, 0, 0, 0]); // QDCOUNT=1
+    for label in name.split('.') {
+        q.push(label.len() as u8);
+        q.extend_from_slice(label.as_bytes());
+    }
+    q.push(0);
+    q.extend_from_slice(&[0, 12, 0, 1]); // QTYPE=PTR, QCLASS=IN
+    q
+}
+
+fn parse_ptr_answer(buf: &[u8], id: u16) -> Option<String> {
+    if buf.len() < 12 || u16::from_be_bytes([buf[0], buf[1]]) != id || buf[3] & 0x0f != 0 {
+        return None;
+    }
+    let qd = u16::from_be_bytes([buf[4], buf[5]]);
+    let an = u16::from_be_bytes([buf[6], buf[7]]);
+
+    let mut pos = 12;
+    for _ in 0..qd {
+        pos = read_name(buf, pos)?.1 + 4;
+    }
+    for _ in 0..an {
+        let (_, after) = read_name(buf, pos)?;
+        let rtype = u16::from_be_bytes([*buf.get(after)?, *buf.get(after + 1)?]);
+        let rdlen = u16::from_be_bytes([*buf.get(after + 8)?, *buf.get(after + 9)?]) as usize;
+        let rdata = after + 10;
+        if rtype == 12 {
+            return read_name(buf, rdata).map(|(n, _)| n);
+        }
+        pos = rdata + rdlen;
+    }
+    None
+}
+
+/// 解析 (可能带压缩指针的) 域名，返回 (名称, 名称之后的位置)
+fn read_name(buf: &[u8], mut pos: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut end = None;
+    // 限制跳转次数，防止恶意的指针循环
+    for _ in 0..64 {
+        let len = *buf.get(pos)? as usize;
+        if len == 0 {
+            return Some((labels.join("."), end.unwrap_or(pos + 1)));
+        }
+        if len & 0xc0 == 0xc0 {
+            let ptr = ((len & 0x3f) << 8) | *buf.get(pos + 1)? as usize;
+            end.get_or_insert(pos + 2);
+            pos = ptr;
+            continue;
+        }
+        let label = buf.get(pos + 1..pos + 1 + len)?;
+        labels.push(String::from_utf8_lossy(label).into_owned());
+        pos += 1 + len;
+    }
+    None
+}
@@ -1,7 +1,7 @@
 use crate::{
     message::{GlobalEvent, Progress, StatusLevel}, prelude::{GlobIO, GlobRecv}, ui::component::Component
 };
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     Frame,
     layout::{Alignment, Constraint, Layout, Rect},
@@ -9,6 +9,7 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Gauge, Paragraph},
 };
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
 pub struct HintComponent; 
@@ -61,8 +62,16 @@ impl Component for HintComponent {
     }
 }
 
+/// 排队等待确认的错误最多保留的条数
+const ERROR_QUEUE_CAP: usize = 32;
+
 pub struct NotifyComponent {
+    // Info / Success / Warning：只显示最新一条，5 秒后自动消失
     current: Option<(String, StatusLevel, Instant)>,
+    // Error：常驻并排队，Esc / 'c' 逐条确认，最新的在队尾
+    errors: VecDeque<(String, Instant)>,
+    // 上次渲染时错误已显示的秒数，用于每秒刷新计时
+    shown_secs: u64,
     recv: GlobRecv,
 }
 
@@ -70,6 +79,8 @@ impl Component for NotifyComponent {
     fn init() -> Self {
         Self {
             current: None,
+            errors: VecDeque::new(),
+            shown_secs: 0,
             recv: GlobIO::recv(),
         }
     }
@@ -78,18 +89,26 @@ impl Component for NotifyComponent {
         let mut changed = false;
 
         // 1. 接收新消息
-        while let Ok(msg) = self.recv.try_recv() {
+        while let Some(msg) = GlobIO::try_recv(&mut self.recv) {
             if let GlobalEvent::Status(content, level, _) = msg {
-                self.current = Some((content, level, Instant::now()));
+                self.push(content, level);
                 changed = true;
             }
         }
 
-        // 2. 自动过期逻辑 (例如 5 秒消失)
-        if let Some((_, level, start_time)) = &self.current {
-            let timeout = if *level == StatusLevel::Error { 60 } else { 5 };
-            if start_time.elapsed() > Duration::from_secs(timeout) {
-                self.current = None;
+        // 2. 非错误消息自动过期 (5 秒消失)
+        if let Some((_, _, start_time)) = &self.current
+            && start_time.elapsed() > Duration::from_secs(5)
+        {
+            self.current = None;
+            changed = true;
+        }
+
+        // 3. 错误显示时长每秒走一次
+        if let Some((_, since)) = self.errors.back() {
+            let secs = since.elapsed().as_secs();
+            if secs != self.shown_secs {
+                self.shown_secs = secs;
                 changed = true;
             }
         }
@@ -97,6 +116,19 @@ impl Component for NotifyComponent {
     }
 
     fn render(&mut self, f: &mut Frame, area: Rect) {
+        // 错误优先于普通通知
+        if let Some((content, since)) = self.errors.back() {
+            let more = match self.errors.len() {
+                1 => String::new(),
+                n => format!(" (+{} more)", n - 1),
+            };
+            let text = format!("{} [{}s]{} · Esc", content, since.elapsed().as_secs(), more);
+            let p = Paragraph::new(text)
+                .style(Style::default().fg(Color::Red))
+                .alignment(Alignment::Center);
+            f.render_widget(p, area);
+            return;
+        }
         if let Some((content, level, _)) = &self.current {
             let color = match level {
                 StatusLevel::Info => Color::Cyan,
@@ -111,8 +143,38 @@ impl Component for NotifyComponent {
         }
     }
 
-    fn handle_key(&mut self, _key: KeyEvent) -> bool {
-        false
+    // 错误显示时长按秒走
+    fn desired_tick_interval(&self) -> Option<Duration> {
+        (!self.errors.is_empty()).then_some(Duration::from_secs(1))
+    }
+
+    /// 有待确认的错误时，Esc / 'c' 确认当前 (最新) 一条，露出下一条；
+    /// 只收到当前标签页没有使用的按键 (见 App::handle_key)
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.errors.is_empty() || !key.modifiers.is_empty() {
+            return false;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('c') => {
+                self.errors.pop_back();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl NotifyComponent {
+    /// 错误进入待确认队列 (超出 ERROR_QUEUE_CAP 时丢弃最早的)，其它级别替换当前通知
+    fn push(&mut self, content: String, level: StatusLevel) {
+        if level == StatusLevel::Error && !content.is_empty() {
+            self.errors.push_back((content, Instant::now()));
+            while self.errors.len() > ERROR_QUEUE_CAP {
+                self.errors.pop_front();
+            }
+        } else {
+            self.current = Some((content, level, Instant::now()));
+        }
     }
 }
 
@@ -133,7 +195,7 @@ impl Component for ProgressComponent {
 
     fn update(&mut self) -> bool {
         let mut changed = false;
-        while let Ok(msg) = self.recv.try_recv() {
+        while let Some(msg) = GlobIO::try_recv(&mut self.recv) {
             if let GlobalEvent::Status(_, level, Some(prog)) = msg {
                 self.state = Some((prog, level));
                 changed = true;
@@ -151,7 +213,10 @@ impl Component for ProgressComponent {
         changed
     }
 
-    
+    // Loading 动画约 10 帧刷新一次 (见 update)
+    fn desired_tick_interval(&self) -> Option<Duration> {
+        matches!(self.state, Some((Progress::Loading, _))).then_some(Duration::from_millis(8))
+    }
 
     fn render(&mut self, f: &mut Frame, area: Rect) {
         let (prog, level) = match &self.state {
@@ -229,3 +294,40 @@ pub fn button_components_init() -> Vec<Box<dyn Component>> {
         Box::new(ProgressComponent::init()),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use ratatui::{Terminal, backend::TestBackend};
+
+    fn shown(comp: &mut NotifyComponent) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(60, 1)).unwrap();
+        terminal.draw(|f| comp.render(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        (0..60).map(|x| buf[(x, 0)].symbol()).collect::<String>().trim().to_string()
+    }
+
+    #[test]
+    fn queued_errors_are_revealed_one_at_a_time() {
+        GlobIO::init_for_test();
+        let mut comp = NotifyComponent::init();
+        for msg in ["disk full", "db locked", "task crashed"] {
+            comp.push(msg.into(), StatusLevel::Error);
+        }
+        comp.push("saved".into(), StatusLevel::Success);
+
+        // 最新的错误在最前，普通通知不会盖过错误
+        assert!(shown(&mut comp).starts_with("task crashed [0s] (+2 more)"));
+        // 带修饰键的 c 不用于确认
+        assert!(!comp.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+        assert!(comp.handle_key(KeyEvent::from(KeyCode::Esc)));
+        assert!(shown(&mut comp).starts_with("db locked [0s] (+1 more)"));
+        assert!(comp.handle_key(KeyEvent::from(KeyCode::Char('c'))));
+        assert!(shown(&mut comp).starts_with("disk full [0s] ·"));
+        assert!(comp.handle_key(KeyEvent::from(KeyCode::Esc)));
+        assert_eq!(shown(&mut comp), "saved");
+        // 没有错误时 Esc 留给其它组件
+        assert!(!comp.handle_key(KeyEvent::from(KeyCode::Esc)));
+    }
+}
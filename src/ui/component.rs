@@ -4,9 +4,14 @@ use crate::{
 use ratatui::{Frame, layout::Rect};
 
 pub trait Component: Send + Sync {
-    // 同步函数：由主循环高频调用，内部使用 try_recv 检查异步状态
+    // 同步函数：由主循环高频调用，处理较重的状态更新；update_background_tabs 关闭时只对当前页调用
     fn update(&mut self) -> bool;
 
+    // 消费广播订阅 (try_recv)，只记录收到的数据：App 每拍对所有页调用，即使处于后台也不会 Lagged
+    fn drain(&mut self) -> bool {
+        false
+    }
+
     // 同步渲染：根据当前内存状态绘图
     fn render(&mut self, f: &mut Frame, area: Rect); //此函数不能异步，因此需要使用同步解锁
 
@@ -16,6 +21,12 @@ pub trait Component: Send + Sync {
     fn init() -> Self
     where
         Self: Sized;
+
+    // 希望主循环驱动 update/render 的间隔：动画组件返回较短的值，静态组件返回 None
+    fn desired_tick_interval(&self) -> Option<std::time::Duration> {
+        None
+    }
+
     // fn init(config: SharedConfig, glob_send: GlobSend, glob_recv: GlobRecv) -> Self
     // where
     //     Self: Sized;
@@ -1,60 +1,114 @@
 use crate::{
     config::{Config, SharedConfig}, 
     message::{DynamicPayload, GlobalEvent}, 
-    prelude::{GlobIO, GlobRecv}, 
+    prelude::{display_count, GlobIO, GlobRecv}, 
     ui::component::Component,
     // 假设常量定义在 constants 或 prelude 中，请根据实际位置调整
-    constans::{INFO_UPDATE_INTERVAL_BASE, INFO_UPDATE_INTERVAL_SLOW_TIMES} 
+    constans::{DB_REFRESH_MIN_SECS, DB_REFRESH_STEP_SECS}
 };
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{prelude::*, widgets::*};
+use sqlx::{Column, Row as _, TypeInfo, ValueRef, sqlite::SqliteRow};
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::task::JoinHandle;
 
 const SQLITE_STATS_KEY: &str = "sqlite_table_stats";
+const SQLITE_ROWS_KEY: &str = "sqlite_table_rows";
+/// 确认弹窗通过后广播，触发遥测维护
+const DB_MAINTENANCE_KEY: &str = "db_maintenance";
+
+/// 行浏览器每页行数
+const ROWS_PAGE_SIZE: usize = 50;
+/// 单元格最大显示宽度，超出部分以省略号截断
+const CELL_MAX_WIDTH: usize = 24;
 
 #[derive(Clone, Debug)]
 pub struct TableStat {
     pub name: String,
-    pub count: i64,
+    /// COUNT(*) 失败时为 None
+    pub count: Option<i64>,
+}
+
+/// 一次 schema 统计：单表计数失败不影响其它表，失败的表计数记为 None 并列入 errors
+#[derive(Clone, Debug, Default)]
+pub struct SchemaReport {
+    pub tables: Vec<TableStat>,
+    /// (表名, 错误信息)
+    pub errors: Vec<(String, String)>,
+}
+
+/// 行浏览器的一页数据
+#[derive(Clone, Debug)]
+pub struct TablePage {
+    pub table: String,
+    pub page: usize,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+#[derive(PartialEq)]
+enum ViewMode {
+    Schema, // 表清单
+    Rows,   // 单表行浏览
 }
 
 pub struct DatabaseComponent {
     pub config: SharedConfig,
     glob_recv: GlobRecv,
     tables: Vec<TableStat>,
+    count_errors: Vec<(String, String)>,
     table_state: TableState,
     is_loading: bool,
+
+    // 自动刷新间隔 (秒) 及对应的后台任务句柄，修改间隔时重启任务
+    refresh_secs: u64,
+    monitor: JoinHandle<()>,
+
+    // 行浏览器状态
+    view_mode: ViewMode,
+    browse_table: String,
+    browse_page: usize,
+    page_data: Option<TablePage>,
+    row_state: TableState,
+    col_offset: usize,
 }
 
 impl Component for DatabaseComponent {
     fn init() -> Self {
-        let inst = Self {
-            config: Config::get(),
-            glob_recv: GlobIO::recv(),
-            tables: Vec::new(),
-            table_state: TableState::default(),
-            is_loading: true,
-        };
-
-        // 启动自动化周期抓取任务
-        Self::spawn_periodic_monitor();
-        inst
+        Self::with_config(Config::get())
     }
 
     fn update(&mut self) -> bool {
+        false
+    }
+
+    // 统计与分页结果来自后台任务，维护请求也要在后台时执行
+    fn drain(&mut self) -> bool {
         let mut changed = false;
-        while let Ok(event) = self.glob_recv.try_recv() {
+        while let Some(event) = GlobIO::try_recv(&mut self.glob_recv) {
             if let GlobalEvent::Data { key, data } = event {
                 if key == SQLITE_STATS_KEY {
-                    if let Ok(stats) = data.0.downcast::<Vec<TableStat>>() {
-                        self.tables = (*stats).clone();
+                    if let Ok(report) = data.0.downcast::<SchemaReport>() {
+                        self.tables = report.tables.clone();
+                        self.count_errors = report.errors.clone();
                         self.is_loading = false;
                         if self.table_state.selected().is_none() && !self.tables.is_empty() {
                             self.table_state.select(Some(0));
                         }
                         changed = true;
                     }
+                } else if key == DB_MAINTENANCE_KEY {
+                    Self::spawn_maintenance();
+                } else if key == SQLITE_ROWS_KEY
+                    && let Ok(page) = data.0.downcast::<TablePage>()
+                    // 丢弃已经过期的页 (用户翻页或切换了表)
+                    && page.table == self.browse_table
+                    && page.page == self.browse_page
+                {
+                    self.row_state.select(if page.rows.is_empty() { None } else { Some(0) });
+                    self.page_data = Some((*page).clone());
+                    changed = true;
                 }
             }
         }
@@ -62,6 +116,49 @@ impl Component for DatabaseComponent {
     }
 
     fn render(&mut self, f: &mut Frame, area: Rect) {
+        match self.view_mode {
+            ViewMode::Schema => self.render_schema(f, area),
+            ViewMode::Rows => self.render_rows(f, area),
+        }
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match self.view_mode {
+            ViewMode::Schema => self.handle_schema_keys(key),
+            ViewMode::Rows => self.handle_rows_keys(key),
+        }
+    }
+}
+
+impl DatabaseComponent {
+    /// 以给定配置构造组件并启动周期刷新任务
+    fn with_config(config: SharedConfig) -> Self {
+        let refresh_secs = config
+            .try_read()
+            .map(|c| c.db_refresh_secs)
+            .unwrap_or_default()
+            .max(DB_REFRESH_MIN_SECS);
+
+        Self {
+            config,
+            glob_recv: GlobIO::recv(),
+            tables: Vec::new(),
+            count_errors: Vec::new(),
+            table_state: TableState::default(),
+            is_loading: true,
+            refresh_secs,
+            // 启动自动化周期抓取任务
+            monitor: Self::spawn_periodic_monitor(refresh_secs),
+            view_mode: ViewMode::Schema,
+            browse_table: String::new(),
+            browse_page: 0,
+            page_data: None,
+            row_state: TableState::default(),
+            col_offset: 0,
+        }
+    }
+
+    fn render_schema(&mut self, f: &mut Frame, area: Rect) {
         let chunks = Layout::vertical([
             Constraint::Length(3), // Header
             Constraint::Min(0),    // Table
@@ -78,42 +175,210 @@ impl Component for DatabaseComponent {
         );
 
         // 2. Table
-        let header_cells = ["Table Name", "Row Count"]
-            .iter()
-            .map(|h| Cell::from(*h).style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+        let header_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+        let header_cells = [
+            Cell::from("Table Name").style(header_style),
+            Cell::from(Line::from("Row Count").alignment(Alignment::Right)).style(header_style),
+        ];
         
+        // 数字列右对齐，位数不同的计数才能按个位对齐
         let rows = self.tables.iter().map(|t| {
             Row::new(vec![
                 Cell::from(t.name.clone()).style(Style::default().fg(Color::Cyan)),
-                Cell::from(t.count.to_string()).style(Style::default().fg(Color::Green)),
+                match t.count {
+                    Some(count) => Cell::from(Line::from(display_count(count.max(0) as u64)).alignment(Alignment::Right))
+                        .style(Style::default().fg(Color::Green)),
+                    None => Cell::from(Line::from("error").alignment(Alignment::Right)).style(Style::default().fg(Color::Red)),
+                },
             ])
         });
 
+        let symbol = crate::ui::style::selection_symbol();
         let table = Table::new(rows, [Constraint::Percentage(70), Constraint::Percentage(30)])
             .header(Row::new(header_cells).height(1).bottom_margin(1))
             .block(Block::default().title(" Schema Overview ").borders(Borders::LEFT | Borders::RIGHT))
-            .highlight_style(Style::default().bg(Color::Rgb(50, 50, 50)))
-            .highlight_symbol(">> ");
+            .row_highlight_style(crate::ui::style::selection_style())
+            .highlight_symbol(symbol.as_str());
 
         f.render_stateful_widget(table, chunks[1], &mut self.table_state);
 
         // 3. Footer
-        let refresh_sec = INFO_UPDATE_INTERVAL_BASE * INFO_UPDATE_INTERVAL_SLOW_TIMES;
-        let hint = if self.is_loading {
+        f.render_widget(Paragraph::new(self.schema_hint()).style(Style::default().fg(Color::DarkGray)), chunks[2]);
+    }
+
+    fn schema_hint(&self) -> String {
+        if self.is_loading {
             " Loading database schema... ".into()
+        } else if !self.count_errors.is_empty() {
+            format!(" ⚠ {} table(s) failed to count | 'r' to retry | ↑↓ to move | Enter to browse ", self.count_errors.len())
         } else {
-            format!(" Auto-refresh every {}s | 'r' to force | ↑↓ to move ", refresh_sec)
+            format!(" Auto-refresh every {}s (+/-) | 'r' to force | 'm' maintenance | ↑↓ to move | Enter to browse ", self.refresh_secs)
+        }
+    }
+
+    fn render_rows(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::vertical([
+            Constraint::Min(0),    // Rows
+            Constraint::Length(1), // Footer/Hint
+        ])
+        .split(area);
+
+        let title = format!(" 🔍 {} | Page {} ", self.browse_table, self.browse_page + 1);
+        let block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Blue));
+
+        match &self.page_data {
+            None => {
+                f.render_widget(Paragraph::new(" Loading rows... ").block(block), chunks[0]);
+            }
+            Some(page) if page.columns.is_empty() => {
+                f.render_widget(Paragraph::new(" (no columns) ").block(block), chunks[0]);
+            }
+            Some(page) => {
+                // 水平滚动：从 col_offset 开始显示列
+                let offset = self.col_offset.min(page.columns.len() - 1);
+                let widths: Vec<Constraint> = (offset..page.columns.len())
+                    .map(|c| {
+                        let w = page
+                            .rows
+                            .iter()
+                            .map(|r| r[c].chars().count())
+                            .chain(std::iter::once(page.columns[c].chars().count()))
+                            .max()
+                            .unwrap_or(0)
+                            .min(CELL_MAX_WIDTH);
+                        Constraint::Length(w as u16)
+                    })
+                    .collect();
+
+                // 整列 (忽略 NULL) 都是数字时右对齐，表头跟随
+                let numeric: Vec<bool> = (offset..page.columns.len())
+                    .map(|c| {
+                        let mut values = page.rows.iter().map(|r| r[c].as_str()).filter(|v| *v != "NULL").peekable();
+                        values.peek().is_some() && values.all(|v| v.parse::<f64>().is_ok())
+                    })
+                    .collect();
+                let aligned = |text: String, right: bool| {
+                    Line::from(text).alignment(if right { Alignment::Right } else { Alignment::Left })
+                };
+                let header = Row::new(page.columns[offset..].iter().zip(&numeric).map(|(h, &right)| {
+                    Cell::from(aligned(truncate(h, CELL_MAX_WIDTH), right))
+                        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+                }));
+                let rows = page.rows.iter().map(|r| {
+                    Row::new(
+                        r[offset..]
+                            .iter()
+                            .zip(&numeric)
+                            .map(|(v, &right)| Cell::from(aligned(truncate(v, CELL_MAX_WIDTH), right))),
+                    )
+                });
+
+                let symbol = crate::ui::style::selection_symbol();
+                let table = Table::new(rows, widths)
+                    .header(header.bottom_margin(1))
+                    .block(block)
+                    .column_spacing(2)
+                    .row_highlight_style(crate::ui::style::selection_style())
+                    .highlight_symbol(symbol.as_str());
+                f.render_stateful_widget(table, chunks[0], &mut self.row_state);
+            }
+        }
+
+        let hint = " PgUp/PgDn page | ←→ columns | ↑↓ rows | Esc back ";
+        f.render_widget(Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)), chunks[1]);
+    }
+
+    fn handle_rows_keys(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Esc => {
+                self.view_mode = ViewMode::Schema;
+                self.page_data = None;
+                true
+            }
+            KeyCode::PageDown => {
+                // 只有当前页是满的才可能还有下一页
+                let full = self
+                    .page_data
+                    .as_ref()
+                    .is_some_and(|p| p.rows.len() == ROWS_PAGE_SIZE);
+                if full {
+                    self.load_page(self.browse_page + 1);
+                }
+                true
+            }
+            KeyCode::PageUp => {
+                if self.browse_page > 0 {
+                    self.load_page(self.browse_page - 1);
+                }
+                true
+            }
+            KeyCode::Left => {
+                self.col_offset = self.col_offset.saturating_sub(1);
+                true
+            }
+            KeyCode::Right => {
+                let cols = self.page_data.as_ref().map(|p| p.columns.len()).unwrap_or(0);
+                if self.col_offset + 1 < cols {
+                    self.col_offset += 1;
+                }
+                true
+            }
+            KeyCode::Up => {
+                self.row_state.select_previous();
+                true
+            }
+            KeyCode::Down => {
+                self.row_state.select_next();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 打开当前选中表的行浏览器
+    fn open_selected_table(&mut self) {
+        let Some(stat) = self.table_state.selected().and_then(|i| self.tables.get(i)) else {
+            return;
         };
-        f.render_widget(Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)), chunks[2]);
+        self.browse_table = stat.name.clone();
+        self.col_offset = 0;
+        self.view_mode = ViewMode::Rows;
+        self.load_page(0);
     }
 
-    fn handle_key(&mut self, key: KeyEvent) -> bool {
+    fn load_page(&mut self, page: usize) {
+        self.browse_page = page;
+        self.page_data = None;
+        Self::spawn_fetch_rows(self.browse_table.clone(), page);
+    }
+
+    fn handle_schema_keys(&mut self, key: KeyEvent) -> bool {
         match key.code {
+            KeyCode::Enter => {
+                self.open_selected_table();
+                true
+            }
             KeyCode::Char('r') => {
                 self.is_loading = true;
                 Self::spawn_fetch_stats();
                 true
             }
+            // 归档 + 清理 + VACUUM，属于破坏性操作：先试运行统计影响的行数再确认
+            KeyCode::Char('m') => {
+                Self::spawn_maintenance_preview();
+                true
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.set_refresh_secs(self.refresh_secs + DB_REFRESH_STEP_SECS);
+                true
+            }
+            KeyCode::Char('-') => {
+                self.set_refresh_secs(self.refresh_secs.saturating_sub(DB_REFRESH_STEP_SECS));
+                true
+            }
             KeyCode::Up => {
                 let i = match self.table_state.selected() {
                     Some(i) => if i == 0 { self.tables.len().saturating_sub(1) } else { i - 1 },
@@ -133,19 +398,89 @@ impl Component for DatabaseComponent {
             _ => false,
         }
     }
-}
 
-impl DatabaseComponent {
-    /// 核心逻辑：计算刷新周期并建立后台长线任务
-    fn spawn_periodic_monitor() {
+    /// 修改刷新间隔：重启后台任务并持久化到配置
+    fn set_refresh_secs(&mut self, secs: u64) {
+        let secs = secs.max(DB_REFRESH_MIN_SECS);
+        if secs == self.refresh_secs {
+            return;
+        }
+        self.refresh_secs = secs;
+        self.monitor.abort();
+        self.monitor = Self::spawn_periodic_monitor(secs);
+
+        if let Ok(mut cfg) = self.config.try_write() {
+            cfg.db_refresh_secs = secs;
+            if let Err(e) = cfg.save() {
+                GlobIO::error(format!("Failed to save config: {}", e));
+            }
+        }
+    }
+
+    /// 核心逻辑：按给定周期建立后台长线任务
+    fn spawn_periodic_monitor(refresh_secs: u64) -> JoinHandle<()> {
         tokio::spawn(async move {
-            let interval_duration = Duration::from_secs(INFO_UPDATE_INTERVAL_BASE * INFO_UPDATE_INTERVAL_SLOW_TIMES);
+            let interval_duration = Duration::from_secs(refresh_secs);
             let mut interval = tokio::time::interval(interval_duration);
             
             loop {
                 interval.tick().await;
                 Self::spawn_fetch_stats();
             }
+        })
+    }
+
+    /// 试运行维护，把会归档/删除的行数放进确认提示
+    fn spawn_maintenance_preview() {
+        let (retain, archive) = Config::get()
+            .try_read()
+            .map(|c| (c.telemetry_retain_days, c.telemetry_archive_days))
+            .unwrap_or((30, 365));
+        tokio::spawn(async move {
+            let r = match crate::ui::info::TelemetryRecord::maintenance(retain, archive, true).await {
+                Ok(r) => r,
+                Err(e) => return GlobIO::error(format!("Maintenance dry run failed: {}", e)),
+            };
+            let prompt = if r.archived == 0 && r.pruned == 0 {
+                "Nothing to archive or delete. VACUUM anyway?".to_string()
+            } else {
+                format!(
+                    "This will archive {} rows (>{} days) and delete {} rows (>{} days), then VACUUM. Proceed?",
+                    display_count(r.archived),
+                    retain,
+                    display_count(r.pruned),
+                    archive
+                )
+            };
+            GlobIO::confirm(
+                prompt,
+                GlobalEvent::Data { key: DB_MAINTENANCE_KEY, data: DynamicPayload(Arc::new(())) },
+            );
+        });
+    }
+
+    /// 后台执行遥测维护，完成后刷新表统计
+    fn spawn_maintenance() {
+        let (retain, archive) = Config::get()
+            .try_read()
+            .map(|c| (c.telemetry_retain_days, c.telemetry_archive_days))
+            .unwrap_or((30, 365));
+        tokio::spawn(async move {
+            let _ = GlobIO::send().send(GlobalEvent::Status(
+                "Running database maintenance...".into(),
+                crate::message::StatusLevel::Info,
+                Some(crate::message::Progress::Loading),
+            ));
+            match crate::ui::info::TelemetryRecord::maintenance(retain, archive, false).await {
+                Ok(r) => GlobIO::success(format!(
+                    "Maintenance done: {} archived, {} pruned, {} KB reclaimed",
+                    display_count(r.archived),
+                    display_count(r.pruned),
+                    display_count(r.bytes_reclaimed / 1024)
+                )),
+                Err(e) => GlobIO::error(format!("Maintenance failed: {}", e)),
+            }
+            Self::spawn_fetch_stats();
         });
     }
 
@@ -154,26 +489,204 @@ impl DatabaseComponent {
             let glob_send = GlobIO::send();
             let pool = crate::db::Database::pool();
 
-            let table_names: Vec<String> = sqlx::query_scalar(
+            let report = schema_report(pool).await;
+
+            let _ = glob_send.send(GlobalEvent::Data {
+                key: SQLITE_STATS_KEY,
+                data: DynamicPayload(Arc::new(report)),
+            });
+        });
+    }
+    fn spawn_fetch_rows(table: String, page: usize) {
+        tokio::spawn(async move {
+            let pool = crate::db::Database::pool();
+
+            // 表名只能来自 sqlite_master 的已知列表，防止注入
+            let known: Vec<String> = sqlx::query_scalar(
                 "SELECT name FROM sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%'"
             )
             .fetch_all(pool)
             .await
             .unwrap_or_default();
+            if !known.contains(&table) {
+                GlobIO::warn(format!("Unknown table: {}", table));
+                return;
+            }
+            let ident = format!("\"{}\"", table.replace('"', "\"\""));
 
-            let mut stats = Vec::new();
-            for name in table_names {
-                let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", name))
-                    .fetch_one(pool)
+            let sql = format!(
+                "SELECT * FROM {} LIMIT {} OFFSET {}",
+                ident,
+                ROWS_PAGE_SIZE,
+                page * ROWS_PAGE_SIZE
+            );
+            let rows: Vec<SqliteRow> = match sqlx::query(&sql).fetch_all(pool).await {
+                Ok(rows) => rows,
+                Err(e) => {
+                    GlobIO::error(format!("Query {} failed: {}", table, e));
+                    return;
+                }
+            };
+
+            // 空页无法从结果中拿到列信息，退回到 PRAGMA table_info
+            let columns: Vec<String> = match rows.first() {
+                Some(r) => r.columns().iter().map(|c| c.name().to_string()).collect(),
+                None => sqlx::query_scalar(&format!("SELECT name FROM pragma_table_info('{}')", table.replace('\'', "''")))
+                    .fetch_all(pool)
                     .await
-                    .unwrap_or(0);
-                stats.push(TableStat { name, count });
-            }
+                    .unwrap_or_default(),
+            };
+            let rows = rows
+                .iter()
+                .map(|r| (0..r.columns().len()).map(|i| cell_to_string(r, i)).collect())
+                .collect();
 
-            let _ = glob_send.send(GlobalEvent::Data {
-                key: SQLITE_STATS_KEY,
-                data: DynamicPayload(Arc::new(stats)),
+            let _ = GlobIO::send().send(GlobalEvent::Data {
+                key: SQLITE_ROWS_KEY,
+                data: DynamicPayload(Arc::new(TablePage { table, page, columns, rows })),
             });
         });
     }
-}
\ No newline at end of file
+}
+
+/// 列出所有用户表并逐表计数；空库返回空报告
+async fn schema_report(pool: &sqlx::SqlitePool) -> SchemaReport {
+    let table_names: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM sqlite_master WHERE type='table' AND name NOT LIKE 'sqlite_%'")
+            .fetch_all(pool)
+            .await
+            .unwrap_or_default();
+    count_tables(pool, table_names).await
+}
+
+/// 单表失败 (例如列出后被删除) 只记录错误，不中断整个报告
+async fn count_tables(pool: &sqlx::SqlitePool, names: Vec<String>) -> SchemaReport {
+    let mut report = SchemaReport::default();
+    for name in names {
+        let count = match sqlx::query_scalar::<_, i64>(&format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\"")))
+            .fetch_one(pool)
+            .await
+        {
+            Ok(count) => Some(count),
+            Err(e) => {
+                log::warn!("Row count failed for table {}: {}", name, e);
+                report.errors.push((name.clone(), e.to_string()));
+                None
+            }
+        };
+        report.tables.push(TableStat { name, count });
+    }
+    report
+}
+
+/// 按值的实际存储类型解码为可显示的字符串
+pub(crate) fn cell_to_string(row: &SqliteRow, idx: usize) -> String {
+    let raw = match row.try_get_raw(idx) {
+        Ok(raw) => raw,
+        Err(_) => return "?".into(),
+    };
+    if raw.is_null() {
+        return "NULL".into();
+    }
+    let value = match raw.type_info().name() {
+        "INTEGER" | "BOOLEAN" => row.try_get_unchecked::<i64, _>(idx).map(|v| v.to_string()),
+        "REAL" | "NUMERIC" => row.try_get_unchecked::<f64, _>(idx).map(|v| v.to_string()),
+        "BLOB" => row
+            .try_get_unchecked::<Vec<u8>, _>(idx)
+            .map(|v| format!("<{} bytes>", v.len())),
+        _ => row.try_get_unchecked::<String, _>(idx),
+    };
+    value.unwrap_or_else(|_| "?".into())
+}
+
+/// 超出宽度时截断并追加省略号
+fn truncate(s: &str, max: usize) -> String {
+    if s.chars().count() <= max {
+        s.to_string()
+    } else {
+        let mut out: String = s.chars().take(max.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(refresh_secs: u64) -> DatabaseComponent {
+        GlobIO::init_for_test();
+        let config = Config { db_refresh_secs: refresh_secs, ..Default::default() };
+        DatabaseComponent::with_config(Arc::new(tokio::sync::RwLock::new(config)))
+    }
+
+    #[tokio::test]
+    async fn footer_hint_shows_configured_interval() {
+        let mut comp = component(30);
+        assert_eq!(comp.schema_hint(), " Loading database schema... ");
+        comp.is_loading = false;
+        assert!(comp.schema_hint().contains("Auto-refresh every 30s"), "{}", comp.schema_hint());
+
+        // 低于下限的配置值按 DB_REFRESH_MIN_SECS 生效，提示同步显示
+        let mut comp = component(1);
+        comp.is_loading = false;
+        assert!(comp.schema_hint().contains(&format!("every {}s", DB_REFRESH_MIN_SECS)));
+    }
+
+    #[tokio::test]
+    async fn row_counts_are_right_aligned() {
+        Config::init_for_test();
+        crate::prelude::AtlasPath::init_for_test();
+        let mut comp = component(30);
+        comp.tables = [("users", 5), ("events", 12345), ("logs", 300)]
+            .into_iter()
+            .map(|(name, count)| TableStat { name: name.into(), count: Some(count) })
+            .collect();
+
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(60, 12)).unwrap();
+        terminal.draw(|f| comp.render_schema(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        let lines: Vec<String> = (0..buf.area.height)
+            .map(|y| (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+
+        // 各行计数的最后一个字符落在同一列
+        let ends: Vec<usize> = ["users", "events", "logs"]
+            .iter()
+            .map(|name| {
+                let line = lines.iter().find(|l| l.contains(name)).unwrap_or_else(|| panic!("{name} missing"));
+                line.trim_end_matches(['│', ' ']).chars().count()
+            })
+            .collect();
+        assert!(ends.iter().all(|&e| e == ends[0]), "{lines:#?}");
+        assert!(lines.iter().any(|l| l.trim_end_matches(['│', ' ']).ends_with("    5")), "{lines:#?}");
+    }
+
+    #[tokio::test]
+    async fn empty_and_partially_broken_schemas_still_report() {
+        // 独立的内存库，不受其它测试建表的影响
+        let pool = sqlx::SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let report = schema_report(&pool).await;
+        assert!(report.tables.is_empty() && report.errors.is_empty());
+
+        sqlx::query("CREATE TABLE samples (v INTEGER)").execute(&pool).await.unwrap();
+        sqlx::query("INSERT INTO samples VALUES (1), (2)").execute(&pool).await.unwrap();
+        sqlx::query("CREATE TABLE \"odd name\" (v INTEGER)").execute(&pool).await.unwrap();
+        let report = schema_report(&pool).await;
+        let counts: Vec<_> = report.tables.iter().map(|t| (t.name.as_str(), t.count)).collect();
+        assert_eq!(counts, [("samples", Some(2)), ("odd name", Some(0))]);
+
+        // 列出后被删除的表计数失败：记为 None 并列入 errors，其它表照常统计
+        let report = count_tables(&pool, vec!["samples".into(), "dropped".into()]).await;
+        let counts: Vec<_> = report.tables.iter().map(|t| (t.name.as_str(), t.count)).collect();
+        assert_eq!(counts, [("samples", Some(2)), ("dropped", None)]);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].0, "dropped");
+
+        // 页脚提示失败数量
+        let mut comp = component(30);
+        comp.is_loading = false;
+        comp.count_errors = report.errors;
+        assert!(comp.schema_hint().contains("1 table(s) failed to count"), "{}", comp.schema_hint());
+    }
+}
@@ -1,15 +1,15 @@
 use crate::{
-    config::{AppColor, Config, SharedConfig}, constans::{
-         HISTORY_CAP, INFO_UPDATE_INTERVAL_BASE, INFO_UPDATE_INTERVAL_SLOW_TIMES, INFO_UPDATE_INTERVAL_SLOWEST
-    }, message::{DynamicPayload, GlobalEvent}, prelude::{AtlasPath, GlobIO, GlobRecv, GlobSend}, ui::component::Component
+    config::{AppColor, Config, DiskSort, FreqUnit, HealthWeights, MemWidgetStyle, SharedConfig}, constans::{
+         HISTORY_CAP, INFO_UPDATE_INTERVAL_BASE, NET_CHANGE_POLL_SECS, PUBLIC_IP_BACKOFF_MAX_SECS, PUBLIC_IP_REFRESH_SECS, INFO_UPDATE_INTERVAL_SLOW_TIMES, INFO_UPDATE_INTERVAL_SLOWEST
+    }, message::{DynamicPayload, GlobalEvent}, prelude::{display_count, AtlasPath, GlobIO, GlobRecv, GlobSend}, ui::component::Component
 };
 use chrono::{DateTime, Utc};
 use crossterm::event::{KeyCode, KeyEvent};
 use directories::{BaseDirs, UserDirs};
 use ratatui::{prelude::*, symbols::block, widgets::*};
 use serde::{Deserialize, Serialize};
-use std::{collections::VecDeque, sync::Arc, time::Duration};
-use sysinfo::{Disks, System};
+use std::{collections::{HashMap, VecDeque}, sync::Arc, time::{Duration, Instant}};
+use sysinfo::{CpuRefreshKind, DiskRefreshKind, Disks, MemoryRefreshKind, RefreshKind, System};
 use tokio::sync::{broadcast, mpsc};
 // use crate::db::Mongo;
 use sqlx::{sqlite::SqliteRow, Row};
@@ -22,59 +22,413 @@ const COLL_NAME: &str = "telemetry_history"; // database collections
 const MEM_SWAP_LONG: &str = "mem_swap_long";
 const ANDROID_CPU_LONG: &str = "android_cpu_long";
 const ANDROID_BAT: &str = "android_bat";
+// 手动采集 ('r') 的电池读数：只更新当前值，不进入按长周期间隔排列的 bat_history
+const ANDROID_BAT_NOW: &str = "android_bat_now";
 pub type AndroidBatInfo = (u8, String, f64); // (电量百分比, 充放电状态String, 电池温度f32)
 const ANDROID_CPU: &str = "android_cpu";
-type CpuInfo = (Vec<f32>, f32, f32); // (各核心频率Vec<f32>, Zone0温度f32, Zone7温度f32)
+type CpuInfo = (Vec<f32>, f32, f32); // (各核心频率Vec<f32>, Zone0温度f32, CPU温度f32 (匹配分区的最大值))
 const MEM_SWAP: &str = "mem_swap";
+const CPU_USAGE: &str = "cpu_usage";
+type CpuUsage = f32; // 全局 CPU 使用率 0-100 (sysinfo，两次采样的差值)
 type MemSwapMB = (u64, u64);
+const SELF_USAGE: &str = "self_usage";
+/// Atlas 自身的资源占用 (每个基础周期采样，不随帧率)
+#[derive(Debug, Clone, Copy, Default)]
+struct SelfUsage {
+    cpu: f32,         // 进程 CPU%，多核时可超过 100
+    rss_mb: u64,
+    fds: Option<usize>, // 打开的文件描述符数 (仅 Linux/Android，读 /proc/self/fd)
+}
 const DISK_IP: &str = "disk_ip";
 // 修改类型定义，将 IP 分为 (IPv4列表, IPv6列表)
 type IPData = (Vec<String>, Vec<String>);
 type DiskIP = (Vec<DiskInf>, IPData);
-type DiskInf = (String, u64, u64, String);
+type DiskInf = (String, u64, u64, String, String); // (名称, 总容量, 可用, 挂载点, 文件系统)
+
+/// Config.disk_columns 可用的列名
+const DISK_COLUMNS: &[&str] = &["bar", "pct", "total", "used", "avail", "mount", "fs", "name"];
+const PUBLIC_IP: &str = "public_ip";
+const IP_HISTORY: &str = "ip_history";
+/// IP 窗格历史视图显示的最近变化条数
+const IP_HISTORY_SHOWN: i64 = 50;
+#[cfg(feature = "reverse-dns")]
+const PUBLIC_PTR: &str = "public_ptr";
+const THERMAL_ROOT: &str = "/sys/class/thermal";
+const CPU_SYSFS_ROOT: &str = "/sys/devices/system/cpu";
+const DISK_RELIST_EVERY: u64 = 4;
+/// 读取电池失败时的状态文字
+const BATTERY_UNKNOWN: &str = "Unknown";
+/// 短周期历史与长周期历史 (含电池) 的采样间隔，单位秒
+const SHORT_INTERVAL: u64 = INFO_UPDATE_INTERVAL_BASE;
+const LONG_INTERVAL: u64 = INFO_UPDATE_INTERVAL_BASE * INFO_UPDATE_INTERVAL_SLOWEST;
+/// 每个挂载点保留的使用率样本数 (中周期采集)，以及迷你趋势图显示的点数
+const DISK_HISTORY_CAP: usize = 64;
+const DISK_TREND_POINTS: usize = 16;
+/// 估算剩余时间使用的最近电池样本数
+const BAT_ETA_SAMPLES: usize = 20;
+
+/// 记录一次磁盘采样：按挂载点追加使用率，超出 DISK_HISTORY_CAP 丢弃最旧的，移除已消失的挂载点
+fn record_disk_history(history: &mut HashMap<String, VecDeque<u8>>, disks: &[DiskInf]) {
+    history.retain(|mount, _| disks.iter().any(|d| &d.3 == mount));
+    for d in disks.iter().filter(|d| d.1 > 0) {
+        let pct = (d.1.saturating_sub(d.2) * 100 / d.1) as u8;
+        let h = history.entry(d.3.clone()).or_default();
+        h.push_back(pct);
+        while h.len() > DISK_HISTORY_CAP {
+            h.pop_front();
+        }
+    }
+}
+
+/// 启动时获得焦点的窗格 (Config.info_default_focus)，配置被占用时为挂载点
+fn initial_focus(config: &SharedConfig) -> Option<usize> {
+    Some(config.try_read().map(|c| c.info_default_focus.index()).unwrap_or(0))
+}
+
+/// 单行文字迷你趋势图 (▁..█)，取最近 points 个百分比样本
+fn trend_line(values: &VecDeque<u8>, points: usize) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    values
+        .iter()
+        .skip(values.len().saturating_sub(points))
+        .map(|v| BARS[(*v as usize * (BARS.len() - 1)) / 100])
+        .collect()
+}
+
+/// 按 cpuinfo_max_freq 把核心分成簇 (big.LITTLE)，如 "4x little + 4x big"；
+/// 读不到 sysfs 或所有核心同频时返回 None
+fn cpu_clusters(root: &std::path::Path) -> Option<String> {
+    let mut clusters: Vec<(u64, usize)> = Vec::new(); // (最大频率 kHz, 核心数)
+    for i in 0.. {
+        let dir = root.join(format!("cpu{}", i));
+        if !dir.exists() {
+            break;
+        }
+        let Some(max) = std::fs::read_to_string(dir.join("cpufreq/cpuinfo_max_freq"))
+            .ok()
+            .and_then(|s| s.trim().parse::<u64>().ok())
+        else {
+            continue;
+        };
+        match clusters.iter_mut().find(|(f, _)| *f == max) {
+            Some((_, n)) => *n += 1,
+            None => clusters.push((max, 1)),
+        }
+    }
+    if clusters.len() < 2 {
+        return None;
+    }
+    clusters.sort_unstable();
+    let names: &[&str] = match clusters.len() {
+        2 => &["little", "big"],
+        3 => &["little", "mid", "big"],
+        _ => &[],
+    };
+    let parts: Vec<String> = clusters
+        .iter()
+        .enumerate()
+        .map(|(i, (f, n))| match names.get(i) {
+            Some(name) => format!("{}x {}", n, name),
+            None => format!("{}x {:.1}GHz", n, *f as f32 / 1_000_000.0),
+        })
+        .collect();
+    Some(parts.join(" + "))
+}
+
+/// "All Cores" 行：逐核显示放得下就逐核显示，否则退化为 min / avg / max
+fn format_core_freqs(freqs: &[f32], unit: FreqUnit, width: usize) -> String {
+    let per_core = freqs.iter().map(|f| unit.value(*f)).collect::<Vec<_>>().join("|");
+    if per_core.chars().count() <= width || freqs.is_empty() {
+        return per_core;
+    }
+    let min = freqs.iter().cloned().fold(f32::MAX, f32::min);
+    let max = freqs.iter().cloned().fold(0.0, f32::max);
+    let avg = freqs.iter().sum::<f32>() / freqs.len() as f32;
+    format!(
+        "{} cores  min {} / avg {} / max {} {}",
+        freqs.len(),
+        unit.value(min),
+        unit.value(avg),
+        unit.value(max),
+        unit.suffix()
+    )
+}
+
+/// 根据最近 BAT_ETA_SAMPLES 个电量样本做最小二乘线性拟合，估算耗尽所需秒数。
+/// 样本间隔为 interval_secs；占位样本 (状态为空) 不参与，电量不降 (平坦/充电/噪声) 时返回 None
+fn battery_eta_secs(history: &VecDeque<AndroidBatInfo>, interval_secs: f64) -> Option<f64> {
+    let mut samples: Vec<f64> = history
+        .iter()
+        .rev()
+        .take_while(|(_, status, _)| !status.is_empty())
+        .take(BAT_ETA_SAMPLES)
+        .map(|(p, _, _)| *p as f64)
+        .collect();
+    samples.reverse(); // 旧 -> 新
+    if samples.len() < 3 {
+        return None;
+    }
+
+    let n = samples.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = samples.iter().sum::<f64>() / n;
+    let (mut cov, mut var) = (0.0, 0.0);
+    for (i, y) in samples.iter().enumerate() {
+        let dx = i as f64 - mean_x;
+        cov += dx * (y - mean_y);
+        var += dx * dx;
+    }
+    let slope = cov / var; // 每个样本的电量变化 (%)
+
+    // 每小时下降不足 0.5% 视为平坦，不给出估计
+    let per_hour = slope * 3600.0 / interval_secs;
+    if per_hour > -0.5 {
+        return None;
+    }
+    let current = *samples.last()?;
+    Some(current / -slope * interval_secs)
+}
+
+/// 例如 "2h15m" / "45m"
+fn format_eta(secs: f64) -> String {
+    let mins = (secs / 60.0).round() as u64;
+    match mins / 60 {
+        0 => format!("{}m", mins),
+        h => format!("{}h{:02}m", h, mins % 60),
+    }
+}
+
+/// 公网 IP 的获取状态
+#[derive(Debug, Clone, Default)]
+pub enum PublicIp {
+    #[default]
+    Pending,
+    Retrying(u32), // 第 N 次重试
+    Known(String),
+    /// 超过 Config.public_ip_timeout_secs 仍未获取到，后台继续重试，'p' 立即重试
+    Unavailable,
+}
+
+/// 'p' 唤醒公网 IP 任务立即重新获取 (跳过当前的退避等待)
+static PUBLIC_IP_RETRY: tokio::sync::Notify = tokio::sync::Notify::const_new();
+/// 'r' 让监控任务立即做一次完整采集 (内存/CPU/电池/磁盘/IP)，不等下一个周期
+static COLLECT_NOW: tokio::sync::Notify = tokio::sync::Notify::const_new();
+/// 会话内的 min / max / 平均值，O(1) 增量更新，与历史队列的淘汰无关
+#[derive(Debug, Clone, Copy, Default)]
+struct RunningStats {
+    min: f64,
+    max: f64,
+    mean: f64,
+    count: u64,
+}
+
+impl RunningStats {
+    fn push(&mut self, v: f64) {
+        if self.count == 0 {
+            (self.min, self.max) = (v, v);
+        } else {
+            self.min = self.min.min(v);
+            self.max = self.max.max(v);
+        }
+        self.count += 1;
+        self.mean += (v - self.mean) / self.count as f64;
+    }
+
+    /// 例如 " min 38 / avg 47 / max 62 "，尚无样本时为空
+    fn label(&self) -> String {
+        if self.count == 0 {
+            return String::new();
+        }
+        format!(" min {:.0} / avg {:.0} / max {:.0} ", self.min, self.mean, self.max)
+    }
+}
+
 pub struct InfoComponent {
     glob_recv: GlobRecv,
 
     // 数据存储
     mount_points: Vec<DiskInf>,
-    dir_list: Vec<String>,
+    // 各挂载点的使用率历史 (%)，挂载点消失时移除
+    disk_history: HashMap<String, VecDeque<u8>>,
+    dir_list: Vec<(String, Option<std::path::PathBuf>)>, // 标题行的路径为 None
+    dir_state: ListState,
     ip_list: (Vec<String>, Vec<String>),
+    public_ip: PublicIp,
+    public_ip_since: Instant, // 本轮获取开始的时间，用于超时判定
+    show_all_interfaces: bool, // 'f' 切换：忽略 hide_interfaces 显示全部网卡
+    // 'H' 切换：IP 窗格显示 ip_history 中最近的变化
+    show_ip_history: bool,
+    ip_history: Vec<IpChange>,
+    host_name: String,
+    // 公网 IP 的反向解析缓存 (ip, ptr)，只有 IP 变化时才重新查询
+    public_ptr: Option<(String, Option<String>)>,
 
     // UI 状态
     focus_index: Option<usize>, // 0: Mounts, 1: Dirs, 2: IPs
-    scroll_offsets: [u16; 3],
+    // 挂载点与 IP 窗格的滚动位置 (目录窗格是可选列表，由 dir_state 管理)
+    mount_scroll: u16,
+    ip_scroll: u16,
 
     total_mem_swap_mb: (u64, u64),
     mem_swap_history: VecDeque<(u64, u64)>,
     mem_swap_long_history: VecDeque<(u64, u64)>,
     // Android 专用数据存储
     bat_history: VecDeque<AndroidBatInfo>,
+    bat_now: Option<AndroidBatInfo>, // 最近一次读数 (含手动采集)，显示当前值时优先使用
     cpu_info_history: VecDeque<CpuInfo>,
     cpu_info_long_history: VecDeque<CpuInfo>,
 
     system_info: String, // 例如: "Android 14"
+    cpu_topology: String, // 例如: "8 threads · 4x little + 4x big"，启动时计算一次
+    cpu_usage: CpuUsage,
+    self_usage: Option<SelfUsage>,
+
+    // 按 'c' 捕获的参考快照，用于显示与当前值的差值
+    reference: Option<TelemetryRecord>,
+    // 'v' 切换：文字摘要模式 (慢速 SSH 下避免重绘大量 sparkline)
+    summary_mode: bool,
+    // 'r' 手动采集后、下一次磁盘/IP 数据到达前显示 refreshing…
+    refreshing: bool,
+    // 会话统计 ('R' 重置)：CPU 温度 °C、内存 MB、电量 %
+    stats_cpu_temp: RunningStats,
+    stats_mem: RunningStats,
+    stats_bat: RunningStats,
 }
 
 impl InfoComponent // rende part uis
 {
+    /// 当前电池读数：手动采集的值优先，否则为最近一次长周期样本
+    fn current_battery(&self) -> Option<&AndroidBatInfo> {
+        self.bat_now.as_ref().or(self.bat_history.back())
+    }
+
+    /// 当前最新数据组成的记录，作为参考快照
+    fn latest_record(&self) -> TelemetryRecord {
+        TelemetryRecord {
+            timestamp: Utc::now().to_rfc3339(),
+            cpu_data: self.cpu_info_history.back().cloned().unwrap_or_default(),
+            mem_swap: self.mem_swap_history.back().cloned().unwrap_or_default(),
+            battery_data: self.current_battery().cloned().unwrap_or_default(),
+        }
+    }
+
+    /// 健康度评分所需的最新指标
+    fn latest_metrics(&self) -> Metrics {
+        let (mem_total, _) = self.total_mem_swap_mb;
+        let (mem_used, _) = self.mem_swap_history.back().copied().unwrap_or_default();
+        Metrics {
+            cpu_temp: self.cpu_info_history.back().map(|c| c.2).unwrap_or(0.0),
+            mem_ratio: if mem_total > 0 { mem_used as f64 / mem_total as f64 } else { 0.0 },
+            disk_ratio: self
+                .mount_points
+                .iter()
+                .filter(|d| d.1 > 0)
+                .map(|d| d.1.saturating_sub(d.2) as f64 / d.1 as f64)
+                .fold(0.0, f64::max),
+            battery_pct: if cfg!(target_os = "android") {
+                self.current_battery().map(|b| b.0)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// 摘要模式的文字，每个指标一行
+    fn summary_lines(&self) -> Vec<String> {
+        let gib = |mb: u64| mb as f64 / 1024.0;
+        let pct = |used: u64, total: u64| (used * 100).checked_div(total).unwrap_or(0);
+
+        let (freqs, _, cpu_temp) = self.cpu_info_history.back().cloned().unwrap_or_default();
+        let max_freq = freqs.iter().cloned().fold(0.0, f32::max);
+        let unit = Config::get().try_read().map(|c| c.freq_unit).unwrap_or_default();
+        let (mem_total, swap_total) = self.total_mem_swap_mb;
+        let (mem_used, swap_used) = self.mem_swap_history.back().copied().unwrap_or_default();
+
+        let mut lines = vec![
+            format!("CPU:  {:.0}% {}{} {:.0}°C", self.cpu_usage, unit.value(max_freq), unit.suffix(), cpu_temp),
+            format!(
+                "RAM:  {:.1}/{:.1} GiB ({}%)",
+                gib(mem_used), gib(mem_total), pct(mem_used, mem_total)
+            ),
+            format!("SWAP: {:.1}/{:.1} GiB", gib(swap_used), gib(swap_total)),
+        ];
+        if let Some((bat_pct, status, temp)) = self.current_battery() {
+            lines.push(format!("BAT:  {}% {} {:.0}°C", bat_pct, status.to_lowercase(), temp));
+        }
+        lines.extend(self.mount_points.iter().filter(|d| d.1 > 0).map(|d| {
+            format!("DISK {}: {}%", d.3, pct(d.1.saturating_sub(d.2), d.1))
+        }));
+        lines
+    }
+
+    /// 与参考快照的差值文本，例如 " (+150)"；没有参考快照时为空
+    fn delta(&self, diff: impl Fn(&TelemetryRecord) -> f64, precision: usize) -> String {
+        match &self.reference {
+            Some(r) => format!(" ({:+.*})", precision, diff(r)),
+            None => String::new(),
+        }
+    }
+
     fn render_ip_addresses(&self, f: &mut Frame, area: Rect) {
-        let (v4, v6) = &self.ip_list;
+        let (hidden, v4_color, v6_color, abbreviate, dedup, group) = Config::get()
+            .try_read()
+            .map(|c| {
+                let hidden = if self.show_all_interfaces { vec![] } else { c.hide_interfaces.clone() };
+                (
+                    hidden,
+                    c.ipv4_color.to_ratatui_color(),
+                    c.ipv6_color.to_ratatui_color(),
+                    c.ipv6_abbreviate,
+                    c.ip_dedup,
+                    c.ip_group_by_interface,
+                )
+            })
+            .unwrap_or_else(|_| (vec![], Color::Cyan, Color::LightGreen, true, true, false));
+        let (v4, mut v6) = filter_interfaces(&self.ip_list, &hidden);
+        if abbreviate {
+            for entry in v6.iter_mut() {
+                if let Some((name, addr)) = entry.split_once(": ") {
+                    *entry = format!("{}: {}", name, abbreviate_ipv6(addr));
+                }
+            }
+        }
+        let (v4, v6) = (arrange_ips(v4, dedup, group), arrange_ips(v6, dedup, group));
+        let filter_hint = if self.show_all_interfaces { "all" } else { "filtered" };
+        let public = match &self.public_ip {
+            PublicIp::Pending => "...".to_string(),
+            PublicIp::Retrying(n) => format!("retrying (attempt {})", n),
+            PublicIp::Unavailable => "Unavailable (timeout) 'p' retry".to_string(),
+            PublicIp::Known(ip) => match &self.public_ptr {
+                Some((addr, Some(ptr))) if addr == ip => format!("{} (ptr: {})", ip, ptr),
+                _ => ip.clone(),
+            },
+        };
 
         // 创建包裹容器
         let block = Block::default()
             .borders(Borders::ALL)
-            .title(" 🌐 IP Addresses (Left: v4 | Right: v6) ")
-            .border_style(if self.focus_index == Some(2) {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::Gray)
-            });
+            .title(crate::ui::style::focus_title(
+                &format!(
+                    " 🌐 {} | IP (Left: v4 | Right: v6) | Public: {} | 'f' {} | 'H' history ",
+                    self.host_name, public, filter_hint
+                ),
+                self.focus_index == Some(2),
+            ))
+            .border_style(crate::ui::style::focus_border(self.focus_index == Some(2)));
 
         let inner_area = block.inner(area);
         f.render_widget(block, area);
 
+        if self.show_ip_history {
+            let lines: Vec<Line> = if self.ip_history.is_empty() {
+                vec![Line::from(" No IP changes recorded yet ").fg(Color::DarkGray)]
+            } else {
+                self.ip_history.iter().map(IpChange::line).collect()
+            };
+            f.render_widget(Paragraph::new(lines).scroll((self.ip_scroll, 0)), inner_area);
+            return;
+        }
+
         // 在容器内部进行横向切分
         let chunks = Layout::horizontal([
             Constraint::Percentage(45), // v4 区域
@@ -83,11 +437,12 @@ impl InfoComponent // rende part uis
         ])
         .split(inner_area);
 
-        // 渲染 IPv4
+        // 渲染 IPv4 (超出列宽、需要换行的条目变暗，便于区分换行与新条目)
         f.render_widget(
-            Paragraph::new(v4.join("\n"))
-                .style(Style::default().fg(Color::Cyan))
-                .scroll((self.scroll_offsets[2], 0)),
+            Paragraph::new(ip_lines(&v4, chunks[0].width))
+                .style(Style::default().fg(v4_color))
+                .wrap(Wrap { trim: false })
+                .scroll((self.ip_scroll, 0)),
             chunks[0],
         );
 
@@ -100,9 +455,10 @@ impl InfoComponent // rende part uis
 
         // 渲染 IPv6
         f.render_widget(
-            Paragraph::new(v6.join("\n"))
-                .style(Style::default().fg(Color::LightGreen)) // v6 通常不常用，颜色调淡
-                .scroll((self.scroll_offsets[2], 0)),
+            Paragraph::new(ip_lines(&v6, chunks[2].width))
+                .style(Style::default().fg(v6_color))
+                .wrap(Wrap { trim: false }) // 长 v6 地址换行而不是被截断
+                .scroll((self.ip_scroll, 0)),
             chunks[2],
         );
     }
@@ -114,6 +470,10 @@ impl InfoComponent // rende part uis
         let (mem_total, swap_total) = self.total_mem_swap_mb;
         // 获取最新数值用于标题展示
         let (mem_last, swap_last) = self.mem_swap_history.back().unwrap_or(&(0, 0));
+        let style = Config::get()
+            .try_read()
+            .map(|cfg| cfg.mem_widget_style)
+            .unwrap_or_default();
 
         for (i, is_mem) in [true, false].iter().enumerate() {
             let inner_chunks =
@@ -125,34 +485,68 @@ impl InfoComponent // rende part uis
 
             let title = if *is_mem { " 📟 RAM" } else { " 🔁 SWAP" };
             let last_val = if *is_mem { mem_last } else { swap_last };
+            let delta = self.delta(|r| {
+                let (m, s) = r.mem_swap;
+                *last_val as f64 - if *is_mem { m } else { s } as f64
+            }, 0);
             let total = if *is_mem { mem_total } else { swap_total };
             let color = if *is_mem { Color::Blue } else { Color::Magenta };
+            // 当前值按总量的宽度右对齐，数值位数变化时标题不跳动
+            let total_str = display_count(total);
+            // Gauge 模式只显示当前值，没有时间跨度
+            let short_span = if style == MemWidgetStyle::Gauge {
+                String::new()
+            } else {
+                format!(" · {}", span_label(self.mem_swap_history.len().min(width), SHORT_INTERVAL))
+            };
+            let long_span = span_label(self.mem_swap_long_history.len().min(width), LONG_INTERVAL);
+            let top_title = format!(
+                " {}: {:>w$}/{}MB{}{}  ",
+                title,
+                display_count(*last_val),
+                total_str,
+                delta,
+                short_span,
+                w = total_str.len()
+            );
+            let top_block = Block::default()
+                .title(top_title)
+                .borders(Borders::LEFT | Borders::RIGHT | Borders::TOP);
 
-            // 1. 渲染短周期 (上) - 采样最新的数据
-            let data_s: Vec<u64> = self
-                .mem_swap_history
-                .iter()
-                .map(|(m, s)| {
-                    let val = if *is_mem { *m } else { *s };
-                    if total > 0 { val * 100 / total } else { 0 }
-                })
-                .rev()
-                .take(width)
-                .rev() // 只取最新可见部分
-                .collect();
+            // 1. 渲染短周期 (上)：Gauge 模式只显示当前值，否则为最新数据的 sparkline
+            if style == MemWidgetStyle::Gauge {
+                let ratio = if total > 0 { (*last_val as f64 / total as f64).min(1.0) } else { 0.0 };
+                f.render_widget(
+                    Gauge::default()
+                        .block(top_block)
+                        .gauge_style(Style::default().fg(usage_color(ratio, color)))
+                        .ratio(ratio)
+                        .label(format!("{:.1}%", ratio * 100.0)),
+                    inner_chunks[0],
+                );
+            } else {
+                let data_s: Vec<u64> = self
+                    .mem_swap_history
+                    .iter()
+                    .map(|(m, s)| {
+                        let val = if *is_mem { *m } else { *s };
+                        if total > 0 { val * 100 / total } else { 0 }
+                    })
+                    .rev()
+                    .take(width)
+                    .rev() // 只取最新可见部分
+                    .collect();
 
-            f.render_widget(
-                Sparkline::default()
-                    .data(&data_s)
-                    .max(100)
-                    .style(Style::default().fg(color))
-                    .block(
-                        Block::default()
-                            .title(format!(" {}: {}/{}MB  ", title, last_val, total))
-                            .borders(Borders::LEFT | Borders::RIGHT | Borders::TOP),
-                    ),
-                inner_chunks[0],
-            );
+                f.render_widget(
+                    Sparkline::default()
+                        .bar_set(crate::ui::style::sparkline_bars())
+                        .data(&data_s)
+                        .max(100)
+                        .style(Style::default().fg(color))
+                        .block(top_block),
+                    inner_chunks[0],
+                );
+            }
 
             // 2. 渲染长周期 (下) - 采样最新的数据
             let data_l: Vec<u64> = self
@@ -169,12 +563,14 @@ impl InfoComponent // rende part uis
 
             f.render_widget(
                 Sparkline::default()
+                    .bar_set(crate::ui::style::sparkline_bars())
                     .data(&data_l)
                     .max(100)
                     .style(Style::default().fg(color).add_modifier(Modifier::DIM)) // 调暗颜色区分
                     .block(
                         Block::default()
-                            .title(format!(" {} (Long Trend) ", title))
+                            .title(format!(" {} (Long Trend · {}) ", title, long_span))
+                            .title_bottom(if *is_mem { self.stats_mem.label() } else { String::new() })
                             .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM),
                     ),
                 inner_chunks[1],
@@ -196,6 +592,7 @@ impl InfoComponent // rende part uis
             Layout::vertical([Constraint::Percentage(70), Constraint::Percentage(30)])
                 .split(chunks[0]);
         let max_cur_freq = freqs.iter().cloned().fold(0.0, f32::max);
+        let unit = Config::get().try_read().map(|c| c.freq_unit).unwrap_or_default();
 
         let freq_data: Vec<u64> = self
             .cpu_info_history
@@ -211,37 +608,42 @@ impl InfoComponent // rende part uis
 
         f.render_widget(
             Sparkline::default()
+                .bar_set(crate::ui::style::sparkline_bars())
                 .data(&freq_data)
                 .max(100)
                 .style(Style::default().fg(Color::Yellow))
                 .block(
                     Block::default()
-                        .title(format!(" ⚡ CPU Freq: {:.1}GHz (Max) ", max_cur_freq))
+                        .title(format!(
+                            " ⚡ CPU {:.0}% · Freq: {}{} (Max) · {} ",
+                            self.cpu_usage,
+                            unit.value(max_cur_freq),
+                            unit.suffix(),
+                            span_label(freq_data.len(), SHORT_INTERVAL)
+                        ))
                         .borders(Borders::LEFT | Borders::TOP | Borders::RIGHT),
                 ),
             left_chunks[0],
         );
 
-        // 底部文字显示所有核心频率
-        let freqs_text = freqs
-            .iter()
-            .map(|f| format!("{:.1}", f))
-            .collect::<Vec<_>>()
-            .join("|");
+        // 底部文字显示所有核心频率 (宽度不够时汇总)
+        let freqs_text = format_core_freqs(freqs, unit, left_chunks[1].width.saturating_sub(2) as usize);
         f.render_widget(
             Paragraph::new(freqs_text).block(
                 Block::default()
-                    .title(" All Cores ")
+                    .title(format!(" All Cores ({}) ", unit.suffix()))
+                    .title_bottom(format!(" {} ", self.cpu_topology))
                     .borders(Borders::LEFT | Borders::BOTTOM | Borders::RIGHT),
             ),
             left_chunks[1],
         );
 
-        // --- 右侧：温度采集 (映射 10°C-90°C -> 0-100) ---
+        // --- 右侧：温度采集 (按 Config.cpu_temp_range 映射到 0-100，默认 10°C-90°C) ---
         let right_chunks =
             Layout::vertical([Constraint::Percentage(70), Constraint::Percentage(30)])
                 .split(chunks[1]);
-        let map_temp = |t: f32| (((t - 10.0) / (90.0 - 10.0)) * 100.0).clamp(0.0, 100.0) as u64;
+        let range = Config::get().try_read().map(|c| c.cpu_temp_range).unwrap_or((10.0, 90.0));
+        let map_temp = |t: f32| temp_percent(t as f64, range);
 
         let temp_s: Vec<u64> = self
             .cpu_info_history
@@ -262,12 +664,18 @@ impl InfoComponent // rende part uis
 
         f.render_widget(
             Sparkline::default()
+                .bar_set(crate::ui::style::sparkline_bars())
                 .data(&temp_s)
                 .max(100)
                 .style(Style::default().fg(Color::Red))
                 .block(
                     Block::default()
-                        .title(format!(" 🌡️ Temp: {:.1}°C   ", z7))
+                        .title(format!(
+                            " 🌡️ Temp: {:.1}°C{} · {}   ",
+                            z7,
+                            self.delta(|r| (z7 - r.cpu_data.2) as f64, 1),
+                            span_label(temp_s.len(), SHORT_INTERVAL)
+                        ))
                         .borders(Borders::LEFT | Borders::TOP | Borders::RIGHT),
                 ),
             right_chunks[0],
@@ -275,12 +683,19 @@ impl InfoComponent // rende part uis
 
         f.render_widget(
             Sparkline::default()
+                .bar_set(crate::ui::style::sparkline_bars())
                 .data(&temp_l)
                 .max(100)
                 .style(Style::default().fg(Color::Red).add_modifier(Modifier::DIM))
                 .block(
                     Block::default()
-                        .title(" 🌡️  Temp (Long Trend) ")
+                        .title(format!(
+                            " 🌡️  Temp (Long Trend, {:.0}-{:.0}°C · {}) ",
+                            range.0,
+                            range.1,
+                            span_label(temp_l.len(), LONG_INTERVAL)
+                        ))
+                        .title_bottom(self.stats_cpu_temp.label())
                         .borders(Borders::LEFT | Borders::BOTTOM | Borders::RIGHT),
                 ),
             right_chunks[1],
@@ -293,7 +708,17 @@ impl InfoComponent // rende part uis
 
         let width = chunks[0].width.saturating_sub(2) as usize;
         let default_bat = (0u8, String::from("N/A"), 0.0f64);
-        let (pct, _status, temp) = self.bat_history.back().unwrap_or(&default_bat);
+        let (pct, status, temp) = self.current_battery().unwrap_or(&default_bat);
+        let charging = status.to_ascii_lowercase().starts_with("charging");
+        let state = if charging {
+            "⚡ charging".to_string()
+        } else {
+            // 电池样本按长周期采集
+            match battery_eta_secs(&self.bat_history, LONG_INTERVAL as f64) {
+                Some(secs) => format!("~{} to empty", format_eta(secs)),
+                None => String::new(),
+            }
+        };
 
         // 左侧：剩余电量历史 (基于已存储的长周期 bat_history)
         let bat_data: Vec<u64> = self
@@ -307,22 +732,31 @@ impl InfoComponent // rende part uis
 
         f.render_widget(
             Sparkline::default()
+                .bar_set(crate::ui::style::sparkline_bars())
                 .data(&bat_data)
                 .max(100)
                 .style(Style::default().fg(Color::Green))
                 .block(
                     Block::default()
-                        .title(format!(" 🔋 Battery: {}% ", pct,))
+                        .title(format!(
+                            " 🔋 Battery: {}%{} {} · {} ",
+                            pct,
+                            self.delta(|r| *pct as f64 - r.battery_data.0 as f64, 0),
+                            state,
+                            span_label(bat_data.len(), LONG_INTERVAL)
+                        ))
+                        .title_bottom(self.stats_bat.label())
                         .borders(Borders::ALL),
                 ),
             chunks[0],
         );
 
-        // 右侧：电池温度历史 (映射 20°C-50°C 常用区间)
+        // 右侧：电池温度历史 (按 Config.batt_temp_range 映射，默认 20°C-50°C 常用区间)
+        let range = Config::get().try_read().map(|c| c.batt_temp_range).unwrap_or((20.0, 50.0));
         let bat_temp_data: Vec<u64> = self
             .bat_history
             .iter()
-            .map(|(_, _, t)| ((*t - 20.0).max(0.0) * (100.0 / 30.0)) as u64)
+            .map(|(_, _, t)| temp_percent(*t, range))
             .rev()
             .take(width)
             .rev()
@@ -330,12 +764,18 @@ impl InfoComponent // rende part uis
 
         f.render_widget(
             Sparkline::default()
+                .bar_set(crate::ui::style::sparkline_bars())
                 .data(&bat_temp_data)
                 .max(100)
                 .style(Style::default().fg(Color::Yellow))
                 .block(
                     Block::default()
-                        .title(format!("🌡️ Bat Temp: {:.1}°C ", temp))
+                        .title(format!(
+                            "🌡️ Bat Temp: {:.1}°C{} · {} ",
+                            temp,
+                            self.delta(|r| temp - r.battery_data.2, 1),
+                            span_label(bat_temp_data.len(), LONG_INTERVAL)
+                        ))
                         .borders(Borders::ALL),
                 ),
             chunks[1],
@@ -344,76 +784,115 @@ impl InfoComponent // rende part uis
 
 
 
+    /// 启动时检查一次 disk_columns，未知列名只提示一次；
+    /// 由 App 在底部通知组件订阅之后调用，否则广播的提示会丢失
+    pub fn warn_unknown_disk_columns() {
+        let columns = Config::get()
+            .try_read()
+            .map(|c| c.disk_columns.clone())
+            .unwrap_or_default();
+        let unknown = unknown_disk_columns(&columns);
+        if !unknown.is_empty() {
+            GlobIO::warn(format!("Unknown disk_columns ignored: {}", unknown.join(", ")));
+        }
+    }
+
+    /// 目录列表：长路径 (如 Termux 的 /data/data/com.termux/...) 按宽度折成多行，
+    /// 只有路径行可选，Enter 复制到剪贴板
+    fn render_dir_list(&mut self, f: &mut Frame, area: Rect) {
+        let focused = self.focus_index == Some(1);
+        if self.dir_state.selected().is_none() {
+            self.dir_state.select(next_dir_entry(&self.dir_list, None, true));
+        }
+        let width = area.width.saturating_sub(2 + 3).max(1) as usize; // 边框与选择符号
+        let items: Vec<ListItem> = self
+            .dir_list
+            .iter()
+            .map(|(text, path)| {
+                let chars: Vec<char> = text.chars().collect();
+                let lines: Vec<Line> = chars
+                    .chunks(width)
+                    .map(|c| Line::from(c.iter().collect::<String>()))
+                    .collect();
+                let item = ListItem::new(if lines.is_empty() { vec![Line::default()] } else { lines });
+                if path.is_none() { item.style(Style::default().fg(Color::DarkGray)) } else { item }
+            })
+            .collect();
+
+        let symbol = crate::ui::style::selection_symbol();
+        let mut list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(crate::ui::style::focus_title(" 📂 Directories ", focused))
+                .title_bottom(if focused { " Enter copy " } else { "" })
+                .border_style(crate::ui::style::focus_border(focused)),
+        );
+        // 未聚焦时不显示选中行
+        if focused {
+            list = list
+                .highlight_style(crate::ui::style::selection_style())
+                .highlight_symbol(symbol.as_str());
+        }
+        f.render_stateful_widget(list, area, &mut self.dir_state);
+    }
+
+    /// 复制选中的目录路径 (OSC 52)
+    fn copy_selected_dir(&self) {
+        let Some(path) = selected_dir(&self.dir_list, self.dir_state.selected()) else {
+            return;
+        };
+        let path = path.display().to_string();
+        match crate::clipboard::copy(&path) {
+            Ok(()) => GlobIO::success(format!("Copied {} to clipboard", path)),
+            Err(e) => GlobIO::error(format!("Failed to copy path: {}", e)),
+        }
+    }
+
     fn render_disk_list(&self, f: &mut Frame, area: Rect) {
         // --- 1. 使用缓存数据，不再调用 Disks::new() ---
         let mut sorted_disks = self.mount_points.clone();
 
-        // --- 2. 保持原有的跨平台排序逻辑 ---
-        #[cfg(not(target_os = "windows"))]
-        sorted_disks.sort_by(|a, b| b.1.cmp(&a.1)); // 按总空间排序 (DiskInf.1 是 total_space)
-
-        #[cfg(target_os = "windows")]
-        sorted_disks.sort_by(|a, b| a.3.cmp(&b.3)); // 按挂载点路径排序 (DiskInf.3 是 mount_point)
+        // --- 2. 排序：Auto 保持原有的跨平台逻辑 ---
+        let sort = Config::get().try_read().map(|c| c.disk_sort).unwrap_or_default();
+        let sort = match sort {
+            DiskSort::Auto if cfg!(target_os = "windows") => DiskSort::Mount,
+            DiskSort::Auto => DiskSort::Size,
+            s => s,
+        };
+        let used_ratio = |d: &DiskInf| (d.1 - d.2.min(d.1)) as f64 / d.1.max(1) as f64;
+        match sort {
+            DiskSort::Mount => sorted_disks.sort_by(|a, b| a.3.cmp(&b.3)), // 按挂载点路径排序 (DiskInf.3 是 mount_point)
+            DiskSort::Usage => sorted_disks.sort_by(|a, b| used_ratio(b).total_cmp(&used_ratio(a))),
+            _ => sorted_disks.sort_by(|a, b| b.1.cmp(&a.1)), // 按总空间排序 (DiskInf.1 是 total_space)
+        }
 
         // --- 3. 计算分页与显示范围 ---
-        let offset = self.scroll_offsets[0] as usize;
+        let offset = self.mount_scroll as usize;
         let visible_height = area.height.saturating_sub(2) as usize;
 
         let displayed_disks = sorted_disks.iter().skip(offset).take(visible_height);
 
-        // --- 4. 构造列表项 (逻辑保持一致，仅数据源切换为 DiskInf 元组) ---
+        // --- 4. 构造列表项：列及顺序由 Config.disk_columns 决定，未知列直接跳过 ---
+        let columns = Config::get()
+            .try_read()
+            .map(|c| c.disk_columns.clone())
+            .unwrap_or_default();
         let items: Vec<ListItem> = displayed_disks
-            .map(|(name, total, available, mount_point)| {
-                let used = total.saturating_sub(*available);
-                let pct = if *total > 0 {
-                    (used as f64 / *total as f64)
-                } else {
-                    0.0
-                };
-
-                // 进度条渲染
-                let bar_width = 12;
-                let filled = (pct * bar_width as f64).round() as usize;
-                let empty = bar_width - filled;
-                let bar_str = format!("[{}{}] ", "█".repeat(filled), "░".repeat(empty));
-
-                // 颜色策略
-                let color = if pct > 0.9 {
-                    Color::Red
-                } else if pct > 0.7 {
-                    Color::Yellow
-                } else {
-                    Color::Green
-                };
-
-                // 文本格式化
-                let info_text = format!(
-                    "{:>5.1}% {:>6.1} GB  {:<15}",
-                    pct * 100.0,
-                    *total as f64 / 1024.0 / 1024.0 / 1024.0,
-                    mount_point
-                );
-
-                ListItem::new(Line::from(vec![
-                    Span::styled(bar_str, Style::default().fg(color)),
-                    Span::raw(info_text),
-                    Span::styled(format!(" ({})", name), Style::default().fg(Color::DarkGray)),
-                ]))
-            })
+            .map(|disk| ListItem::new(Line::from(disk_spans(disk, &columns))))
             .collect();
 
-        // --- 5. 渲染组件 ---
+        // --- 5. 渲染组件：底部显示当前 (最上方可见) 磁盘的使用率趋势 ---
+        let trend = sorted_disks
+            .get(offset)
+            .and_then(|d| Some((d, self.disk_history.get(&d.3)?)))
+            .map(|(d, h)| format!(" {} {} {}% ", d.3, trend_line(h, DISK_TREND_POINTS), h.back().unwrap_or(&0)))
+            .unwrap_or_default();
         let list = List::new(items).block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" 🗄️ Mount Points ")
-                .border_style(if self.focus_index == Some(0) {
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD)
-                } else {
-                    Style::default().fg(Color::Gray)
-                }),
+                .title(crate::ui::style::focus_title(" 🗄️ Mount Points ", self.focus_index == Some(0)))
+                .title_bottom(trend)
+                .border_style(crate::ui::style::focus_border(self.focus_index == Some(0))),
         );
 
         f.render_widget(list, area);
@@ -437,40 +916,74 @@ impl Component for InfoComponent {
             db_bat.push_back(Default::default());
         }
 
-        // 2. 瞬间获取系统静态信息
-        let mut sys = System::new_all();
-        sys.refresh_all();
-        let system_info = format!("{}*{}*{}*{}", 
+        // 2. 瞬间获取系统静态信息 (只需内存总量，不做全量刷新)
+        let sys = System::new_with_specifics(
+            RefreshKind::nothing().with_memory(MemoryRefreshKind::everything()),
+        );
+        let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(0);
+        let system_info = format!("{}*{}*{}*{}*{}T", 
             System::cpu_arch(),
             System::name().unwrap_or_default(),
             System::kernel_long_version().split('-').next().unwrap_or(""),
-            System::os_version().unwrap_or_default()
+            System::os_version().unwrap_or_default(),
+            threads
         );
+        let mut cpu_topology = match System::physical_core_count() {
+            Some(cores) if cores != threads => format!("{}C/{}T", cores, threads),
+            _ => format!("{} threads", threads),
+        };
+        if let Some(clusters) = cpu_clusters(std::path::Path::new(CPU_SYSFS_ROOT)) {
+            cpu_topology = format!("{} · {}", cpu_topology, clusters);
+        }
 
         // 3. 关键：启动两个异步任务，一个抓取历史，一个持续监控
         Self::spawn_history_fetch_task(); // 新增：后台抓历史
         Self::spawn_monitor_task();       // 持续采样
+        Self::spawn_public_ip_task();     // 公网 IP (带退避重试)
 
         Self {
             glob_recv: GlobIO::recv(),
             mount_points: Default::default(),
+            disk_history: HashMap::new(),
             dir_list: AtlasPath::collect_dirs(),
+            dir_state: ListState::default(),
             ip_list: Default::default(),
-            focus_index: Some(0),
-            scroll_offsets: [0, 0, 0],
+            public_ip: Default::default(),
+            public_ip_since: Instant::now(),
+            show_all_interfaces: false,
+            show_ip_history: false,
+            ip_history: Vec::new(),
+            host_name: System::host_name().unwrap_or_else(|| "unknown".into()),
+            public_ptr: None,
+            focus_index: initial_focus(&Config::get()),
+            mount_scroll: 0,
+            ip_scroll: 0,
             total_mem_swap_mb: (sys.total_memory() / 1024 / 1024, sys.total_swap() / 1024 / 1024),
             mem_swap_history: db_mem.clone(),
             mem_swap_long_history: db_mem,
             cpu_info_history: db_cpu.clone(),
             cpu_info_long_history: db_cpu,
             bat_history: db_bat,
+            bat_now: None,
             system_info,
+            cpu_topology,
+            reference: None,
+            summary_mode: Config::get()
+                .try_read()
+                .map(|c| c.ui_state.info_summary)
+                .unwrap_or(false),
+            refreshing: false,
+            stats_cpu_temp: RunningStats::default(),
+            stats_mem: RunningStats::default(),
+            stats_bat: RunningStats::default(),
+            cpu_usage: 0.0,
+            self_usage: None,
         }
     }
         
 
-    /// 接受广播定期回传的信息
-    fn update(&mut self) -> bool {
+    /// 接受广播定期回传的信息 (后台时也记录历史，切回本页时曲线是完整的)
+    fn drain(&mut self) -> bool {
         /*
         要使 update 函数返回合理的 bool 值，核心逻辑是：只要任何一个数据源（MPSC 通道或 Broadcast 频道）在本次调用中产生了新数据，就将标志位设为 true。
         如果不返回 true，主循环就不会触发重绘，用户也就看不到最新的 CPU 使用率或进度条变化。
@@ -478,7 +991,7 @@ impl Component for InfoComponent {
         let mut changed = false;
 
         // 持续尝试接收来自全局通道的所有事件
-        while let Ok(event) = self.glob_recv.try_recv() {
+        while let Some(event) = GlobIO::try_recv(&mut self.glob_recv) {
             match event {
 
                 GlobalEvent::Data { key, data } => {
@@ -506,6 +1019,7 @@ impl Component for InfoComponent {
                         MEM_SWAP => {
                             if let Some(pkg) = data.0.downcast_ref::<MemSwapMB>() {
                                 self.mem_swap_history.push_back(*pkg);
+                                self.stats_mem.push(pkg.0 as f64);
                                 if self.mem_swap_history.len() > HISTORY_CAP {
                                     self.mem_swap_history.pop_front();
                                 }
@@ -526,12 +1040,28 @@ impl Component for InfoComponent {
                         ANDROID_CPU => {
                             if let Some(pkg) = data.0.downcast_ref::<CpuInfo>() {
                                 self.cpu_info_history.push_back(pkg.clone());
+                                // 0 表示读不到温度，不计入统计
+                                if pkg.2 > 0.0 {
+                                    self.stats_cpu_temp.push(pkg.2 as f64);
+                                }
                                 if self.cpu_info_history.len() > HISTORY_CAP {
                                     self.cpu_info_history.pop_front();
                                 }
                                 changed = true;
                             }
                         }
+                        CPU_USAGE => {
+                            if let Some(pkg) = data.0.downcast_ref::<CpuUsage>() {
+                                self.cpu_usage = *pkg;
+                                changed = true;
+                            }
+                        }
+                        SELF_USAGE => {
+                            if let Some(pkg) = data.0.downcast_ref::<SelfUsage>() {
+                                self.self_usage = Some(*pkg);
+                                changed = true;
+                            }
+                        }
                         // --- 4. CPU 核心、温度 (长周期) ---
                         ANDROID_CPU_LONG => {
                             if let Some(pkg) = data.0.downcast_ref::<CpuInfo>() {
@@ -545,21 +1075,64 @@ impl Component for InfoComponent {
                         // --- 5. 电池数据 (长周期) ---
                         ANDROID_BAT => {
                             if let Some(pkg) = data.0.downcast_ref::<AndroidBatInfo>() {
+                                self.bat_now = Some(pkg.clone());
                                 self.bat_history.push_back(pkg.clone());
+                                self.stats_bat.push(pkg.0 as f64);
                                 if self.bat_history.len() > HISTORY_CAP {
                                     self.bat_history.pop_front();
                                 }
                                 changed = true;
                             }
                         }
+                        ANDROID_BAT_NOW => {
+                            if let Some(pkg) = data.0.downcast_ref::<AndroidBatInfo>() {
+                                self.bat_now = Some(pkg.clone());
+                                changed = true;
+                            }
+                        }
                         // --- 6. 磁盘与 IP ---
                         DISK_IP => {
                             if let Some((disks, ips)) = data.0.downcast_ref::<DiskIP>() {
                                 self.mount_points = disks.clone();
+                                record_disk_history(&mut self.disk_history, disks);
                                 self.ip_list = ips.clone(); // 此时 ips 是 (Vec<String>, Vec<String>)
+                                self.refreshing = false;
+                                changed = true;
+                            }
+                        }
+                        IP_HISTORY => {
+                            if let Ok(h) = data.0.downcast::<Vec<IpChange>>() {
+                                self.ip_history = (*h).clone();
                                 changed = true;
                             }
                         }
+                        PUBLIC_IP => {
+                            if let Some(ip) = data.0.downcast_ref::<PublicIp>() {
+                                #[cfg(feature = "reverse-dns")]
+                                if let PublicIp::Known(addr) = ip {
+                                    self.lookup_ptr(addr);
+                                }
+                                // 已知 IP 失效后重新开始计时
+                                if matches!(self.public_ip, PublicIp::Known(_)) && !matches!(ip, PublicIp::Known(_)) {
+                                    self.public_ip_since = Instant::now();
+                                }
+                                // 超时后的后台重试失败不覆盖 Unavailable，避免两种状态来回跳
+                                if !(matches!(self.public_ip, PublicIp::Unavailable) && matches!(ip, PublicIp::Retrying(_))) {
+                                    self.public_ip = ip.clone();
+                                    changed = true;
+                                }
+                            }
+                        }
+                        #[cfg(feature = "reverse-dns")]
+                        PUBLIC_PTR => {
+                            if let Some(ptr) = data.0.downcast_ref::<(String, Option<String>)>() {
+                                // 丢弃过期的结果
+                                if self.public_ptr.as_ref().is_some_and(|(ip, _)| *ip == ptr.0) {
+                                    self.public_ptr = Some(ptr.clone());
+                                    changed = true;
+                                }
+                            }
+                        }
 
                         _ => {}
                     }
@@ -567,6 +1140,20 @@ impl Component for InfoComponent {
                 _ => {}
             }
         }
+
+        changed
+    }
+
+    fn update(&mut self) -> bool {
+        let mut changed = false;
+        // 后台任务卡住 (例如排队等网络许可) 时也不能一直停在 "..."
+        if matches!(self.public_ip, PublicIp::Pending | PublicIp::Retrying(_)) {
+            let timeout = Config::get().try_read().map(|c| c.public_ip_timeout_secs).unwrap_or(0);
+            if timeout > 0 && self.public_ip_since.elapsed() >= Duration::from_secs(timeout) {
+                self.public_ip = PublicIp::Unavailable;
+                changed = true;
+            }
+        }
         changed
     }
 
@@ -597,26 +1184,7 @@ impl Component for InfoComponent {
 
                 self.render_disk_list(f, list_chunks[0]);
 
-                {
-                    // 目录渲染
-                    f.render_widget(
-                        Paragraph::new(self.dir_list.join("\n"))
-                            .block(
-                                Block::default()
-                                    .borders(Borders::ALL)
-                                    .title(" 📂 Directories ")
-                                    .border_style(if self.focus_index == Some(1) {
-                                        Style::default()
-                                            .fg(Color::Yellow)
-                                            .add_modifier(Modifier::BOLD)
-                                    } else {
-                                        Style::default().fg(Color::Gray)
-                                    }),
-                            )
-                            .scroll((self.scroll_offsets[1], 0)),
-                        list_chunks[1],
-                    );
-                }
+                self.render_dir_list(f, list_chunks[1]);
                 self.render_ip_addresses(f, list_chunks[2]);
             }
         }
@@ -624,44 +1192,169 @@ impl Component for InfoComponent {
         // 磁盘渲染
 
         // 剩下的 chunks 严格对应 main_chunks 定义的顺序
-        if let Some(a) = main_chunks_cnt.next() {
-            self.render_mem_swap_status(f, *a);
-        }
-        if let Some(a) = main_chunks_cnt.next() {
-            self.render_cpu_status(f, *a);
-        }
-        if let Some(a) = main_chunks_cnt.next() {
-            self.render_battery_status(f, *a);
+        if self.summary_mode {
+            // 摘要模式：三块图表区合并为一个文字面板
+            let (mem, cpu, bat) = (main_chunks_cnt.next(), main_chunks_cnt.next(), main_chunks_cnt.next());
+            if let (Some(top), Some(bottom)) = (mem, bat.or(cpu)) {
+                let area = top.union(*bottom);
+                f.render_widget(
+                    Paragraph::new(self.summary_lines().join("\n")).block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(" 📋 Summary ['v' charts] "),
+                    ),
+                    area,
+                );
+            }
+        } else {
+            if let Some(a) = main_chunks_cnt.next() {
+                self.render_mem_swap_status(f, *a);
+            }
+            if let Some(a) = main_chunks_cnt.next() {
+                self.render_cpu_status(f, *a);
+            }
+            if let Some(a) = main_chunks_cnt.next() {
+                self.render_battery_status(f, *a);
+            }
         }
         {
             if let Some(area) = main_chunks_cnt.next() {
+                let mut footer = match &self.reference {
+                    Some(r) => {
+                        let at = DateTime::parse_from_rfc3339(&r.timestamp)
+                            .map(|t| t.with_timezone(&chrono::Local).format("%H:%M:%S").to_string())
+                            .unwrap_or_default();
+                        format!("Δ vs {} ['c' update, Esc clear] | {}", at, self.system_info)
+                    }
+                    None => self.system_info.clone(),
+                };
+                if self.refreshing {
+                    footer = format!("refreshing… | {}", footer);
+                }
+                // Atlas 自身的占用，便于确认空闲时确实很安静、日志缓冲没有无限增长
+                if let Some(u) = &self.self_usage {
+                    let fds = u.fds.map(|n| format!(" {}fd", n)).unwrap_or_default();
+                    let up = crate::version::format_uptime(crate::version::uptime());
+                    footer = format!("atlas {:.1}% {}MB{} up {} | {}", u.cpu, u.rss_mb, fds, up, footer);
+                }
+
+                // 左侧健康度徽章，右侧系统信息
+                let weights = Config::get()
+                    .try_read()
+                    .map(|c| c.health_weights)
+                    .unwrap_or_default();
+                let health = compute_health(&self.latest_metrics(), &weights);
+                let (label, color) = match health.level {
+                    HealthLevel::Good => ("Good", Color::Green),
+                    HealthLevel::Warn => ("Warn", Color::Yellow),
+                    HealthLevel::Crit => ("Crit", Color::Red),
+                };
+                let badge = format!(" ● Health {} {} ", health.score, label);
+                let parts = Layout::horizontal([
+                    Constraint::Length(badge.chars().count() as u16 + health.worst.len() as u16 + 3),
+                    Constraint::Min(0),
+                ])
+                .split(*area);
                 f.render_widget(
-                    Paragraph::new(self.system_info.clone())
+                    Paragraph::new(Line::from(vec![
+                        Span::styled(badge, Style::default().fg(Color::Black).bg(color).add_modifier(Modifier::BOLD)),
+                        Span::styled(format!(" {}", health.worst), Style::default().fg(color)),
+                    ])),
+                    parts[0],
+                );
+                f.render_widget(
+                    Paragraph::new(footer)
                         .alignment(Alignment::Right)
                         .style(
                             Style::default()
                                 .fg(Color::DarkGray)
                                 .add_modifier(Modifier::ITALIC),
                         ),
-                    *area,
+                    parts[1],
                 );
             }
         }
     }
 
+    // 跟随采集任务的基础周期
+    fn desired_tick_interval(&self) -> Option<Duration> {
+        Some(Duration::from_secs(INFO_UPDATE_INTERVAL_BASE))
+    }
+
     fn handle_key(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            // 捕获/更新参考快照
+            KeyCode::Char('c') => {
+                self.reference = Some(self.latest_record());
+                return true;
+            }
+            KeyCode::Esc if self.reference.is_some() => {
+                self.reference = None;
+                return true;
+            }
+            // 立即采集一次，结果到达前页脚显示 refreshing…
+            KeyCode::Char('r') => {
+                self.refreshing = true;
+                COLLECT_NOW.notify_one();
+                return true;
+            }
+            // 重置会话统计
+            KeyCode::Char('R') => {
+                self.stats_cpu_temp = RunningStats::default();
+                self.stats_mem = RunningStats::default();
+                self.stats_bat = RunningStats::default();
+                return true;
+            }
+            // 图表 / 文字摘要切换，并持久化到 ui_state
+            KeyCode::Char('v') => {
+                self.summary_mode = !self.summary_mode;
+                if let Ok(mut cfg) = Config::get().try_write() {
+                    cfg.ui_state.info_summary = self.summary_mode;
+                    if let Err(e) = cfg.save() {
+                        GlobIO::error(format!("Failed to save Info view mode: {}", e));
+                    }
+                }
+                return true;
+            }
+            _ => {}
+        }
+
         if let Some(ref mut idx) = self.focus_index {
             match key.code {
                 KeyCode::Tab => {
                     *idx = (*idx + 1) % 3;
                     true
                 }
-                KeyCode::Up => {
-                    self.scroll_offsets[*idx] = self.scroll_offsets[*idx].saturating_sub(1);
+                // 目录窗格是可选列表：上下移动时跳过标题行
+                KeyCode::Up | KeyCode::Down if *idx == 1 => {
+                    let next = next_dir_entry(&self.dir_list, self.dir_state.selected(), key.code == KeyCode::Down);
+                    if next.is_some() {
+                        self.dir_state.select(next);
+                    }
+                    true
+                }
+                KeyCode::Enter if *idx == 1 => {
+                    self.copy_selected_dir();
+                    true
+                }
+                KeyCode::Up | KeyCode::Down => {
+                    let scroll = if *idx == 0 { &mut self.mount_scroll } else { &mut self.ip_scroll };
+                    *scroll = if key.code == KeyCode::Up { scroll.saturating_sub(1) } else { scroll.saturating_add(1) };
+                    true
+                }
+                KeyCode::Char('f') if *idx == 2 => {
+                    self.show_all_interfaces = !self.show_all_interfaces;
                     true
                 }
-                KeyCode::Down => {
-                    self.scroll_offsets[*idx] = self.scroll_offsets[*idx].saturating_add(1);
+                KeyCode::Char('H') if *idx == 2 => {
+                    self.show_ip_history = !self.show_ip_history;
+                    self.ip_scroll = 0;
+                    true
+                }
+                KeyCode::Char('p') if *idx == 2 => {
+                    self.public_ip = PublicIp::Pending;
+                    self.public_ip_since = Instant::now();
+                    PUBLIC_IP_RETRY.notify_one();
                     true
                 }
                 _ => false,
@@ -676,29 +1369,96 @@ impl Component for InfoComponent {
 
 impl InfoComponent { // 辅助采集函数
 
+    /// 只包含内存与 CPU 使用率的 System；创建时的 CPU 读数只是基准，
+    /// 间隔 MINIMUM_CPU_UPDATE_INTERVAL 后再 refresh_cpu_usage 才有意义
+    fn sampling_system() -> System {
+        System::new_with_specifics(
+            RefreshKind::nothing()
+                .with_memory(MemoryRefreshKind::everything())
+                .with_cpu(CpuRefreshKind::nothing().with_cpu_usage()),
+        )
+    }
+
+    /// `--once --json` 用的一次性快照：直接调用采集函数，不经过广播、不启动后台任务。
+    /// CPU 使用率需要两次采样，因此会阻塞约 MINIMUM_CPU_UPDATE_INTERVAL
+    pub fn snapshot_json() -> serde_json::Value {
+        let mut sys = Self::sampling_system();
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        sys.refresh_cpu_usage();
+        let (freqs, zone0_temp, cpu_temp) = Self::task_collect_cpu();
+        let mut disks = Disks::new();
+        let disks = Self::task_collect_disks(&mut disks, true);
+        let (percent, state, temp) = Self::task_collect_battery();
+        let (v4, v6) = Self::ip_list();
+        serde_json::json!({
+            "version": crate::version::version_string(),
+            "timestamp": Utc::now().to_rfc3339(),
+            "cpu": {
+                "usage": sys.global_cpu_usage().clamp(0.0, 100.0),
+                "freqs_ghz": freqs,
+                "zone0_temp": zone0_temp,
+                "temp": cpu_temp,
+            },
+            "memory": {
+                "used_mb": sys.used_memory() / 1024 / 1024,
+                "total_mb": sys.total_memory() / 1024 / 1024,
+                "swap_used_mb": sys.used_swap() / 1024 / 1024,
+                "swap_total_mb": sys.total_swap() / 1024 / 1024,
+            },
+            "disks": disks.iter().map(|(name, total, avail, mount, fs)| serde_json::json!({
+                "name": name,
+                "mount": mount,
+                "fs": fs,
+                "total": total,
+                "available": avail,
+            })).collect::<Vec<_>>(),
+            "battery": { "percent": percent, "state": state, "temp": temp },
+            "ips": { "v4": v4, "v6": v6 },
+        })
+    }
 
     /// 在info 初始化时建立长期任务，定期发送系统信息
     fn spawn_monitor_task() {
         tokio::spawn(async move {
             let glob_send = GlobIO::send();
-            let mut sys = System::new_all();
+            // 采样模型：只刷新需要的部分 (内存 + CPU 使用率)。
+            // CPU 使用率是两次 refresh_cpu_usage 之间的差值，首次读数无意义，
+            // 因此先预热一次并等待 MINIMUM_CPU_UPDATE_INTERVAL；之后每个基础周期 (2s) 远大于该下限
+            let mut sys = Self::sampling_system();
+            tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+            // 与 sys 一样常驻，避免每次重新枚举分配 Disks
+            let mut disks = Disks::new_with_refreshed_list();
             let mut tick_count: u64 = 0;
             let mut interval = tokio::time::interval(Duration::from_secs(INFO_UPDATE_INTERVAL_BASE));
+            let mut ip_tracker = IpTracker::new(&["v4", "v6"]);
+            let self_pid = sysinfo::get_current_pid().ok();
+            let mut alerts = crate::alerts::AlertEngine::default();
 
             // 启动预热
             Self::perform_full_sync(&mut sys, &glob_send);
 
             loop {
-                interval.tick().await;
-                tick_count = tick_count.wrapping_add(1);
+                // 手动触发的采集不计入周期计数，长/中周期的节奏保持不变
+                let forced = next_pass(&mut interval, &COLLECT_NOW).await;
+                if forced {
+                    Self::perform_full_sync(&mut sys, &glob_send);
+                } else {
+                    tick_count = tick_count.wrapping_add(1);
+                }
 
-                // --- 1. 基础数据采集 (每秒) ---
+                // --- 1. 基础数据采集 (每个基础周期) ---
                 sys.refresh_memory();
+                sys.refresh_cpu_usage();
                 let mem_val: MemSwapMB = (
                     sys.used_memory() / 1024 / 1024,
                     sys.used_swap() / 1024 / 1024,
                 );
                 let cpu_val = Self::task_collect_cpu();
+                let usage: CpuUsage = sys.global_cpu_usage().clamp(0.0, 100.0);
+                let _ = glob_send.send(GlobalEvent::Data { key: CPU_USAGE, data: DynamicPayload(Arc::new(usage)) });
+                if let Some(own) = Self::collect_self_usage(&mut sys, self_pid) {
+                    let _ = glob_send.send(GlobalEvent::Data { key: SELF_USAGE, data: DynamicPayload(Arc::new(own)) });
+                }
 
                 // 包装为 Arc Payload
                 let mem_payload = DynamicPayload(Arc::new(mem_val));
@@ -708,9 +1468,26 @@ impl InfoComponent { // 辅助采集函数
                 let _ = glob_send.send(GlobalEvent::Data { key: MEM_SWAP, data: mem_payload.clone() });
                 let _ = glob_send.send(GlobalEvent::Data { key: ANDROID_CPU, data: cpu_payload.clone() });
 
+                // 告警指标：本周期采到的值，电池/磁盘只在各自的周期加入
+                let mut samples = Vec::new();
+                if let Some(t) = known_temp(cpu_val.2) {
+                    samples.push(("cpu_temp", t));
+                }
+                if sys.total_memory() > 0 {
+                    samples.push(("mem_pct", sys.used_memory() as f64 / sys.total_memory() as f64 * 100.0));
+                }
+
+                // 手动采集只刷新电池显示，不写库也不进入长周期历史
+                if forced {
+                    let bat_val = Self::task_collect_battery();
+                    samples.extend(known_battery_pct(&bat_val).map(|p| ("battery_pct", p)));
+                    let _ = glob_send.send(GlobalEvent::Data { key: ANDROID_BAT_NOW, data: DynamicPayload(Arc::new(bat_val)) });
+                }
+
                 // --- 3. 长周期处理 (数据库存储 + 历史分发) ---
-                if tick_count % INFO_UPDATE_INTERVAL_SLOWEST == 1 {
+                if !forced && tick_count % INFO_UPDATE_INTERVAL_SLOWEST == 1 {
                     let bat_val = Self::task_collect_battery();
+                    samples.extend(known_battery_pct(&bat_val).map(|p| ("battery_pct", p)));
                     let bat_payload = DynamicPayload(Arc::new(bat_val.clone()));
 
                     // A. 构造持久化记录 (结构与发送一致)
@@ -723,12 +1500,8 @@ impl InfoComponent { // 辅助采集函数
 
                     // B. 异步存入 MongoDB (使用全局 DATABASE_NAME)
                     // let _ = Mongo::save(DATABASE_NAME, COLL_NAME, record).await;
-                    let record_to_save = record.clone();
-                    tokio::spawn(async move {
-                        if let Err(e) = record_to_save.save_to_db().await {
-                                    // 可以通过 glob_send 发送一个错误通知给 UI
-                                }
-                    });
+                    // 进入写入队列，由后台 writer 批量提交
+                    record.queue();
 
                     // C. 分发长周期 Payload
                     let _ = glob_send.send(GlobalEvent::Data { key: MEM_SWAP_LONG, data: mem_payload });
@@ -737,42 +1510,173 @@ impl InfoComponent { // 辅助采集函数
                 }
 
                 // --- 4. 中周期分发 (磁盘与网络) ---
-                if tick_count % INFO_UPDATE_INTERVAL_SLOW_TIMES == 1 {
-                    let pkg: DiskIP = (Self::task_collect_disks(), Self::ip_list());
+                if forced || tick_count % INFO_UPDATE_INTERVAL_SLOW_TIMES == 1 {
+                    // 每 DISK_RELIST_EVERY 次中周期重新枚举一次，以发现新挂载/卸载的设备 (手动采集时总是重新枚举)
+                    let relist = forced || (tick_count / INFO_UPDATE_INTERVAL_SLOW_TIMES).is_multiple_of(DISK_RELIST_EVERY);
+                    let pkg: DiskIP = (Self::task_collect_disks(&mut disks, relist), Self::ip_list());
+                    let fullest = disks
+                        .iter()
+                        .filter(|d| d.total_space() > 0)
+                        .filter(|d| {
+                            counts_for_disk_alert(&d.name().to_string_lossy(), &d.file_system().to_string_lossy(), d.is_read_only())
+                        })
+                        .map(|d| d.total_space().saturating_sub(d.available_space()) as f64 / d.total_space() as f64 * 100.0)
+                        .fold(None, |acc: Option<f64>, p| Some(acc.map_or(p, |a| a.max(p))));
+                    if let Some(p) = fullest {
+                        samples.push(("disk_pct", p));
+                    }
+                    ip_tracker.observe(IpTracker::local_snapshot(&pkg.1)).await;
                     let _ = glob_send.send(GlobalEvent::Data {
                         key: DISK_IP,
                         data: DynamicPayload(Arc::new(pkg)),
                     });
                 }
+
+                // --- 5. 告警规则 (配置被占用时跳过本次，保留规则状态) ---
+                if let Ok(cfg) = Config::get().try_read() {
+                    for (msg, level) in alerts.evaluate(&cfg.alerts, &samples, std::time::Instant::now()) {
+                        let _ = glob_send.send(GlobalEvent::Status(msg, level, None));
+                    }
+                }
             }
         });
     }
 
-    fn spawn_history_fetch_task() {
+    /// 公网 IP 后台任务：失败时指数退避重试，成功后每 PUBLIC_IP_REFRESH_SECS 刷新一次；
+    /// 本地网卡变化时立即重置退避并重新获取
+    fn spawn_public_ip_task() {
         tokio::spawn(async move {
             let glob_send = GlobIO::send();
-            
-            // 1. 先确保表已存在（SQLite 启动极快，这里调用是安全的）
-            if let Err(e) = TelemetryRecord::init_table().await {
-                eprintln!("🔴 SQL Table Init Error: {}", e);
-                return;
-            }
+            let send = |ip: PublicIp| {
+                let _ = glob_send.send(GlobalEvent::Data { key: PUBLIC_IP, data: DynamicPayload(Arc::new(ip)) });
+            };
+            let mut attempt: u32 = 0;
+            let mut ip_tracker = IpTracker::new(&["public"]);
+            let timeout = Config::get().read().await.public_ip_timeout_secs;
+            // 本轮获取的截止时间，成功后清空；手动重试或网卡变化时重新计时
+            let give_up_from_now = || (timeout > 0).then(|| tokio::time::Instant::now() + Duration::from_secs(timeout));
+            let mut give_up = give_up_from_now();
 
-            // 2. 异步拉取历史
-            let db_records = TelemetryRecord::fetch_recent(HISTORY_CAP as i64).await;
-            
-            if !db_records.is_empty() {
-                let _ = glob_send.send(GlobalEvent::Data {
-                    key: "HISTORY_REFILL", 
-                    data: DynamicPayload(Arc::new(db_records)) 
-                });
+            loop {
+                let fetched = Self::fetch_public_ip().await;
+                let wait = public_ip_next_wait(&mut attempt, fetched.is_ok());
+                match fetched {
+                    Ok(ip) => {
+                        give_up = None;
+                        ip_tracker
+                            .observe(std::collections::BTreeMap::from([(("public".into(), "-".into()), ip.clone())]))
+                            .await;
+                        send(PublicIp::Known(ip));
+                    }
+                    Err(_) => {
+                        if give_up.is_some_and(|d| tokio::time::Instant::now() >= d) {
+                            send(PublicIp::Unavailable);
+                        } else {
+                            send(PublicIp::Retrying(attempt));
+                        }
+                    }
+                }
+
+                // 分段等待，期间网卡变化或按下 'p' 则提前结束并重置退避
+                let local = Self::ip_list();
+                let deadline = tokio::time::Instant::now() + wait;
+                while tokio::time::Instant::now() < deadline {
+                    let step = deadline
+                        .saturating_duration_since(tokio::time::Instant::now())
+                        .min(Duration::from_secs(NET_CHANGE_POLL_SECS));
+                    tokio::select! {
+                        _ = tokio::time::sleep(step) => {}
+                        _ = PUBLIC_IP_RETRY.notified() => {
+                            attempt = 0;
+                            give_up = give_up_from_now();
+                            break;
+                        }
+                    }
+                    if Self::ip_list() != local {
+                        attempt = 0;
+                        give_up = give_up_from_now();
+                        break;
+                    }
+                }
             }
         });
     }
 
-    // 提取出一个全量同步函数，供初始化和特殊时刻调用
-    fn perform_full_sync(sys: &mut System, glob_send: &GlobSend) {
-        sys.refresh_memory();
+    /// 公网 IP 变化时后台查询 PTR，失败静默 (只显示 IP)
+    #[cfg(feature = "reverse-dns")]
+    fn lookup_ptr(&mut self, ip: &str) {
+        if self.public_ptr.as_ref().is_some_and(|(cached, _)| cached == ip) {
+            return;
+        }
+        let Ok(addr) = ip.parse::<std::net::IpAddr>() else {
+            return;
+        };
+        self.public_ptr = Some((ip.to_string(), None));
+        let ip = ip.to_string();
+        tokio::spawn(async move {
+            let ptr = crate::rdns::reverse_lookup(addr).await;
+            let _ = GlobIO::send().send(GlobalEvent::Data {
+                key: PUBLIC_PTR,
+                data: DynamicPayload(Arc::new((ip, ptr))),
+            });
+        });
+    }
+
+    /// 通过明文 HTTP 请求 api.ipify.org 获取公网 IP (无需额外的 HTTP 依赖)
+    async fn fetch_public_ip() -> std::io::Result<String> {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let invalid = |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, msg.to_string());
+
+        // 排队等待许可的时间不计入超时
+        let _permit = crate::net::permit().await;
+        let request = async {
+            let mut stream = tokio::net::TcpStream::connect("api.ipify.org:80").await?;
+            stream
+                .write_all(b"GET / HTTP/1.1\r\nHost: api.ipify.org\r\nConnection: close\r\n\r\n")
+                .await?;
+            let mut buf = Vec::new();
+            stream.read_to_end(&mut buf).await?;
+            Ok::<_, std::io::Error>(buf)
+        };
+        let buf = tokio::time::timeout(Duration::from_secs(10), request)
+            .await
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "public ip timeout"))??;
+
+        let resp = String::from_utf8_lossy(&buf);
+        let (head, body) = resp.split_once("\r\n\r\n").ok_or_else(|| invalid("malformed response"))?;
+        if !head.starts_with("HTTP/1.1 200") && !head.starts_with("HTTP/1.0 200") {
+            return Err(invalid("unexpected status"));
+        }
+        let ip = body.trim();
+        ip.parse::<std::net::IpAddr>().map_err(|_| invalid("invalid ip"))?;
+        Ok(ip.to_string())
+    }
+
+    fn spawn_history_fetch_task() {
+        tokio::spawn(async move {
+            let glob_send = GlobIO::send();
+            
+            // 1. 先确保表已存在（SQLite 启动极快，这里调用是安全的）
+            if let Err(e) = TelemetryRecord::init_table().await {
+                log::error!("SQL table init error: {}", e);
+                return;
+            }
+
+            // 2. 异步拉取历史
+            let db_records = TelemetryRecord::fetch_recent(HISTORY_CAP as i64).await;
+            
+            if !db_records.is_empty() {
+                let _ = glob_send.send(GlobalEvent::Data {
+                    key: "HISTORY_REFILL", 
+                    data: DynamicPayload(Arc::new(db_records)) 
+                });
+            }
+        });
+    }
+
+    // 提取出一个全量同步函数，供初始化和特殊时刻调用
+    fn perform_full_sync(sys: &mut System, glob_send: &GlobSend) {
+        sys.refresh_memory();
         let mem = (
             sys.used_memory() / 1024 / 1024,
             sys.used_swap() / 1024 / 1024,
@@ -797,7 +1701,7 @@ impl InfoComponent { // 辅助采集函数
                 )
             } else {
                 // 如果 termux-api 调用失败（例如未安装 API 包），返回默认值
-                (0, "Unknown".to_string(), 0.0)
+                (0, BATTERY_UNKNOWN.to_string(), 0.0)
             }            
         }
         #[cfg(not(target_os = "android"))]
@@ -821,26 +1725,35 @@ impl InfoComponent { // 辅助采集函数
                     .unwrap_or(0.0);
                 freqs.push(f);
             }
-            let read_zone = |z| {
-                std::fs::read_to_string(format!("/sys/class/thermal/thermal_zone{}/temp", z))
-                    .ok()
-                    .and_then(|s| s.trim().parse::<f32>().ok())
-                    .map(|t| t / 1000.0)
-                    .unwrap_or(0.0)
-            };
-            (freqs, read_zone(0), read_zone(7))            
+            let root = std::path::Path::new(THERMAL_ROOT);
+            (freqs, read_zone_temp(&root.join("thermal_zone0")).unwrap_or(0.0), Self::cpu_temp(root))
         }
         #[cfg(not(target_os = "android"))]
         {
-            (vec![0.0; 8], 0.0, 0.0)
+            // 桌面 Linux 同样有 /sys/class/thermal，其他平台读不到时为 0
+            (vec![0.0; 8], 0.0, Self::cpu_temp(std::path::Path::new(THERMAL_ROOT)))
         }
 
     }
 
+    fn cpu_temp(root: &std::path::Path) -> f32 {
+        let patterns = Config::get()
+            .try_read()
+            .map(|c| c.thermal_zone_types.clone())
+            .unwrap_or_default();
+        discover_cpu_temp(root, &patterns)
+    }
+
     // --- 辅助采集函数：磁盘 ---
-    fn task_collect_disks() -> Vec<DiskInf> {
-        let mut disks = Disks::new_with_refreshed_list();
-        disks.refresh(true);
+    /// relist=false 时只刷新已知磁盘的容量，不重新读取挂载表
+    fn task_collect_disks(disks: &mut Disks, relist: bool) -> Vec<DiskInf> {
+        if relist {
+            disks.refresh(true);
+        } else {
+            for d in disks.list_mut() {
+                d.refresh_specifics(DiskRefreshKind::nothing().with_storage());
+            }
+        }
         disks
             .iter()
             .map(|d| {
@@ -849,11 +1762,28 @@ impl InfoComponent { // 辅助采集函数
                     d.total_space(),
                     d.available_space(),
                     d.mount_point().to_string_lossy().into_owned(),
+                    d.file_system().to_string_lossy().into_owned(),
                 )
             })
             .collect()
     }
 
+    /// 只刷新自身进程；CPU% 同样是两次刷新之间的差值，首个周期为 0
+    fn collect_self_usage(sys: &mut System, pid: Option<sysinfo::Pid>) -> Option<SelfUsage> {
+        let pid = pid?;
+        sys.refresh_processes_specifics(
+            sysinfo::ProcessesToUpdate::Some(&[pid]),
+            true,
+            sysinfo::ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+        let p = sys.process(pid)?;
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        let fds = std::fs::read_dir("/proc/self/fd").ok().map(|d| d.count());
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        let fds = None;
+        Some(SelfUsage { cpu: p.cpu_usage(), rss_mb: p.memory() / 1024 / 1024, fds })
+    }
+
     fn ip_list() -> (Vec<String>, Vec<String>) {
         let mut v4_list = Vec::new();
         let mut v6_list = Vec::new();
@@ -879,6 +1809,219 @@ impl InfoComponent { // 辅助采集函数
 }
 
 
+/// ip_history 表中的一条变化记录：address 为空表示该网卡不再有此类地址
+#[derive(Debug, Clone, PartialEq)]
+pub struct IpChange {
+    pub timestamp: String,
+    pub kind: String, // v4 / v6 / public
+    pub iface: String,
+    pub address: String,
+}
+
+/// (kind, iface) -> 地址 (同一网卡多个地址时排序后以逗号连接)
+type IpState = std::collections::BTreeMap<(String, String), String>;
+
+impl IpChange {
+    pub async fn init_table() -> Result<(), String> {
+        crate::db::Database::setup_table(
+            r#"
+            CREATE TABLE IF NOT EXISTS ip_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                iface TEXT NOT NULL,
+                address TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_ip_history_ts ON ip_history(timestamp);
+        "#,
+        )
+        .await
+    }
+
+    /// 对比两次快照 (只比较 kinds 中的类型)：新增或变化的记录当前地址，消失的记录空地址
+    fn diff(prev: &IpState, next: &IpState, kinds: &[&str], timestamp: &str) -> Vec<Self> {
+        let make = |(kind, iface): &(String, String), address: &str| Self {
+            timestamp: timestamp.to_string(),
+            kind: kind.clone(),
+            iface: iface.clone(),
+            address: address.to_string(),
+        };
+        let mut out: Vec<Self> = next
+            .iter()
+            .filter(|(k, v)| prev.get(*k) != Some(*v))
+            .map(|(k, v)| make(k, v))
+            .collect();
+        out.extend(
+            prev.iter()
+                .filter(|(k, v)| kinds.contains(&k.0.as_str()) && !v.is_empty() && !next.contains_key(*k))
+                .map(|(k, _)| make(k, "")),
+        );
+        out
+    }
+
+    /// 每个 (kind, iface) 最后一次记录的地址，用于跨重启判断是否变化
+    async fn last_known() -> IpState {
+        let pool = crate::db::Database::pool();
+        sqlx::query(
+            "SELECT kind, iface, address FROM ip_history WHERE id IN (SELECT MAX(id) FROM ip_history GROUP BY kind, iface)",
+        )
+        .fetch_all(pool)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|row: SqliteRow| ((row.get("kind"), row.get("iface")), row.get("address")))
+        .collect()
+    }
+
+    async fn save(changes: &[Self]) -> Result<(), String> {
+        let pool = crate::db::Database::pool();
+        let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+        for c in changes {
+            sqlx::query("INSERT INTO ip_history (timestamp, kind, iface, address) VALUES (?, ?, ?, ?)")
+                .bind(&c.timestamp)
+                .bind(&c.kind)
+                .bind(&c.iface)
+                .bind(&c.address)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        tx.commit().await.map_err(|e| e.to_string())
+    }
+
+    pub async fn fetch_recent(limit: i64) -> Vec<Self> {
+        let pool = crate::db::Database::pool();
+        sqlx::query("SELECT timestamp, kind, iface, address FROM ip_history ORDER BY id DESC LIMIT ?")
+            .bind(limit)
+            .fetch_all(pool)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|row: SqliteRow| Self {
+                timestamp: row.get("timestamp"),
+                kind: row.get("kind"),
+                iface: row.get("iface"),
+                address: row.get("address"),
+            })
+            .collect()
+    }
+
+    fn line(&self) -> Line<'static> {
+        let at = DateTime::parse_from_rfc3339(&self.timestamp)
+            .map(|t| t.with_timezone(&chrono::Local).format("%m-%d %H:%M").to_string())
+            .unwrap_or_else(|_| self.timestamp.clone());
+        let address = match self.address.as_str() {
+            "" => Span::styled("(gone)", Style::default().fg(Color::DarkGray)),
+            a => Span::styled(a.to_string(), Style::default().fg(Color::Cyan)),
+        };
+        Line::from(vec![
+            Span::styled(format!("{} ", at), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("{:<6} ", self.kind), Style::default().fg(Color::Yellow)),
+            Span::raw(format!("{}: ", self.iface)),
+            address,
+        ])
+    }
+}
+
+/// 后台采集任务持有：只在地址真正变化时写入 ip_history，并广播最新的变化列表
+struct IpTracker {
+    kinds: &'static [&'static str],
+    known: Option<IpState>,
+}
+
+impl IpTracker {
+    fn new(kinds: &'static [&'static str]) -> Self {
+        Self { kinds, known: None }
+    }
+
+    /// ip_list() 的 "name: ip" 条目转为快照
+    fn local_snapshot(ips: &IPData) -> IpState {
+        let mut grouped: std::collections::BTreeMap<(String, String), Vec<&str>> = Default::default();
+        for (kind, list) in [("v4", &ips.0), ("v6", &ips.1)] {
+            for (iface, addr) in list.iter().filter_map(|e| e.split_once(": ")) {
+                grouped.entry((kind.into(), iface.into())).or_default().push(addr);
+            }
+        }
+        grouped
+            .into_iter()
+            .map(|(k, mut v)| {
+                v.sort_unstable();
+                (k, v.join(","))
+            })
+            .collect()
+    }
+
+    async fn observe(&mut self, next: IpState) {
+        let enabled = Config::get().try_read().map(|c| c.ip_history).unwrap_or(true);
+        // 空快照通常是读取网卡失败，不能当作"所有地址消失"记录
+        if !enabled || next.is_empty() || crate::db::Database::is_read_only() {
+            return;
+        }
+        let first = self.known.is_none();
+        if first {
+            if let Err(e) = IpChange::init_table().await {
+                log::error!("ip_history table init error: {}", e);
+                return;
+            }
+            self.known = Some(IpChange::last_known().await);
+        }
+        let prev = self.known.as_ref().map(|k| {
+            k.iter()
+                .filter(|(key, _)| self.kinds.contains(&key.0.as_str()))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect::<IpState>()
+        });
+        let changes = IpChange::diff(&prev.unwrap_or_default(), &next, self.kinds, &Utc::now().to_rfc3339());
+        if !changes.is_empty()
+            && let Err(e) = IpChange::save(&changes).await
+        {
+            log::error!("ip_history write error: {}", e);
+        }
+        self.known = Some(next);
+        if first || !changes.is_empty() {
+            let recent = IpChange::fetch_recent(IP_HISTORY_SHOWN).await;
+            let _ = GlobIO::send().send(GlobalEvent::Data { key: IP_HISTORY, data: DynamicPayload(Arc::new(recent)) });
+        }
+    }
+}
+
+/// 一次维护 (归档 + 清理 + VACUUM) 的结果
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceReport {
+    pub archived: u64,
+    pub pruned: u64,
+    pub bytes_reclaimed: u64,
+}
+
+// 维护不允许并发执行 (重复触发时直接拒绝)
+static MAINTENANCE_RUNNING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// 持有期间 MAINTENANCE_RUNNING 为 true；future 被取消或 panic 时也会在 drop 中清除
+struct MaintenanceGuard;
+
+impl MaintenanceGuard {
+    fn acquire() -> Option<Self> {
+        (!MAINTENANCE_RUNNING.swap(true, std::sync::atomic::Ordering::AcqRel)).then_some(Self)
+    }
+}
+
+impl Drop for MaintenanceGuard {
+    fn drop(&mut self) {
+        MAINTENANCE_RUNNING.store(false, std::sync::atomic::Ordering::Release);
+    }
+}
+
+/// 遥测写入队列容量与单次事务的最大条数
+const DB_WRITE_QUEUE_CAP: usize = 256;
+const DB_FLUSH_BATCH: usize = 32;
+
+static TELEMETRY_WRITER: std::sync::OnceLock<mpsc::Sender<WriterMsg>> = std::sync::OnceLock::new();
+
+enum WriterMsg {
+    Record(TelemetryRecord),
+    Flush(tokio::sync::oneshot::Sender<()>),
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TelemetryRecord {
     pub timestamp: String, // 改为 String 提高序列化兼容性
@@ -903,18 +2046,218 @@ impl TelemetryRecord {
         crate::db::Database::setup_table(ddl).await
     }
 
-    /// 存储记录到 SQLite
-    pub async fn save_to_db(&self) -> Result<(), String> {
+    /// 归档表结构，第一次维护时创建
+    async fn init_archive_table() -> Result<(), String> {
+        let ddl = r#"
+            CREATE TABLE IF NOT EXISTS telemetry_archive (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT NOT NULL,
+                cpu_data TEXT NOT NULL,
+                mem_swap TEXT NOT NULL,
+                battery_data TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_telemetry_archive_ts ON telemetry_archive(timestamp);
+        "#;
+        crate::db::Database::setup_table(ddl).await
+    }
+
+    /// 在一个事务中写入一批记录 (一次 fsync)，减少闪存磨损
+    pub async fn save_batch(records: &[Self]) -> Result<(), String> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        let mut tx = crate::db::Database::pool().begin().await.map_err(|e| e.to_string())?;
+        for r in records {
+            sqlx::query("INSERT INTO telemetry (timestamp, cpu_data, mem_swap, battery_data) VALUES (?, ?, ?, ?)")
+                .bind(&r.timestamp)
+                .bind(serde_json::to_string(&r.cpu_data).unwrap_or_default())
+                .bind(serde_json::to_string(&r.mem_swap).unwrap_or_default())
+                .bind(serde_json::to_string(&r.battery_data).unwrap_or_default())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        tx.commit().await.map_err(|e| e.to_string())
+    }
+
+    /// 放入写入队列；首次调用时启动后台 writer
+    pub fn queue(self) {
+        // 另一个实例持有数据目录锁：只显示不落盘，避免两个进程同时写库
+        if crate::db::Database::is_read_only() {
+            return;
+        }
+        let tx = TELEMETRY_WRITER.get_or_init(Self::spawn_writer);
+        if tx.try_send(WriterMsg::Record(self)).is_err() {
+            GlobIO::warn("Telemetry write queue full, record dropped");
+        }
+    }
+
+    /// 立即写入队列中剩余的记录 (退出前调用)
+    pub async fn flush() {
+        let Some(tx) = TELEMETRY_WRITER.get() else {
+            return;
+        };
+        let (done, rx) = tokio::sync::oneshot::channel();
+        if tx.send(WriterMsg::Flush(done)).await.is_ok() {
+            let _ = rx.await;
+        }
+    }
+
+    fn spawn_writer() -> mpsc::Sender<WriterMsg> {
+        let flush_secs = Config::get()
+            .try_read()
+            .map(|c| c.db_flush_secs)
+            .unwrap_or(300);
+        Self::spawn_writer_every(flush_secs)
+    }
+
+    /// 后台 writer：攒够 DB_FLUSH_BATCH 条或每 flush_secs 秒提交一次事务
+    fn spawn_writer_every(flush_secs: u64) -> mpsc::Sender<WriterMsg> {
+        let (tx, mut rx) = mpsc::channel::<WriterMsg>(DB_WRITE_QUEUE_CAP);
+        let flush_secs = flush_secs.max(1);
+
+        tokio::spawn(async move {
+            let mut pending: Vec<TelemetryRecord> = Vec::new();
+            let mut ticker = tokio::time::interval(Duration::from_secs(flush_secs));
+            ticker.tick().await; // 跳过立即触发的第一次
+
+            let mut closed = false;
+            while !closed {
+                let ack = tokio::select! {
+                    msg = rx.recv() => match msg {
+                        Some(WriterMsg::Record(r)) => {
+                            pending.push(r);
+                            if pending.len() < DB_FLUSH_BATCH {
+                                continue;
+                            }
+                            None
+                        }
+                        Some(WriterMsg::Flush(done)) => Some(done),
+                        None => {
+                            closed = true; // 发送端全部关闭：写完剩余后退出
+                            None
+                        }
+                    },
+                    _ = ticker.tick() => None,
+                };
+
+                if let Err(e) = Self::save_batch(&pending).await {
+                    GlobIO::error(format!("Telemetry batch write failed: {}", e));
+                }
+                pending.clear();
+                if let Some(done) = ack {
+                    let _ = done.send(());
+                }
+            }
+        });
+        tx
+    }
+
+    /// 维护：早于 retain_days 的记录移入 telemetry_archive，归档中早于 archive_days 的删除，最后 VACUUM。
+    /// dry_run 时只用 COUNT(*) 统计会受影响的行数，不修改数据库 (bytes_reclaimed 为 0)。
+    /// 已有维护在执行或处于只读模式时返回 Err
+    pub async fn maintenance(retain_days: u32, archive_days: u32, dry_run: bool) -> Result<MaintenanceReport, String> {
+        if dry_run {
+            return Self::preview_maintenance(retain_days, archive_days).await;
+        }
+        if crate::db::Database::is_read_only() {
+            return Err("database is read-only (another instance is running)".into());
+        }
+        let Some(_guard) = MaintenanceGuard::acquire() else {
+            return Err("maintenance is already running".into());
+        };
+        Self::run_maintenance(retain_days, archive_days).await
+    }
+
+    /// 与 run_maintenance 统计口径一致：刚归档的行若同时早于 archive_days，也会在同一事务中被删除
+    async fn preview_maintenance(retain_days: u32, archive_days: u32) -> Result<MaintenanceReport, String> {
         let pool = crate::db::Database::pool();
-        sqlx::query("INSERT INTO telemetry (timestamp, cpu_data, mem_swap, battery_data) VALUES (?, ?, ?, ?)")
-            .bind(&self.timestamp)
-            .bind(serde_json::to_string(&self.cpu_data).unwrap_or_default())
-            .bind(serde_json::to_string(&self.mem_swap).unwrap_or_default())
-            .bind(serde_json::to_string(&self.battery_data).unwrap_or_default())
-            .execute(pool)
+        let err = |e: sqlx::Error| e.to_string();
+        let cutoff = |days: u32| (Utc::now() - chrono::Duration::days(days as i64)).to_rfc3339();
+        let (retain, archive) = (cutoff(retain_days), cutoff(archive_days));
+
+        Self::flush().await;
+        let archived: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM telemetry WHERE timestamp < ?")
+            .bind(&retain)
+            .fetch_one(pool)
+            .await
+            .map_err(err)?;
+        let mut pruned: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM telemetry WHERE timestamp < ? AND timestamp < ?")
+            .bind(&retain)
+            .bind(&archive)
+            .fetch_one(pool)
             .await
-            .map(|_| ())
-            .map_err(|e| e.to_string())
+            .map_err(err)?;
+        // 归档表在第一次维护时才创建
+        let has_archive: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='telemetry_archive')",
+        )
+        .fetch_one(pool)
+        .await
+        .map_err(err)?;
+        if has_archive {
+            pruned += sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM telemetry_archive WHERE timestamp < ?")
+                .bind(&archive)
+                .fetch_one(pool)
+                .await
+                .map_err(err)?;
+        }
+
+        Ok(MaintenanceReport {
+            archived: archived.max(0) as u64,
+            pruned: pruned.max(0) as u64,
+            bytes_reclaimed: 0,
+        })
+    }
+
+    async fn run_maintenance(retain_days: u32, archive_days: u32) -> Result<MaintenanceReport, String> {
+        let pool = crate::db::Database::pool();
+        let err = |e: sqlx::Error| e.to_string();
+        let cutoff = |days: u32| (Utc::now() - chrono::Duration::days(days as i64)).to_rfc3339();
+        let db_bytes = || async {
+            sqlx::query_scalar::<_, i64>("SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()")
+                .fetch_one(pool)
+                .await
+                .unwrap_or(0)
+        };
+
+        // 写入队列里尚未落盘的记录先提交，避免与归档交错
+        Self::flush().await;
+        Self::init_archive_table().await?;
+        let before = db_bytes().await;
+
+        let mut tx = pool.begin().await.map_err(err)?;
+        let retain = cutoff(retain_days);
+        let archived = sqlx::query(
+            "INSERT INTO telemetry_archive (timestamp, cpu_data, mem_swap, battery_data) \
+             SELECT timestamp, cpu_data, mem_swap, battery_data FROM telemetry WHERE timestamp < ?",
+        )
+        .bind(&retain)
+        .execute(&mut *tx)
+        .await
+        .map_err(err)?
+        .rows_affected();
+        sqlx::query("DELETE FROM telemetry WHERE timestamp < ?")
+            .bind(&retain)
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?;
+        let pruned = sqlx::query("DELETE FROM telemetry_archive WHERE timestamp < ?")
+            .bind(cutoff(archive_days))
+            .execute(&mut *tx)
+            .await
+            .map_err(err)?
+            .rows_affected();
+        tx.commit().await.map_err(err)?;
+
+        sqlx::query("VACUUM").execute(pool).await.map_err(err)?;
+        let after = db_bytes().await;
+
+        Ok(MaintenanceReport {
+            archived,
+            pruned,
+            bytes_reclaimed: before.saturating_sub(after) as u64,
+        })
     }
 
     /// 从 SQLite 获取最近记录
@@ -937,7 +2280,1031 @@ impl TelemetryRecord {
     }
 }
 
+/// 图表覆盖的时间跨度 (可见样本数 × 采样间隔)，作为标题后缀，如 "last 5m"
+fn span_label(samples: usize, interval_secs: u64) -> String {
+    format!("last {}", crate::version::format_uptime(Duration::from_secs(samples as u64 * interval_secs)))
+}
+
+/// 温度按 (下限, 上限) 线性映射到 0-100 (超出范围的截断)，供 max(100) 的 Sparkline 使用
+fn temp_percent(t: f64, (lo, hi): (f64, f64)) -> u64 {
+    if hi <= lo {
+        return 0;
+    }
+    ((t - lo) / (hi - lo) * 100.0).clamp(0.0, 100.0) as u64
+}
+
+/// 占用率着色：>90% 红，>70% 黄，其余使用组件自己的主题色
+fn usage_color(pct: f64, accent: Color) -> Color {
+    if pct > 0.9 {
+        Color::Red
+    } else if pct > 0.7 {
+        Color::Yellow
+    } else {
+        accent
+    }
+}
+
+/// 一次获取之后到下一次获取的等待时间：成功后清零失败计数并按常规周期刷新，
+/// 失败则计数加一并按退避等待
+fn public_ip_next_wait(attempt: &mut u32, ok: bool) -> Duration {
+    if ok {
+        *attempt = 0;
+        Duration::from_secs(PUBLIC_IP_REFRESH_SECS)
+    } else {
+        *attempt += 1;
+        public_ip_backoff(*attempt)
+    }
+}
+
+/// 第 attempt 次失败后的等待时间：1s, 2s, 4s ... 封顶 PUBLIC_IP_BACKOFF_MAX_SECS
+fn public_ip_backoff(attempt: u32) -> Duration {
+    let secs = 1u64
+        .checked_shl(attempt.saturating_sub(1))
+        .unwrap_or(u64::MAX)
+        .min(PUBLIC_IP_BACKOFF_MAX_SECS);
+    Duration::from_secs(secs)
+}
+
+/// 等待下一次采集：周期到达返回 false，'r' 手动触发返回 true (不必等到下个周期)
+async fn next_pass(interval: &mut tokio::time::Interval, trigger: &tokio::sync::Notify) -> bool {
+    tokio::select! {
+        _ = interval.tick() => false,
+        _ = trigger.notified() => true,
+    }
+}
+
+/// 读不到传感器时温度为 0，不能当作真实读数参与告警
+fn known_temp(t: f32) -> Option<f64> {
+    (t > 0.0).then_some(t as f64)
+}
+
+/// termux-api 不可用时 task_collect_battery 返回 (0, "Unknown", 0.0)
+fn known_battery_pct(bat: &AndroidBatInfo) -> Option<f64> {
+    (bat.1 != BATTERY_UNKNOWN).then_some(bat.0 as f64)
+}
+
+/// disk_pct 告警只看可写的普通磁盘：只读挂载、loop 设备和 squashfs/erofs 等镜像文件系统总是满的
+fn counts_for_disk_alert(name: &str, fs: &str, read_only: bool) -> bool {
+    const IMAGE_FS: &[&str] = &["squashfs", "erofs", "iso9660", "udf", "cramfs"];
+    !read_only
+        && !name.trim_start_matches("/dev/").starts_with("loop")
+        && !IMAGE_FS.iter().any(|f| fs.eq_ignore_ascii_case(f))
+}
+
+/// 读取一个 thermal_zone 目录下的温度 (毫摄氏度 -> 摄氏度)
+fn read_zone_temp(zone: &std::path::Path) -> Option<f32> {
+    std::fs::read_to_string(zone.join("temp"))
+        .ok()
+        .and_then(|s| s.trim().parse::<f32>().ok())
+        .map(|t| t / 1000.0)
+}
+
+/// 扫描 root 下所有 thermal_zone*，取 type 含任一关键字 (不区分大小写) 的分区温度最大值；
+/// 各 SoC 的分区编号和含义差别很大，不能写死编号。没有匹配时回退到 thermal_zone0
+fn discover_cpu_temp(root: &std::path::Path, patterns: &[String]) -> f32 {
+    let patterns: Vec<String> = patterns.iter().map(|p| p.to_lowercase()).collect();
+    let matched = std::fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("thermal_zone"))
+        .filter(|e| {
+            let kind = std::fs::read_to_string(e.path().join("type"))
+                .unwrap_or_default()
+                .to_lowercase();
+            patterns.iter().any(|p| kind.contains(p.as_str()))
+        })
+        .filter_map(|e| read_zone_temp(&e.path()))
+        .fold(None, |acc: Option<f32>, t| Some(acc.map_or(t, |a| a.max(t))));
+
+    matched
+        .or_else(|| read_zone_temp(&root.join("thermal_zone0")))
+        .unwrap_or(0.0)
+}
+
+/// 网卡名是否匹配隐藏规则：含 '*' 的按简单通配符匹配，否则按前缀匹配
+fn interface_hidden(name: &str, patterns: &[String]) -> bool {
+    patterns.iter().any(|p| {
+        if !p.contains('*') {
+            return name.starts_with(p.as_str());
+        }
+        // 依次匹配 '*' 分隔的片段：首段为前缀，末段为后缀，中间按顺序出现
+        let parts: Vec<&str> = p.split('*').collect();
+        let mut rest = name;
+        for (i, part) in parts.iter().enumerate() {
+            if i == 0 {
+                match rest.strip_prefix(part) {
+                    Some(r) => rest = r,
+                    None => return false,
+                }
+            } else if i == parts.len() - 1 {
+                return rest.ends_with(part);
+            } else {
+                match rest.find(part) {
+                    Some(pos) => rest = &rest[pos + part.len()..],
+                    None => return false,
+                }
+            }
+        }
+        true
+    })
+}
+
+/// v6 地址规范化：连续的 0 段压缩为 `::` (已压缩的输入保持不变)，去掉 `%zone` 后缀；
+/// 无法解析时原样返回
+fn abbreviate_ipv6(addr: &str) -> String {
+    let bare = addr.split_once('%').map_or(addr, |(a, _)| a);
+    bare.parse::<std::net::Ipv6Addr>()
+        .map(|ip| ip.to_string())
+        .unwrap_or_else(|_| addr.to_string())
+}
+
+/// IP 条目转为行，超过 width 的条目加 DIM
+fn ip_lines(entries: &[String], width: u16) -> Vec<Line<'static>> {
+    entries
+        .iter()
+        .map(|e| {
+            let line = Line::from(e.clone());
+            if line.width() > width as usize {
+                line.add_modifier(Modifier::DIM)
+            } else {
+                line
+            }
+        })
+        .collect()
+}
+
+/// 整理一列 "name: ip" 条目 (v4 与 v6 分别调用)：
+/// dedup 把相同地址合并为一条 "eth0, eth0:1: ip"；group 按网卡排序，同一网卡的后续地址省略名称
+fn arrange_ips(entries: Vec<String>, dedup: bool, group: bool) -> Vec<String> {
+    let mut pairs: Vec<(String, String)> = entries
+        .into_iter()
+        .map(|e| match e.split_once(": ") {
+            Some((name, addr)) => (name.to_string(), addr.to_string()),
+            None => (String::new(), e),
+        })
+        .collect();
+
+    if dedup {
+        let mut merged: Vec<(String, String)> = Vec::with_capacity(pairs.len());
+        for (name, addr) in pairs {
+            match merged.iter_mut().find(|(_, a)| *a == addr) {
+                Some((names, _)) if !names.split(", ").any(|n| n == name) => {
+                    names.push_str(", ");
+                    names.push_str(&name);
+                }
+                Some(_) => {}
+                None => merged.push((name, addr)),
+            }
+        }
+        pairs = merged;
+    }
+    if group {
+        pairs.sort_by(|a, b| a.0.cmp(&b.0)); // 稳定排序，同一网卡内保持原顺序
+    }
+
+    let mut out = Vec::with_capacity(pairs.len());
+    let mut prev: Option<&str> = None;
+    for (name, addr) in &pairs {
+        out.push(match (name.is_empty(), group && prev == Some(name.as_str())) {
+            (true, _) => addr.clone(),
+            (false, true) => format!("{:w$}  {}", "", addr, w = name.chars().count()),
+            (false, false) => format!("{}: {}", name, addr),
+        });
+        prev = Some(name);
+    }
+    out
+}
+
+/// 从 from 开始按方向找下一条带路径的目录条目 (跳过标题与空行)，没有时返回 None
+fn next_dir_entry(entries: &[(String, Option<std::path::PathBuf>)], from: Option<usize>, down: bool) -> Option<usize> {
+    let is_path = |i: &usize| entries[*i].1.is_some();
+    match (from, down) {
+        (None, _) => (0..entries.len()).find(is_path),
+        (Some(i), true) => (i + 1..entries.len()).find(is_path),
+        (Some(i), false) => (0..i).rev().find(is_path),
+    }
+}
+
+/// 选中项对应的路径 (标题行为 None)
+fn selected_dir(entries: &[(String, Option<std::path::PathBuf>)], selected: Option<usize>) -> Option<&std::path::PathBuf> {
+    entries.get(selected?)?.1.as_ref()
+}
+
+/// 按 hide_interfaces 过滤 ip_list() 的结果 (条目格式为 "name: ip")
+fn filter_interfaces(ips: &IPData, patterns: &[String]) -> IPData {
+    let keep = |list: &Vec<String>| -> Vec<String> {
+        list.iter()
+            .filter(|e| {
+                let name = e.split(": ").next().unwrap_or("");
+                !interface_hidden(name, patterns)
+            })
+            .cloned()
+            .collect()
+    };
+    (keep(&ips.0), keep(&ips.1))
+}
+
+/// disk_columns 中无法识别的列名
+fn unknown_disk_columns(columns: &[String]) -> Vec<&str> {
+    columns
+        .iter()
+        .map(String::as_str)
+        .filter(|c| !DISK_COLUMNS.contains(c))
+        .collect()
+}
+
+/// 按列配置构造一行磁盘信息；颜色始终跟随占用率 (bar 与 pct 两列)
+fn disk_spans(disk: &DiskInf, columns: &[String]) -> Vec<Span<'static>> {
+    let (name, total, available, mount_point, fs) = disk;
+    let used = total.saturating_sub(*available);
+    let pct = if *total > 0 { used as f64 / *total as f64 } else { 0.0 };
+    let color = usage_color(pct, Color::Green);
+    let gb = |b: u64| b as f64 / 1024.0 / 1024.0 / 1024.0;
+
+    columns
+        .iter()
+        .filter_map(|col| {
+            Some(match col.as_str() {
+                "bar" => {
+                    // 进度条渲染
+                    let bar_width = 12;
+                    let filled = ((pct * bar_width as f64).round() as usize).min(bar_width);
+                    let bar = format!("[{}{}] ", "█".repeat(filled), "░".repeat(bar_width - filled));
+                    Span::styled(bar, Style::default().fg(color))
+                }
+                "pct" => Span::styled(format!("{:>5.1}% ", pct * 100.0), Style::default().fg(color)),
+                "total" => Span::raw(format!("{:>6.1} GB  ", gb(*total))),
+                "used" => Span::raw(format!("{:>6.1} GB used  ", gb(used))),
+                "avail" => Span::raw(format!("{:>6.1} GB free  ", gb(*available))),
+                "mount" => Span::raw(format!("{:<15}", mount_point)),
+                "fs" => Span::styled(format!(" [{}]", fs), Style::default().fg(Color::DarkGray)),
+                "name" => Span::styled(format!(" ({})", name), Style::default().fg(Color::DarkGray)),
+                _ => return None,
+            })
+        })
+        .collect()
+}
+
+/// 健康度评分的输入
+pub struct Metrics {
+    pub cpu_temp: f32,
+    pub mem_ratio: f64,
+    pub disk_ratio: f64,          // 最满的磁盘
+    pub battery_pct: Option<u8>, // 仅移动设备
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HealthLevel {
+    Good,
+    Warn,
+    Crit,
+}
+
+pub struct HealthStatus {
+    pub score: u8,
+    pub level: HealthLevel,
+    pub worst: &'static str, // 得分最低的维度
+}
+
+/// 单项得分：不超过 good 为 100，达到 bad 为 0，中间线性 (bad < good 时表示越低越差)
+fn sub_score(v: f64, good: f64, bad: f64) -> f64 {
+    ((bad - v) / (bad - good)).clamp(0.0, 1.0) * 100.0
+}
+
+/// 按权重合成 0-100 的健康度；>=70 Good，>=40 Warn，否则 Crit
+pub fn compute_health(m: &Metrics, w: &HealthWeights) -> HealthStatus {
+    let mut dims: Vec<(&'static str, f64, f64)> = vec![
+        ("temp", sub_score(m.cpu_temp as f64, 50.0, 85.0), w.cpu_temp),
+        ("memory", sub_score(m.mem_ratio, 0.70, 0.95), w.memory),
+        ("disk", sub_score(m.disk_ratio, 0.80, 0.98), w.disk),
+    ];
+    if let Some(b) = m.battery_pct {
+        dims.push(("battery", sub_score(b as f64, 30.0, 5.0), w.battery));
+    }
+    // 读不到温度 (0°C) 时不参与评分
+    if m.cpu_temp <= 0.0 {
+        dims.retain(|d| d.0 != "temp");
+    }
+
+    let total_w: f64 = dims.iter().map(|d| d.2.max(0.0)).sum();
+    let score = if total_w > 0.0 {
+        dims.iter().map(|d| d.1 * d.2.max(0.0)).sum::<f64>() / total_w
+    } else {
+        100.0
+    };
+    let worst = dims
+        .iter()
+        .filter(|d| d.1 < 100.0)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|d| d.0)
+        .unwrap_or("");
+
+    HealthStatus {
+        score: score.round() as u8,
+        level: if score >= 70.0 {
+            HealthLevel::Good
+        } else if score >= 40.0 {
+            HealthLevel::Warn
+        } else {
+            HealthLevel::Crit
+        },
+        worst,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 不启动后台任务、不读取系统信息的空组件
+    fn info() -> InfoComponent {
+        GlobIO::init_for_test();
+        Config::init_for_test();
+        InfoComponent {
+            glob_recv: GlobIO::recv(),
+            mount_points: Vec::new(),
+            disk_history: HashMap::new(),
+            dir_list: Vec::new(),
+            dir_state: ListState::default(),
+            ip_list: Default::default(),
+            public_ip: Default::default(),
+            public_ip_since: Instant::now(),
+            show_all_interfaces: false,
+            show_ip_history: false,
+            ip_history: Vec::new(),
+            host_name: "test".into(),
+            public_ptr: None,
+            focus_index: Some(0),
+            mount_scroll: 0,
+            ip_scroll: 0,
+            total_mem_swap_mb: (0, 0),
+            mem_swap_history: VecDeque::new(),
+            mem_swap_long_history: VecDeque::new(),
+            bat_history: VecDeque::new(),
+            bat_now: None,
+            cpu_info_history: VecDeque::new(),
+            cpu_info_long_history: VecDeque::new(),
+            system_info: String::new(),
+            cpu_topology: String::new(),
+            cpu_usage: 0.0,
+            self_usage: None,
+            reference: None,
+            summary_mode: false,
+            refreshing: false,
+            stats_cpu_temp: RunningStats::default(),
+            stats_mem: RunningStats::default(),
+            stats_bat: RunningStats::default(),
+        }
+    }
+
+    #[test]
+    fn maintenance_flag_is_released_on_drop_and_panic() {
+        let guard = MaintenanceGuard::acquire().expect("first acquire");
+        assert!(MaintenanceGuard::acquire().is_none());
+        drop(guard);
+
+        let result = std::panic::catch_unwind(|| {
+            let _guard = MaintenanceGuard::acquire().expect("acquire after drop");
+            panic!("maintenance failed");
+        });
+        assert!(result.is_err());
+        assert!(MaintenanceGuard::acquire().is_some());
+    }
+
+    #[test]
+    fn dir_navigation_skips_headers_and_enter_yields_path() {
+        use std::path::PathBuf;
+        let entries = vec![
+            ("--- Atlas ---".to_string(), None),
+            ("config".to_string(), Some(PathBuf::from("/cfg"))),
+            ("--- System ---".to_string(), None),
+            (String::new(), None),
+            ("home".to_string(), Some(PathBuf::from("/home/u"))),
+        ];
+        let first = next_dir_entry(&entries, None, true);
+        assert_eq!(first, Some(1));
+        let second = next_dir_entry(&entries, first, true);
+        assert_eq!(second, Some(4));
+        assert_eq!(next_dir_entry(&entries, second, true), None);
+        assert_eq!(next_dir_entry(&entries, second, false), Some(1));
+        assert_eq!(next_dir_entry(&entries, Some(1), false), None);
+
+        assert_eq!(selected_dir(&entries, second), Some(&PathBuf::from("/home/u")));
+        assert_eq!(selected_dir(&entries, Some(2)), None);
+        assert_eq!(selected_dir(&entries, None), None);
+    }
+
+    #[test]
+    fn span_label_is_samples_times_interval() {
+        assert_eq!(span_label(0, 5), "last 0s");
+        assert_eq!(span_label(10, 5), "last 50s");
+        assert_eq!(span_label(60, 5), "last 5m");
+        assert_eq!(span_label(120, 60), "last 2h00m");
+        assert_eq!(
+            span_label(HISTORY_CAP, LONG_INTERVAL),
+            format!("last {}", crate::version::format_uptime(Duration::from_secs(HISTORY_CAP as u64 * LONG_INTERVAL)))
+        );
+    }
 
+    #[test]
+    fn custom_disk_columns_render_in_order() {
+        let disk: DiskInf = ("sda1".into(), 100 << 30, 25 << 30, "/data".into(), "ext4".into());
+        let columns: Vec<String> = ["mount", "bogus", "pct", "name"].iter().map(|s| s.to_string()).collect();
+        let spans = disk_spans(&disk, &columns);
+        let text: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, [format!("{:<15}", "/data").as_str(), " 75.0% ", " (sda1)"]);
+        // 颜色只取决于使用率，与显示哪些列无关
+        assert_eq!(spans[1].style.fg, Some(usage_color(0.75, Color::Green)));
+        assert_eq!(unknown_disk_columns(&columns), ["bogus"]);
+    }
+
+    #[tokio::test]
+    async fn collect_now_runs_a_pass_outside_the_interval() {
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        let trigger = tokio::sync::Notify::new();
+        // interval 的第一次 tick 立即完成，之后一小时内不会再有周期采集
+        assert!(!next_pass(&mut interval, &trigger).await);
+        trigger.notify_one();
+        let forced = tokio::time::timeout(Duration::from_secs(1), next_pass(&mut interval, &trigger)).await;
+        assert_eq!(forced.ok(), Some(true));
+        // 没有触发时不会提前采集
+        let idle = tokio::time::timeout(Duration::from_millis(50), next_pass(&mut interval, &trigger)).await;
+        assert!(idle.is_err());
+    }
+
+    #[test]
+    fn unreadable_sensors_are_not_alert_samples() {
+        assert_eq!(known_temp(0.0), None);
+        assert_eq!(known_temp(42.5), Some(42.5));
+        assert_eq!(known_battery_pct(&(0, BATTERY_UNKNOWN.to_string(), 0.0)), None);
+        assert_eq!(known_battery_pct(&(12, "Discharging".to_string(), 30.0)), Some(12.0));
+    }
+
+    #[test]
+    fn disk_alert_skips_read_only_and_image_mounts() {
+        assert!(counts_for_disk_alert("/dev/sda1", "ext4", false));
+        assert!(counts_for_disk_alert("/dev/block/dm-5", "f2fs", false));
+        assert!(!counts_for_disk_alert("/dev/sda1", "ext4", true));
+        assert!(!counts_for_disk_alert("/dev/loop3", "ext4", false));
+        assert!(!counts_for_disk_alert("/dev/loop0", "squashfs", true));
+        assert!(!counts_for_disk_alert("/dev/block/dm-0", "erofs", false));
+    }
+
+    #[test]
+    fn public_ip_backoff_follows_mock_fetcher() {
+        // 模拟获取器：前 N 次失败，之后成功
+        let mut results = [false, false, false, false, true, false].into_iter();
+        let mut attempt = 0;
+        let waits: Vec<u64> = std::iter::from_fn(|| results.next())
+            .map(|ok| public_ip_next_wait(&mut attempt, ok).as_secs())
+            .collect();
+        assert_eq!(waits, [1, 2, 4, 8, PUBLIC_IP_REFRESH_SECS, 1]);
+        assert_eq!(attempt, 1);
+
+        // 持续失败时封顶，不会溢出
+        for n in [10, 64, u32::MAX] {
+            assert_eq!(public_ip_backoff(n).as_secs(), PUBLIC_IP_BACKOFF_MAX_SECS);
+        }
+    }
+
+    #[test]
+    fn cpu_temp_is_discovered_by_zone_type() {
+        let root = std::env::temp_dir().join(format!("atlas_thermal_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let zone = |n: u32, kind: &str, milli: &str| {
+            let dir = root.join(format!("thermal_zone{}", n));
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("type"), format!("{}\n", kind)).unwrap();
+            std::fs::write(dir.join("temp"), format!("{}\n", milli)).unwrap();
+        };
+        zone(0, "battery", "31000");
+        zone(3, "cpu-0-0-usr", "45500");
+        zone(7, "CPU-1-2-usr", "52000");
+        zone(9, "gpu-usr", "60000");
+        std::fs::create_dir_all(root.join("cooling_device0")).unwrap();
+        let patterns = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        // 不区分大小写，多个匹配取最大值，编号不连续也能找到
+        assert_eq!(discover_cpu_temp(&root, &patterns(&["cpu"])), 52.0);
+        assert_eq!(discover_cpu_temp(&root, &patterns(&["tsens", "gpu"])), 60.0);
+        // 没有匹配时回退到 thermal_zone0
+        assert_eq!(discover_cpu_temp(&root, &patterns(&["soc"])), 31.0);
+        std::fs::remove_dir_all(root.join("thermal_zone0")).unwrap();
+        assert_eq!(discover_cpu_temp(&root, &patterns(&["soc"])), 0.0);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn hidden_interfaces_are_filtered_by_prefix_and_glob() {
+        let list = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let ips: IPData = (
+            list(&["lo: 127.0.0.1", "wlan0: 192.168.1.5", "docker0: 172.17.0.1", "br-1a2b: 172.18.0.1", "veth9f: 169.254.1.1"]),
+            list(&["lo: ::1", "wlan0: fe80::1", "rmnet_data0: 2001:db8::5"]),
+        );
+        let patterns = list(&["lo", "docker", "br-*", "v*h*"]);
+        let (v4, v6) = filter_interfaces(&ips, &patterns);
+        assert_eq!(v4, ["wlan0: 192.168.1.5"]);
+        assert_eq!(v6, ["wlan0: fe80::1", "rmnet_data0: 2001:db8::5"]);
+
+        // 前缀规则不会误伤名字里中间含有该片段的网卡
+        assert!(!interface_hidden("wlo1", &list(&["lo"])));
+        assert!(interface_hidden("rmnet_data3", &list(&["*data*"])));
+        assert!(!interface_hidden("rmnet_ipa0", &list(&["*data*"])));
+        // 没有规则时全部保留
+        assert_eq!(filter_interfaces(&ips, &[]), ips);
+    }
+
+    fn record(n: u8) -> TelemetryRecord {
+        TelemetryRecord {
+            timestamp: Utc::now().to_rfc3339(),
+            cpu_data: (vec![1800.0], 40.0, 42.0),
+            mem_swap: (1024, 0),
+            battery_data: (n, "Charging".into(), 30.0),
+        }
+    }
+
+    async fn telemetry_rows() -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM telemetry")
+            .fetch_one(crate::db::Database::pool())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn queued_records_are_written_in_one_flush() {
+        let _db = crate::db::Database::init_for_test().await;
+        TelemetryRecord::init_table().await.unwrap();
+        let before = telemetry_rows().await;
+
+        let tx = TelemetryRecord::spawn_writer_every(3600);
+        for n in 0..5 {
+            assert!(tx.try_send(WriterMsg::Record(record(n))).is_ok());
+        }
+        // 不足一批且未到周期：仍在内存中
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(telemetry_rows().await, before);
+
+        let (done, ack) = tokio::sync::oneshot::channel();
+        assert!(tx.send(WriterMsg::Flush(done)).await.is_ok());
+        ack.await.unwrap();
+        assert_eq!(telemetry_rows().await, before + 5);
+    }
+
+    #[test]
+    fn health_score_blames_each_failing_dimension() {
+        let w = HealthWeights::default();
+        let healthy = || Metrics { cpu_temp: 40.0, mem_ratio: 0.5, disk_ratio: 0.5, battery_pct: Some(80) };
+        let h = compute_health(&healthy(), &w);
+        assert_eq!((h.score, h.level, h.worst), (100, HealthLevel::Good, ""));
+
+        // 每个维度单独打满，扣掉的正好是它的权重
+        let cases: [(Metrics, &str, u8); 4] = [
+            (Metrics { cpu_temp: 90.0, ..healthy() }, "temp", 70),
+            (Metrics { mem_ratio: 0.99, ..healthy() }, "memory", 70),
+            (Metrics { disk_ratio: 1.0, ..healthy() }, "disk", 75),
+            (Metrics { battery_pct: Some(3), ..healthy() }, "battery", 85),
+        ];
+        for (m, worst, score) in cases {
+            let h = compute_health(&m, &w);
+            assert_eq!((h.worst, h.score), (worst, score));
+        }
+
+        let h = compute_health(&Metrics { cpu_temp: 90.0, mem_ratio: 0.99, ..healthy() }, &w);
+        assert_eq!((h.score, h.level), (40, HealthLevel::Warn));
+        let h = compute_health(&Metrics { cpu_temp: 90.0, mem_ratio: 0.99, disk_ratio: 1.0, ..healthy() }, &w);
+        assert_eq!(h.level, HealthLevel::Crit);
+
+        // 读不到温度、没有电池时这两项不参与评分
+        let h = compute_health(&Metrics { cpu_temp: 0.0, battery_pct: None, ..healthy() }, &w);
+        assert_eq!((h.score, h.worst), (100, ""));
+    }
+
+    #[test]
+    fn summary_lines_format_latest_values() {
+        let mut comp = info();
+        comp.cpu_usage = 37.4;
+        comp.cpu_info_history.push_back((vec![1.2, 2.4], 38.0, 41.6));
+        comp.total_mem_swap_mb = (8192, 2048);
+        comp.mem_swap_history.push_back((2048, 512));
+        comp.bat_history.push_back((76, "Discharging".into(), 29.5));
+        comp.mount_points = vec![
+            ("/dev/sda1".into(), 100, 25, "/".into(), "ext4".into()),
+            ("tmpfs".into(), 0, 0, "/run".into(), "tmpfs".into()),
+        ];
+        assert_eq!(
+            comp.summary_lines(),
+            [
+                "CPU:  37% 2.4GHz 42°C",
+                "RAM:  2.0/8.0 GiB (25%)",
+                "SWAP: 0.5/2.0 GiB",
+                "BAT:  76% discharging 30°C",
+                "DISK /: 75%",
+            ]
+        );
+    }
 
+    #[test]
+    fn running_stats_track_min_max_and_mean() {
+        let mut stats = RunningStats::default();
+        assert_eq!(stats.label(), "");
+        for v in [47.0, 38.0, 62.0, 41.0, 47.0] {
+            stats.push(v);
+        }
+        assert_eq!((stats.min, stats.max, stats.count), (38.0, 62.0, 5));
+        assert!((stats.mean - 47.0).abs() < 1e-9);
+        assert_eq!(stats.label(), " min 38 / avg 47 / max 62 ");
+
+        // 增量均值在大量样本下不漂移 (历史队列早已淘汰这些样本)
+        let mut stats = RunningStats::default();
+        for i in 0..10_000 {
+            stats.push((i % 100) as f64);
+        }
+        assert!((stats.mean - 49.5).abs() < 1e-6);
+        assert_eq!((stats.min, stats.max), (0.0, 99.0));
+    }
 
+    #[test]
+    fn spaced_cpu_samples_are_plausible_percentages() {
+        let mut sys = InfoComponent::sampling_system();
+        for _ in 0..2 {
+            std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+            sys.refresh_cpu_usage();
+            let usage = sys.global_cpu_usage();
+            assert!((0.0..=100.0).contains(&usage), "cpu usage {usage}");
+        }
+        sys.refresh_memory();
+        assert!(sys.used_memory() <= sys.total_memory());
+    }
 
+    #[test]
+    fn declining_battery_gives_a_reasonable_eta() {
+        let series = |pcts: &[u8]| -> VecDeque<AndroidBatInfo> {
+            pcts.iter().map(|p| (*p, "DISCHARGING".to_string(), 30.0)).collect()
+        };
+        // 每分钟掉 1%，剩余 50% 约 50 分钟
+        let declining: Vec<u8> = (50..=69).rev().collect();
+        let eta = battery_eta_secs(&series(&declining), 60.0).unwrap();
+        assert!((eta - 3000.0).abs() < 1.0, "eta {eta}");
+
+        // 带噪声的下降仍给出同量级的估计
+        let noisy = series(&[60, 60, 58, 58, 57, 55, 55, 54, 52, 52]);
+        let eta = battery_eta_secs(&noisy, 60.0).unwrap();
+        assert!((2400.0..5400.0).contains(&eta), "eta {eta}");
+
+        // 平坦、充电、样本过少时不估计
+        assert_eq!(battery_eta_secs(&series(&[80; 10]), 60.0), None);
+        assert_eq!(battery_eta_secs(&series(&[40, 41, 42, 43]), 60.0), None);
+        assert_eq!(battery_eta_secs(&series(&[60, 59]), 60.0), None);
+
+        // 占位样本之前的历史不参与拟合
+        let mut gapped = series(&[90, 80, 70, 60]);
+        gapped.push_back((60, String::new(), 0.0));
+        gapped.extend(series(&[60, 60]));
+        assert_eq!(battery_eta_secs(&gapped, 60.0), None);
+    }
+
+    #[test]
+    fn disk_samples_fill_per_mount_history_and_trim() {
+        let disk = |mount: &str, total: u64, avail: u64| -> DiskInf {
+            ("sda".into(), total, avail, mount.into(), "ext4".into())
+        };
+        let mut history = HashMap::new();
+        for i in 0..DISK_HISTORY_CAP as u64 + 10 {
+            let disks = [disk("/", 100, 100 - i % 100), disk("/data", 200, 150), disk("/empty", 0, 0)];
+            record_disk_history(&mut history, &disks);
+        }
+        assert_eq!(history.len(), 2, "zero-sized mounts are not tracked");
+        let root = &history["/"];
+        assert_eq!(root.len(), DISK_HISTORY_CAP);
+        assert_eq!(root.front(), Some(&10));
+        assert_eq!(root.back(), Some(&(DISK_HISTORY_CAP as u8 + 9)));
+        assert!(history["/data"].iter().all(|p| *p == 25));
+
+        // 消失的挂载点被移除
+        record_disk_history(&mut history, &[disk("/data", 200, 100)]);
+        assert_eq!(history.keys().collect::<Vec<_>>(), vec!["/data"]);
+        assert_eq!(history["/data"].back(), Some(&50));
+        assert_eq!(trend_line(&history["/data"], 2), "▂▄");
+    }
+
+    #[test]
+    fn many_cores_in_a_narrow_pane_are_summarized() {
+        let freqs: Vec<f32> = (0..16).map(|i| if i < 8 { 1.2 } else { 2.4 }).collect();
+        assert_eq!(format_core_freqs(&freqs, FreqUnit::GHz, 40), "16 cores  min 1.2 / avg 1.8 / max 2.4 GHz");
+        assert_eq!(format_core_freqs(&freqs, FreqUnit::MHz, 40), "16 cores  min 1200 / avg 1800 / max 2400 MHz");
+
+        // 放得下时逐核显示
+        let per_core = format_core_freqs(&freqs, FreqUnit::GHz, 200);
+        assert_eq!(per_core.split('|').count(), 16);
+        assert!(per_core.starts_with("1.2|") && per_core.ends_with("|2.4"));
+        assert_eq!(format_core_freqs(&freqs[..2], FreqUnit::GHz, 7), "1.2|1.2");
+    }
+
+    #[test]
+    fn only_the_focused_pane_title_has_the_marker() {
+        use crate::ui::style::FOCUS_MARKER_SYMBOL;
+        let mut comp = info();
+        for (focus, title) in [(0, "Mount Points"), (1, "Directories"), (2, "IP (Left")] {
+            comp.focus_index = Some(focus);
+            let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(160, 48)).unwrap();
+            terminal.draw(|f| comp.render(f, f.area())).unwrap();
+            let buf = terminal.backend().buffer();
+            let marked: Vec<String> = (0..48)
+                .flat_map(|y| (0..160).map(move |x| (x, y)))
+                .filter(|p| buf[*p].symbol() == FOCUS_MARKER_SYMBOL)
+                .map(|(x, y)| (x..160).map(|x| buf[(x, y)].symbol()).collect())
+                .collect();
+            assert_eq!(marked.len(), 1, "focus {focus}: {marked:?}");
+            assert!(marked[0].contains(title), "focus {focus}: {}", marked[0]);
+        }
+    }
+
+    #[test]
+    fn configured_default_focus_is_applied_and_invalid_falls_back() {
+        let focus = |raw: &str| {
+            let cfg: Config = serde_json::from_str(&format!(r#"{{"info_default_focus": {}}}"#, raw)).unwrap();
+            initial_focus(&Arc::new(tokio::sync::RwLock::new(cfg)))
+        };
+        assert_eq!(focus(r#""Ips""#), Some(2));
+        assert_eq!(focus(r#""Dirs""#), Some(1));
+        assert_eq!(focus(r#""Mounts""#), Some(0));
+        for invalid in [r#""ips""#, r#""Network""#, "5", "null"] {
+            assert_eq!(focus(invalid), Some(0), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn cpu_clusters_come_from_max_freq_files() {
+        let root = std::env::temp_dir().join(format!("atlas_cpufreq_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let cores = |max_khz: &[Option<&str>]| {
+            let _ = std::fs::remove_dir_all(&root);
+            for (i, max) in max_khz.iter().enumerate() {
+                let dir = root.join(format!("cpu{}/cpufreq", i));
+                std::fs::create_dir_all(&dir).unwrap();
+                if let Some(max) = max {
+                    std::fs::write(dir.join("cpuinfo_max_freq"), format!("{}\n", max)).unwrap();
+                }
+            }
+            cpu_clusters(&root)
+        };
+
+        let little_big = [Some("1800000"), Some("1800000"), Some("2400000"), Some("1800000"), Some("2400000")];
+        assert_eq!(cores(&little_big).as_deref(), Some("3x little + 2x big"));
+        // 读不到频率的核心被跳过
+        let gapped = [Some("1000000"), None, Some("2000000"), Some("3000000")];
+        assert_eq!(cores(&gapped).as_deref(), Some("1x little + 1x mid + 1x big"));
+        let four = [Some("1000000"), Some("1500000"), Some("2000000"), Some("2500000")];
+        assert_eq!(cores(&four).as_deref(), Some("1x 1.0GHz + 1x 1.5GHz + 1x 2.0GHz + 1x 2.5GHz"));
+        // 同频或没有 sysfs 时不显示
+        assert_eq!(cores(&[Some("2400000"); 4]), None);
+        assert_eq!(cores(&[]), None);
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[tokio::test]
+    async fn only_ip_changes_are_recorded() {
+        let _db = crate::db::Database::init_for_test().await;
+        GlobIO::init_for_test();
+        Config::init_for_test();
+        IpChange::init_table().await.unwrap();
+        let rows = || async {
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM ip_history")
+                .fetch_one(crate::db::Database::pool())
+                .await
+                .unwrap()
+        };
+        let state = |entries: &[(&str, &str, &str)]| -> IpState {
+            entries.iter().map(|(k, i, a)| ((k.to_string(), i.to_string()), a.to_string())).collect()
+        };
+        let before = rows().await;
+
+        let mut tracker = IpTracker::new(&["v4", "v6"]);
+        tracker.observe(state(&[("v4", "eth0", "10.0.0.2")])).await;
+        assert_eq!(rows().await, before + 1);
+        // 没有变化的轮询不写入
+        tracker.observe(state(&[("v4", "eth0", "10.0.0.2")])).await;
+        assert_eq!(rows().await, before + 1);
+
+        tracker.observe(state(&[("v4", "eth0", "10.0.0.3"), ("v6", "wlan0", "fe80::1")])).await;
+        assert_eq!(rows().await, before + 3);
+        tracker.observe(state(&[("v4", "eth0", "10.0.0.3")])).await;
+        assert_eq!(rows().await, before + 4);
+        let latest = &IpChange::fetch_recent(1).await[0];
+        assert_eq!((latest.kind.as_str(), latest.iface.as_str(), latest.address.as_str()), ("v6", "wlan0", ""));
+
+        // 重启后从表中恢复上次的地址，相同快照不会重复记录
+        let mut restarted = IpTracker::new(&["v4", "v6"]);
+        restarted.observe(state(&[("v4", "eth0", "10.0.0.3")])).await;
+        assert_eq!(rows().await, before + 4);
+    }
+
+    #[test]
+    fn long_dir_path_wraps_and_stays_viewable() {
+        use std::path::PathBuf;
+        let long = format!("/data/data/com.termux/files/home/{}/atlas", "deeply/nested".repeat(6));
+        let mut comp = info();
+        comp.focus_index = Some(1);
+        comp.dir_list = vec![
+            ("--- Atlas ---".to_string(), None),
+            (format!("Data: {}", long), Some(PathBuf::from(&long))),
+            ("Cache: /tmp".to_string(), Some(PathBuf::from("/tmp"))),
+        ];
+        let (width, height) = (32u16, 8u16);
+        let draw = |comp: &mut InfoComponent| -> Vec<String> {
+            let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+            terminal.draw(|f| comp.render_dir_list(f, f.area())).unwrap();
+            let buf = terminal.backend().buffer();
+            // 去掉边框与选择符号 (各 1 + 3 列)
+            (1..height - 1)
+                .map(|y| (4..width - 1).map(|x| buf[(x, y)].symbol()).collect::<String>())
+                .collect()
+        };
+
+        // 折行后整条路径都在窗格内，没有被截断
+        let rows = draw(&mut comp);
+        assert!(rows.concat().contains(&format!("Data: {}", long)), "{rows:#?}");
+
+        // 选中下面的条目时列表滚动，使其可见
+        comp.dir_state.select(next_dir_entry(&comp.dir_list, comp.dir_state.selected(), true));
+        let rows = draw(&mut comp);
+        assert!(rows.iter().any(|r| r.trim_end() == "Cache: /tmp"), "{rows:#?}");
+    }
+
+    #[test]
+    fn ipv6_is_compressed_canonically() {
+        assert_eq!(abbreviate_ipv6("fe80:0000:0000:0000:0202:b3ff:fe1e:8329"), "fe80::202:b3ff:fe1e:8329");
+        assert_eq!(abbreviate_ipv6("2001:db8:0:0:1:0:0:1"), "2001:db8::1:0:0:1");
+        assert_eq!(abbreviate_ipv6("0:0:0:0:0:0:0:1"), "::1");
+        assert_eq!(abbreviate_ipv6("fe80::1%wlan0"), "fe80::1");
+        // 已压缩的输入保持不变
+        for done in ["fe80::202:b3ff:fe1e:8329", "::", "2001:db8::1"] {
+            assert_eq!(abbreviate_ipv6(done), done);
+        }
+        // 无法解析时原样返回 (包括 zone)
+        assert_eq!(abbreviate_ipv6("not-an-ip%eth0"), "not-an-ip%eth0");
+    }
+
+    #[test]
+    fn own_process_usage_is_plausible() {
+        let pid = sysinfo::get_current_pid().ok();
+        assert!(pid.is_some(), "self pid must resolve");
+        let mut sys = InfoComponent::sampling_system();
+        assert!(InfoComponent::collect_self_usage(&mut sys, None).is_none());
+
+        InfoComponent::collect_self_usage(&mut sys, pid).expect("first sample");
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        let own = InfoComponent::collect_self_usage(&mut sys, pid).expect("second sample");
+        let cores = std::thread::available_parallelism().map_or(1, |n| n.get()) as f32;
+        assert!((0.0..=100.0 * cores).contains(&own.cpu), "cpu {}", own.cpu);
+        // 测试进程本身至少占用 1MB，远小于 64GB
+        assert!((1..64 * 1024).contains(&own.rss_mb), "rss {}MB", own.rss_mb);
+        #[cfg(target_os = "linux")]
+        assert!(own.fds.is_some_and(|n| n >= 3), "fds {:?}", own.fds);
+    }
+
+    #[test]
+    fn once_json_snapshot_has_every_section() {
+        Config::init_for_test();
+        let snap = InfoComponent::snapshot_json();
+        assert!(snap["version"].as_str().is_some_and(|v| !v.is_empty()));
+        assert!(DateTime::parse_from_rfc3339(snap["timestamp"].as_str().unwrap()).is_ok());
+
+        let usage = snap["cpu"]["usage"].as_f64().unwrap();
+        assert!((0.0..=100.0).contains(&usage));
+        assert!(snap["cpu"]["freqs_ghz"].is_array());
+        let mem = &snap["memory"];
+        assert!(mem["used_mb"].as_u64().unwrap() <= mem["total_mb"].as_u64().unwrap());
+        assert!(mem["swap_used_mb"].is_u64() && mem["swap_total_mb"].is_u64());
+
+        for disk in snap["disks"].as_array().unwrap() {
+            for key in ["name", "mount", "fs"] {
+                assert!(disk[key].is_string(), "{key}: {disk}");
+            }
+            assert!(disk["available"].as_u64().unwrap() <= disk["total"].as_u64().unwrap());
+        }
+        assert!(snap["battery"]["percent"].is_u64() && snap["battery"]["state"].is_string());
+        assert!(snap["ips"]["v4"].is_array() && snap["ips"]["v6"].is_array());
+    }
+
+    #[tokio::test]
+    async fn public_ip_times_out_to_unavailable_and_p_retries() {
+        let mut comp = info();
+        comp.focus_index = Some(2);
+        let timeout = Duration::from_secs(Config::default().public_ip_timeout_secs);
+        let ip_title = |comp: &mut InfoComponent| {
+            let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(160, 6)).unwrap();
+            terminal.draw(|f| comp.render_ip_addresses(f, f.area())).unwrap();
+            let buf = terminal.backend().buffer();
+            (0..160).map(|x| buf[(x, 0)].symbol()).collect::<String>()
+        };
+
+        // 未到超时仍在等待
+        comp.public_ip_since = Instant::now() - timeout / 2;
+        assert!(!comp.update());
+        assert!(matches!(comp.public_ip, PublicIp::Pending));
+
+        comp.public_ip_since = Instant::now() - timeout;
+        assert!(comp.update());
+        assert!(matches!(comp.public_ip, PublicIp::Unavailable));
+        assert!(ip_title(&mut comp).contains("Public: Unavailable (timeout) 'p' retry"));
+
+        // 'p' 回到等待状态并唤醒后台获取任务
+        assert!(comp.handle_key(KeyEvent::from(KeyCode::Char('p'))));
+        assert!(matches!(comp.public_ip, PublicIp::Pending));
+        assert!(!comp.update());
+        let woke = tokio::time::timeout(Duration::from_millis(100), PUBLIC_IP_RETRY.notified()).await;
+        assert!(woke.is_ok(), "retry must wake the fetch task");
+    }
+
+    #[test]
+    fn duplicate_ips_are_merged_and_distinct_ones_kept() {
+        let entries = vec![
+            "eth0: 192.168.1.10".to_string(),
+            "eth0:1: 192.168.1.10".to_string(),
+            "docker0: 172.17.0.1".to_string(),
+            "eth0: 192.168.1.10".to_string(),
+            "wlan0: 10.0.0.5".to_string(),
+        ];
+
+        // 关闭时原样保留
+        assert_eq!(arrange_ips(entries.clone(), false, false), entries);
+
+        // 相同地址合并为一条，重复的网卡名不再追加，不同地址全部保留且顺序不变
+        assert_eq!(
+            arrange_ips(entries.clone(), true, false),
+            vec!["eth0, eth0:1: 192.168.1.10", "docker0: 172.17.0.1", "wlan0: 10.0.0.5"]
+        );
+
+        // 分组时同一网卡的后续地址省略名称
+        let grouped = arrange_ips(
+            vec!["eth0: 10.0.0.1".into(), "lo: 127.0.0.1".into(), "eth0: 10.0.0.2".into()],
+            true,
+            true,
+        );
+        assert_eq!(grouped, vec!["eth0: 10.0.0.1", "      10.0.0.2", "lo: 127.0.0.1"]);
+    }
+
+    #[test]
+    fn temp_range_changes_the_normalized_value() {
+        let defaults = Config::default();
+        // 同一个 35°C，区间不同映射结果不同
+        assert_eq!(temp_percent(35.0, defaults.batt_temp_range), 50);
+        assert_eq!(temp_percent(35.0, (30.0, 60.0)), 16);
+        assert_eq!(temp_percent(35.0, defaults.cpu_temp_range), 31);
+        assert_eq!(temp_percent(35.0, (30.0, 40.0)), 50);
+
+        // 超出范围截断，无效区间 (上限不大于下限) 记为 0
+        assert_eq!(temp_percent(95.0, defaults.cpu_temp_range), 100);
+        assert_eq!(temp_percent(5.0, defaults.cpu_temp_range), 0);
+        assert_eq!(temp_percent(35.0, (40.0, 40.0)), 0);
+    }
+
+    #[tokio::test]
+    async fn dry_run_previews_the_same_counts_without_touching_rows() {
+        let _db = crate::db::Database::init_for_test().await;
+        TelemetryRecord::init_table().await.unwrap();
+        TelemetryRecord::init_archive_table().await.unwrap();
+        let pool = crate::db::Database::pool();
+        let days_ago = |d: i64| (Utc::now() - chrono::Duration::days(d)).to_rfc3339();
+        let seed = |table: &'static str, days: i64| {
+            let ts = days_ago(days);
+            async move {
+                sqlx::query(&format!(
+                    "INSERT INTO {table} (timestamp, cpu_data, mem_swap, battery_data) VALUES (?, '[]', '[]', '[]')"
+                ))
+                .bind(ts)
+                .execute(crate::db::Database::pool())
+                .await
+                .unwrap();
+            }
+        };
+        // 保留 7 天、归档保留 30 天：10 天前的行只归档，40 天前的行归档后随即删除
+        for days in [10, 10, 10, 40, 40] {
+            seed("telemetry", days).await;
+        }
+        for days in [40, 40, 40, 40, 5] {
+            seed("telemetry_archive", days).await;
+        }
+        let counts = || async {
+            let live: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM telemetry").fetch_one(pool).await.unwrap();
+            let archive: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM telemetry_archive").fetch_one(pool).await.unwrap();
+            (live, archive)
+        };
+
+        let before = counts().await;
+        let preview = TelemetryRecord::maintenance(7, 30, true).await.unwrap();
+        assert_eq!(counts().await, before, "dry run must not change any rows");
+        assert!(preview.archived >= 5 && preview.pruned >= 6, "{preview:?}");
+        assert_eq!(preview.bytes_reclaimed, 0);
+
+        let real = TelemetryRecord::run_maintenance(7, 30).await.unwrap();
+        assert_eq!((real.archived, real.pruned), (preview.archived, preview.pruned));
+        let after = counts().await;
+        assert_eq!(after.0, before.0 - preview.archived as i64);
+        assert_eq!(after.1, before.1 + preview.archived as i64 - preview.pruned as i64);
+
+        sqlx::query("DELETE FROM telemetry_archive").execute(pool).await.unwrap();
+    }
+}
@@ -1,8 +1,12 @@
 pub mod app_button;
 pub mod component;
 pub mod info;
+pub mod processes;
 //pub mod sessions;
-//pub mod settings;
+pub mod settings;
 pub mod task_control;
 pub mod welcome;
 pub mod db_view;
+pub mod style;
+pub mod screen_dump;
+pub mod render_stats;
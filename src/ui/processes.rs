@@ -0,0 +1,377 @@
+use crate::{
+    config::Config,
+    constans::{PROC_REFRESH_MS, PROC_TOP_N},
+    message::{DynamicPayload, GlobalEvent},
+    prelude::{GlobIO, GlobRecv},
+    ui::component::Component,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use std::{sync::Arc, time::{Duration, Instant}};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+/// 确认弹窗通过后回传的 KillTarget
+const PROC_KILL_KEY: &str = "proc_kill";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProcSort {
+    Cpu,
+    Mem,
+}
+
+#[derive(Debug, Clone)]
+struct ProcessRow {
+    pid: u32,
+    name: String,
+    cpu: f32,
+    mem_mb: u64,
+    start_time: u64,
+}
+
+/// 确认时选中的进程：PID 可能在确认前被复用，结束前须核对启动时间和名称
+#[derive(Debug, Clone, PartialEq)]
+struct KillTarget {
+    pid: u32,
+    start_time: u64,
+    name: String,
+}
+
+/// 类 top 的进程页：按 CPU 或内存列出前 PROC_TOP_N 个进程，'K' 结束选中进程 (需确认)。
+/// 采样在 update 中进行，且只在页面可见 (最近渲染过) 时采，刷新间隔不低于 PROC_REFRESH_MS
+pub struct ProcessesComponent {
+    sys: System,
+    rows: Vec<ProcessRow>,
+    total: usize,
+    sort: ProcSort,
+    state: TableState,
+    last_refresh: Option<Instant>,
+    // 最近一次渲染的时间，用于判断页面是否可见
+    last_render: Option<Instant>,
+    glob_recv: GlobRecv,
+}
+
+impl Component for ProcessesComponent {
+    fn init() -> Self {
+        Self {
+            sys: System::new(),
+            rows: Vec::new(),
+            total: 0,
+            sort: ProcSort::Cpu,
+            state: TableState::default(),
+            last_refresh: None,
+            last_render: None,
+            glob_recv: GlobIO::recv(),
+        }
+    }
+
+    // 确认弹窗通过时本页不一定在前台
+    fn drain(&mut self) -> bool {
+        while let Some(event) = GlobIO::try_recv(&mut self.glob_recv) {
+            if let GlobalEvent::Data { key, data } = event
+                && key == PROC_KILL_KEY
+                && let Some(target) = data.0.downcast_ref::<KillTarget>()
+            {
+                self.kill(target);
+            }
+        }
+        false
+    }
+
+    fn update(&mut self) -> bool {
+        // 后台时不采样 (系统调用不放在 render 中)
+        if self.visible() && self.refresh_due() {
+            self.refresh();
+            return true;
+        }
+        false
+    }
+
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        self.last_render = Some(Instant::now());
+        let chunks = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(area);
+
+        let sort_mark = |s: ProcSort, label: &str| {
+            if self.sort == s { format!("{} ▼", label) } else { label.to_string() }
+        };
+        let header = Row::new(vec![
+            "PID".to_string(),
+            "Name".to_string(),
+            sort_mark(ProcSort::Cpu, "CPU%"),
+            sort_mark(ProcSort::Mem, "Mem MB"),
+        ])
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+        .bottom_margin(1);
+        let rows = self.rows.iter().map(|p| {
+            Row::new(vec![
+                Cell::from(p.pid.to_string()),
+                Cell::from(p.name.clone()),
+                Cell::from(Line::from(format!("{:.1}", p.cpu)).alignment(Alignment::Right)),
+                Cell::from(Line::from(p.mem_mb.to_string()).alignment(Alignment::Right)),
+            ])
+        });
+
+        let symbol = crate::ui::style::selection_symbol();
+        let title = format!(" ⚡ Processes (top {} of {}) ", self.rows.len(), self.total);
+        let table = Table::new(
+            rows,
+            [Constraint::Length(8), Constraint::Min(16), Constraint::Length(8), Constraint::Length(9)],
+        )
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .row_highlight_style(crate::ui::style::selection_style())
+        .highlight_symbol(symbol.as_str());
+        f.render_stateful_widget(table, chunks[0], &mut self.state);
+
+        f.render_widget(
+            Paragraph::new(if Config::safe_mode() {
+                " ↑↓ to move | s to sort by CPU/Mem | r to refresh | 🔒 safe mode: kill disabled "
+            } else {
+                " ↑↓ to move | s to sort by CPU/Mem | r to refresh | K to kill selected "
+            })
+                .style(Style::default().fg(Color::DarkGray)),
+            chunks[1],
+        );
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let len = self.rows.len();
+        let current = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Down if len > 0 => self.state.select(Some((current + 1) % len)),
+            KeyCode::Up if len > 0 => self.state.select(Some(current.checked_sub(1).unwrap_or(len - 1))),
+            KeyCode::Char('s') => {
+                self.sort = match self.sort {
+                    ProcSort::Cpu => ProcSort::Mem,
+                    ProcSort::Mem => ProcSort::Cpu,
+                };
+                // 只对已截断的前 N 行重排会漏掉另一维度的大户，按已有样本重新取前 N
+                self.recollect();
+            }
+            KeyCode::Char('r') => self.last_refresh = None,
+            // 结束进程属于破坏性操作，经 App 统一确认
+            KeyCode::Char('K') => {
+                if Config::safe_mode() {
+                    GlobIO::warn("Safe mode: killing processes is disabled");
+                    return true;
+                }
+                let Some(p) = self.state.selected().and_then(|i| self.rows.get(i)) else {
+                    return false;
+                };
+                let target = KillTarget { pid: p.pid, start_time: p.start_time, name: p.name.clone() };
+                GlobIO::confirm(
+                    format!("Kill process {} ({})?", p.pid, p.name),
+                    GlobalEvent::Data { key: PROC_KILL_KEY, data: DynamicPayload(Arc::new(target)) },
+                );
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn desired_tick_interval(&self) -> Option<Duration> {
+        Some(Duration::from_millis(PROC_REFRESH_MS))
+    }
+}
+
+impl ProcessesComponent {
+    /// App 只渲染当前页，两个刷新周期内渲染过即视为可见
+    fn visible(&self) -> bool {
+        self.last_render
+            .is_some_and(|t| t.elapsed() < Duration::from_millis(PROC_REFRESH_MS * 2))
+    }
+
+    fn refresh_due(&self) -> bool {
+        self.last_refresh
+            .is_none_or(|t| t.elapsed() >= Duration::from_millis(PROC_REFRESH_MS))
+    }
+
+    /// CPU% 是两次刷新之间的差值，第一次采样时全部为 0
+    fn refresh(&mut self) {
+        self.sys.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+        self.last_refresh = Some(Instant::now());
+        self.recollect();
+    }
+
+    /// 按当前排序从最近一次样本中重新取前 N 个进程 (不采样)
+    fn recollect(&mut self) {
+        let selected_pid = self.state.selected().and_then(|i| self.rows.get(i)).map(|p| p.pid);
+        let (rows, total) = collect_processes(&self.sys, self.sort, PROC_TOP_N);
+        self.rows = rows;
+        self.total = total;
+        // 刷新后尽量保持选中同一个进程
+        let idx = selected_pid
+            .and_then(|pid| self.rows.iter().position(|p| p.pid == pid))
+            .or(if self.rows.is_empty() { None } else { Some(0) });
+        self.state.select(idx);
+    }
+
+    fn kill(&mut self, target: &KillTarget) {
+        // 确认弹窗可能在进入安全模式前发出，执行时再检查一次
+        if Config::safe_mode() {
+            GlobIO::warn("Safe mode: killing processes is disabled");
+            return;
+        }
+        let pid = target.pid;
+        self.sys.refresh_processes(ProcessesToUpdate::Some(&[Pid::from_u32(pid)]), true);
+        let Some(p) = self.sys.process(Pid::from_u32(pid)) else {
+            GlobIO::warn(format!("Process {} no longer exists", pid));
+            return;
+        };
+        if !is_same_process(p, target) {
+            GlobIO::warn(format!("PID {} now belongs to another process, not killed", pid));
+            self.last_refresh = None;
+            return;
+        }
+        // 先尝试 SIGTERM 让进程自行清理，平台不支持时退回默认的 kill
+        let sent = p.kill_with(sysinfo::Signal::Term).unwrap_or_else(|| p.kill());
+        if sent {
+            GlobIO::success(format!("Sent kill signal to {}", pid));
+        } else {
+            GlobIO::error(format!("Failed to kill {} (permission denied?)", pid));
+        }
+        self.last_refresh = None;
+    }
+}
+
+/// 返回 (排序后的前 n 个进程, 进程总数)
+fn collect_processes(sys: &System, sort: ProcSort, n: usize) -> (Vec<ProcessRow>, usize) {
+    let mut rows: Vec<ProcessRow> = sys
+        .processes()
+        .iter()
+        .map(|(pid, p)| ProcessRow {
+            pid: pid.as_u32(),
+            name: p.name().to_string_lossy().into_owned(),
+            cpu: p.cpu_usage(),
+            mem_mb: p.memory() / 1024 / 1024,
+            start_time: p.start_time(),
+        })
+        .collect();
+    let total = rows.len();
+    sort_rows(&mut rows, sort);
+    rows.truncate(n);
+    (rows, total)
+}
+
+/// 确认期间进程退出且 PID 被复用时，启动时间或名称会不同
+fn is_same_process(p: &sysinfo::Process, target: &KillTarget) -> bool {
+    p.start_time() == target.start_time && p.name().to_string_lossy() == target.name
+}
+
+fn sort_rows(rows: &mut [ProcessRow], sort: ProcSort) {
+    match sort {
+        ProcSort::Cpu => rows.sort_by(|a, b| b.cpu.total_cmp(&a.cpu).then(b.mem_mb.cmp(&a.mem_mb))),
+        ProcSort::Mem => rows.sort_by(|a, b| b.mem_mb.cmp(&a.mem_mb).then(b.cpu.total_cmp(&a.cpu))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(pid: u32, cpu: f32, mem_mb: u64) -> ProcessRow {
+        ProcessRow { pid, name: format!("p{}", pid), cpu, mem_mb, start_time: 0 }
+    }
+
+    fn sampled_system() -> System {
+        let mut sys = System::new();
+        sys.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+        sys
+    }
+
+    #[test]
+    fn rows_sort_by_selected_column_with_tiebreak() {
+        let mut rows = vec![row(1, 5.0, 10), row(2, 50.0, 1), row(3, 5.0, 300)];
+        sort_rows(&mut rows, ProcSort::Cpu);
+        assert_eq!(rows.iter().map(|r| r.pid).collect::<Vec<_>>(), [2, 3, 1]);
+        sort_rows(&mut rows, ProcSort::Mem);
+        assert_eq!(rows.iter().map(|r| r.pid).collect::<Vec<_>>(), [3, 1, 2]);
+    }
+
+    #[test]
+    fn collection_is_sorted_and_capped() {
+        let sys = sampled_system();
+        let (rows, total) = collect_processes(&sys, ProcSort::Mem, 5);
+        assert!(!rows.is_empty() && rows.len() <= 5);
+        assert!(total >= rows.len());
+        assert!(rows.windows(2).all(|w| w[0].mem_mb >= w[1].mem_mb));
+    }
+
+    #[test]
+    fn reused_pid_is_not_the_confirmed_process() {
+        let sys = sampled_system();
+        let me = sys.process(Pid::from_u32(std::process::id())).expect("own process");
+        let target = KillTarget {
+            pid: std::process::id(),
+            start_time: me.start_time(),
+            name: me.name().to_string_lossy().into_owned(),
+        };
+        assert!(is_same_process(me, &target));
+        assert!(!is_same_process(me, &KillTarget { start_time: target.start_time + 1, ..target.clone() }));
+        assert!(!is_same_process(me, &KillTarget { name: "other".into(), ..target }));
+    }
+
+    fn confirms_for(rx: &mut GlobRecv, pid: u32) -> Vec<KillTarget> {
+        let mut asked = Vec::new();
+        while let Some(ev) = GlobIO::try_recv(rx) {
+            if let GlobalEvent::Confirm(req) = ev
+                && let GlobalEvent::Data { key: PROC_KILL_KEY, data } = *req.action
+                && let Some(t) = data.0.downcast_ref::<KillTarget>()
+                && t.pid == pid
+            {
+                asked.push(t.clone());
+            }
+        }
+        asked
+    }
+
+    #[test]
+    fn safe_mode_blocks_kill() {
+        GlobIO::init_for_test();
+        Config::set_safe_mode_for_test(true);
+        let mut rx = GlobIO::recv();
+        let mut comp = ProcessesComponent::init();
+        let pid = std::process::id();
+        comp.rows = vec![row(pid, 0.0, 0)];
+        comp.state.select(Some(0));
+        comp.handle_key(KeyEvent::from(KeyCode::Char('K')));
+        assert!(confirms_for(&mut rx, pid).is_empty());
+
+        // 已发出的确认在安全模式下也不执行
+        let mut child = std::process::Command::new("sleep").arg("5").spawn().expect("spawn sleep");
+        let sys = sampled_system();
+        let p = sys.process(Pid::from_u32(child.id())).expect("child process");
+        let target = KillTarget {
+            pid: child.id(),
+            start_time: p.start_time(),
+            name: p.name().to_string_lossy().into_owned(),
+        };
+        comp.kill(&target);
+        std::thread::sleep(Duration::from_millis(100));
+        let survived = child.try_wait().expect("try_wait").is_none();
+        let _ = child.kill();
+        let _ = child.wait();
+        assert!(survived, "safe mode must not signal the process");
+    }
+
+    #[test]
+    fn kill_key_only_asks_for_confirmation() {
+        GlobIO::init_for_test();
+        let mut rx = GlobIO::recv();
+        let mut comp = ProcessesComponent::init();
+        let pid = std::process::id();
+        comp.rows = vec![ProcessRow { start_time: 42, ..row(pid, 0.0, 0) }];
+        comp.state.select(Some(0));
+        assert!(comp.handle_key(KeyEvent::from(KeyCode::Char('K'))));
+
+        let target = confirms_for(&mut rx, pid).pop().expect("'K' must go through the confirmation dialog");
+        assert_eq!((target.start_time, target.name.as_str()), (42, format!("p{}", pid).as_str()));
+    }
+}
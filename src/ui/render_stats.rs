@@ -0,0 +1,158 @@
+//! F12 渲染诊断浮层：帧率、每帧实际变化的单元格/字节数、消息总线状况，以及各部分 render 的耗时。
+//! 用来找出每帧重建大量字符串的组件 (ratatui 的 diff 只省终端输出，省不了这部分 CPU)
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::prelude::GlobIO;
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+/// 单个部分的渲染耗时：最近一次、累计平均、最大值
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderCost {
+    pub last: Duration,
+    pub max: Duration,
+    total: Duration,
+    samples: u32,
+}
+
+impl RenderCost {
+    pub fn push(&mut self, d: Duration) {
+        self.last = d;
+        self.max = self.max.max(d);
+        self.total += d;
+        self.samples += 1;
+    }
+
+    pub fn avg(&self) -> Duration {
+        if self.samples == 0 { Duration::ZERO } else { self.total / self.samples }
+    }
+}
+
+#[derive(Default)]
+pub struct RenderStats {
+    // 按首次出现的顺序保存，浮层中顺序稳定
+    costs: Vec<(&'static str, RenderCost)>,
+    frames: VecDeque<Instant>, // 最近 1 秒内的帧
+    changed_cells: usize,
+    changed_bytes: usize,
+    prev: Option<Buffer>,
+}
+
+impl RenderStats {
+    /// 计时执行 f，并累计到 name 对应的条目
+    pub fn time<R>(&mut self, name: &'static str, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let out = f();
+        self.record(name, start.elapsed());
+        out
+    }
+
+    pub fn record(&mut self, name: &'static str, d: Duration) {
+        match self.costs.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, cost)) => cost.push(d),
+            None => {
+                let mut cost = RenderCost::default();
+                cost.push(d);
+                self.costs.push((name, cost));
+            }
+        }
+    }
+
+    /// 帧画完后调用：与上一帧比较统计变化量 (尺寸变化时视为整屏重绘)
+    pub fn record_frame(&mut self, buf: &Buffer) {
+        let now = Instant::now();
+        self.frames.push_back(now);
+        while self.frames.front().is_some_and(|t| now.duration_since(*t) > Duration::from_secs(1)) {
+            self.frames.pop_front();
+        }
+
+        let (cells, bytes) = match &self.prev {
+            Some(prev) if prev.area == buf.area => prev
+                .diff(buf)
+                .iter()
+                .fold((0, 0), |(c, b), (_, _, cell)| (c + 1, b + cell.symbol().len())),
+            _ => (buf.content.len(), buf.content.iter().map(|c| c.symbol().len()).sum()),
+        };
+        self.changed_cells = cells;
+        self.changed_bytes = bytes;
+        match &mut self.prev {
+            Some(prev) if prev.area == buf.area => prev.clone_from(buf),
+            prev => *prev = Some(buf.clone()),
+        }
+    }
+
+    /// 关闭浮层时清空，重新打开从零开始统计
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn lines(&self) -> Vec<Line<'static>> {
+        let ms = |d: Duration| format!("{:.2}", d.as_secs_f64() * 1000.0);
+        let bus = GlobIO::stats();
+        let mut lines = vec![
+            Line::from(format!(
+                " {} fps | Δ {} cells, {} B ",
+                self.frames.len(),
+                self.changed_cells,
+                self.changed_bytes
+            )),
+            // lagged 增长说明有订阅者消费太慢，消息被丢弃
+            Line::from(Span::styled(
+                format!(" bus: {} rx | cap {} | lagged {} ", bus.receivers, bus.capacity, bus.lagged),
+                Style::default().fg(if bus.lagged > 0 { Color::Yellow } else { Color::Reset }),
+            )),
+            Line::from(Span::styled(
+                format!(" {:<12}{:>7}{:>7}{:>7}", "ms", "last", "avg", "max"),
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        lines.extend(self.costs.iter().map(|(name, c)| {
+            Line::from(format!(" {:<12}{:>7}{:>7}{:>7}", name, ms(c.last), ms(c.avg()), ms(c.max)))
+        }));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn costs_accumulate_per_section_in_first_seen_order() {
+        let mut stats = RenderStats::default();
+        stats.record("tabs", Duration::from_millis(2));
+        stats.record("body", Duration::from_millis(10));
+        stats.record("tabs", Duration::from_millis(6));
+        stats.record("tabs", Duration::from_millis(1));
+
+        let names: Vec<&str> = stats.costs.iter().map(|(n, _)| *n).collect();
+        assert_eq!(names, ["tabs", "body"]);
+        let tabs = stats.costs[0].1;
+        assert_eq!(tabs.last, Duration::from_millis(1));
+        assert_eq!(tabs.max, Duration::from_millis(6));
+        assert_eq!(tabs.avg(), Duration::from_millis(3));
+        assert_eq!(RenderCost::default().avg(), Duration::ZERO);
+
+        let out = stats.time("body", || 7);
+        assert_eq!(out, 7);
+        assert_eq!(stats.costs[1].1.samples, 2);
+    }
+
+    #[test]
+    fn overlay_shows_bus_stats_and_one_row_per_section() {
+        let mut stats = RenderStats::default();
+        stats.record("tabs", Duration::from_millis(1));
+        let text: Vec<String> = stats
+            .lines()
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert!(text.iter().any(|l| l.contains("bus:") && l.contains("lagged")));
+        assert_eq!(text.iter().filter(|l| l.trim_start().starts_with("tabs")).count(), 1);
+    }
+}
@@ -0,0 +1,157 @@
+use std::{fmt::Write as _, fs, io, path::PathBuf};
+
+use ratatui::{
+    buffer::{Buffer, Cell},
+    style::{Color, Modifier},
+    text::Span,
+};
+
+use crate::{constans::SCREEN_DUMP_DIR, prelude::AtlasPath};
+
+/// 一行中实际显示的单元格：宽字符后面被它覆盖的单元格 (内容为空格) 要跳过，
+/// 否则每个 CJK 字符/emoji 后面都会多出一个空格
+fn visible_cells(buf: &Buffer, y: u16) -> impl Iterator<Item = &Cell> {
+    let area = buf.area;
+    let mut covered = 0;
+    (area.left()..area.right()).filter_map(move |x| {
+        let cell = buf.cell((x, y))?;
+        if covered > 0 {
+            covered -= 1;
+            return None;
+        }
+        covered = Span::raw(cell.symbol()).width().saturating_sub(1);
+        Some(cell)
+    })
+}
+
+/// 纯文本：逐行拼接单元格字符，忽略颜色，去掉每行行尾空白
+pub fn buffer_to_text(buf: &Buffer) -> String {
+    let area = buf.area;
+    let mut out = String::with_capacity(area.area() as usize);
+    for y in area.top()..area.bottom() {
+        let line: String = visible_cells(buf, y).map(|c| c.symbol()).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// 带 ANSI SGR 转义的版本 (.ans)，`cat` 到终端即可还原颜色
+pub fn buffer_to_ansi(buf: &Buffer) -> String {
+    let area = buf.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut last = None;
+        for cell in visible_cells(buf, y) {
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if last != Some(style) {
+                out.push_str(&sgr(cell.fg, cell.bg, cell.modifier));
+                last = Some(style);
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// 写入 <数据目录>/reports/screen-<时间>.txt (及 .ans)，返回 .txt 路径
+pub fn dump(buf: &Buffer, with_ansi: bool) -> io::Result<PathBuf> {
+    let dir = AtlasPath::get().proj_dir.join(SCREEN_DUMP_DIR);
+    fs::create_dir_all(&dir)?;
+    let stem = format!("screen-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let txt = dir.join(format!("{}.txt", stem));
+    fs::write(&txt, buffer_to_text(buf))?;
+    if with_ansi {
+        fs::write(dir.join(format!("{}.ans", stem)), buffer_to_ansi(buf))?;
+    }
+    Ok(txt)
+}
+
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    let mut s = String::from("\x1b[0");
+    if modifier.contains(Modifier::BOLD) {
+        s.push_str(";1");
+    }
+    if modifier.contains(Modifier::DIM) {
+        s.push_str(";2");
+    }
+    if modifier.contains(Modifier::ITALIC) {
+        s.push_str(";3");
+    }
+    if modifier.contains(Modifier::UNDERLINED) {
+        s.push_str(";4");
+    }
+    if modifier.contains(Modifier::REVERSED) {
+        s.push_str(";7");
+    }
+    push_color(&mut s, fg, 30);
+    push_color(&mut s, bg, 40);
+    s.push('m');
+    s
+}
+
+/// base: 前景 30 / 背景 40
+fn push_color(s: &mut String, color: Color, base: u8) {
+    let named = |n: u8| Some(base + n);
+    let bright = |n: u8| Some(base + 60 + n);
+    let code = match color {
+        Color::Reset => None,
+        Color::Black => named(0),
+        Color::Red => named(1),
+        Color::Green => named(2),
+        Color::Yellow => named(3),
+        Color::Blue => named(4),
+        Color::Magenta => named(5),
+        Color::Cyan => named(6),
+        Color::Gray => named(7),
+        Color::DarkGray => bright(0),
+        Color::LightRed => bright(1),
+        Color::LightGreen => bright(2),
+        Color::LightYellow => bright(3),
+        Color::LightBlue => bright(4),
+        Color::LightMagenta => bright(5),
+        Color::LightCyan => bright(6),
+        Color::White => bright(7),
+        Color::Indexed(i) => {
+            let _ = write!(s, ";{};5;{}", base + 8, i);
+            None
+        }
+        Color::Rgb(r, g, b) => {
+            let _ = write!(s, ";{};2;{};{};{}", base + 8, r, g, b);
+            None
+        }
+    };
+    if let Some(code) = code {
+        let _ = write!(s, ";{}", code);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{
+        layout::Rect,
+        style::Style,
+        text::Line,
+        widgets::{Block, Borders, Paragraph, Widget},
+    };
+
+    #[test]
+    fn text_dump_matches_rendered_cells() {
+        // 缓冲区比组件宽两列，确认行尾空白被去掉
+        let mut buf = Buffer::empty(Rect::new(0, 0, 14, 4));
+        let lines = vec![Line::styled("ok 中文", Style::default().red().bold()), Line::from("x")];
+        let block = Block::default().borders(Borders::ALL).title("Log");
+        Paragraph::new(lines).block(block).render(Rect::new(0, 0, 12, 4), &mut buf);
+
+        assert_eq!(
+            buffer_to_text(&buf),
+            "┌Log───────┐\n│ok 中文   │\n│x         │\n└──────────┘\n"
+        );
+
+        let ansi = buffer_to_ansi(&buf);
+        assert!(ansi.contains("\x1b[0;1;31mok"), "{ansi:?}");
+        assert_eq!(ansi.matches("\x1b[0m\n").count(), 4);
+    }
+}
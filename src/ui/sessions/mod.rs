@@ -38,11 +38,9 @@ impl SessionsComponent {
 }
 
 impl Component for SessionsComponent {
-    fn update(&mut self) -> bool {
-        //由于 SessionsComponent 包含嵌套的子组件（SubSession），它也必须负责向下传递 update。
+    // 1. 先尝试接收自己的广播（如果有）
+    fn drain(&mut self) -> bool {
         let mut changed = false;
-
-        // 1. 先尝试接收自己的广播（如果有）
         while let Ok(event) = self.event_rx.try_recv() {
             if let GlobalEvent::SyncProgress(ProgressType::Percentage(p)) = event {
                 // 将进度同步给所有嵌套的子组件
@@ -54,6 +52,12 @@ impl Component for SessionsComponent {
                 changed = true; // 开启时全速渲染进度条，关闭时0.5s渲染进度条
             }
         }
+        changed
+    }
+
+    fn update(&mut self) -> bool {
+        //由于 SessionsComponent 包含嵌套的子组件（SubSession），它也必须负责向下传递 update。
+        let mut changed = false;
 
         // 2. 驱动当前子组件的内部 update (处理它的 MPSC 通道)
         if let Some(sub) = self.sub_sessions[self.primary_index].get_mut(self.secondary_index) {
@@ -99,8 +99,11 @@ impl Component for SubSession {
                 .iter()
                 .map(|i| ListItem::new(i.as_str()))
                 .collect();
+            let symbol = crate::ui::style::selection_symbol();
             f.render_stateful_widget(
-                List::new(items).highlight_symbol(">> "),
+                List::new(items)
+                    .highlight_style(crate::ui::style::selection_style())
+                    .highlight_symbol(symbol.as_str()),
                 chunks[0],
                 &mut self.state,
             );
@@ -0,0 +1,433 @@
+use crate::{
+    config::{Config, SharedConfig},
+    constans::CONFIG_RELOADED,
+    message::GlobalEvent,
+    prelude::{GlobIO, GlobRecv},
+    ui::component::Component,
+};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{prelude::*, widgets::*};
+use serde_json::{Map, Value};
+
+/// 枚举类型字段的可选值 (按字段名)，其余字符串字段按文本编辑
+const ENUM_OPTIONS: &[(&str, &[&str])] = &[
+    ("background_color", APP_COLORS),
+    ("theme_color", APP_COLORS),
+    ("stderr_color", APP_COLORS),
+    ("ipv4_color", APP_COLORS),
+    ("ipv6_color", APP_COLORS),
+    ("info_default_focus", &["Mounts", "Dirs", "Ips"]),
+    ("disk_sort", &["Auto", "Size", "Mount", "Usage"]),
+    ("mem_widget_style", &["Sparkline", "Gauge"]),
+    ("sparkline_style", &["Blocks", "Braille", "Ascii"]),
+    ("idle_screen", &["Dim", "Clock"]),
+    ("freq_unit", &["GHz", "MHz"]),
+    ("on_small_terminal", &["Warn", "Compact"]),
+    ("crash_backtrace", &["None", "Short", "Full"]),
+    ("on_readonly_config", &["Session", "Override"]),
+];
+/// 字段名包含这些片段的视为密钥：不在表单中显示 (也就不会出现在 Ctrl+S 截屏里)，只能在配置文件中修改
+const SECRET_MARKERS: &[&str] = &["token", "secret", "password"];
+const APP_COLORS: &[&str] = &["Black", "Red", "Green", "Yellow", "Blue", "Magenta", "Cyan", "White"];
+
+/// 表单中一行设置的编辑方式，由当前值的 JSON 类型推断
+#[derive(Debug, Clone, PartialEq)]
+enum FieldKind {
+    Bool,
+    Number,
+    Enum(&'static [&'static str]),
+    Text, // 字符串或未设置的 Option (null)
+}
+
+#[derive(Debug, Clone)]
+struct SettingRow {
+    name: String,
+    value: Value,
+    kind: FieldKind,
+}
+
+/// 编辑中的一行：buffer 为输入内容，枚举为当前选中的下标
+struct EditState {
+    buffer: String,
+    choice: usize,
+}
+
+/// 设置页：把 Config 的标量字段列成表单，↑↓ 选择、Enter 编辑，修改后立即保存。
+/// 数组/对象类型的字段 (标签顺序、权重等) 仍需在配置文件中编辑
+pub struct SettingsComponent {
+    config: SharedConfig,
+    rows: Vec<SettingRow>,
+    state: TableState,
+    editing: Option<EditState>,
+    glob_recv: GlobRecv,
+    reload_pending: bool, // 编辑期间收到的热加载，结束编辑后再重建
+}
+
+impl Component for SettingsComponent {
+    fn init() -> Self {
+        let mut component = Self {
+            config: Config::get(),
+            rows: Vec::new(),
+            state: TableState::default(),
+            editing: None,
+            glob_recv: GlobIO::recv(),
+            reload_pending: false,
+        };
+        component.reload_rows();
+        component
+    }
+
+    fn drain(&mut self) -> bool {
+        while let Some(event) = GlobIO::try_recv(&mut self.glob_recv) {
+            if let GlobalEvent::Data { key, .. } = event
+                && key == CONFIG_RELOADED
+            {
+                self.reload_pending = true;
+            }
+        }
+        false
+    }
+
+    /// 只在配置热加载后重建表单 (本页自己的修改在 apply 中已重建)；编辑中推迟，以免覆盖输入
+    fn update(&mut self) -> bool {
+        if !self.reload_pending || self.editing.is_some() {
+            return false;
+        }
+        self.reload_pending = false;
+        self.reload_rows();
+        true
+    }
+
+
+    fn render(&mut self, f: &mut Frame, area: Rect) {
+        let chunks = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).split(area);
+        let selected = self.state.selected();
+
+        let header = Row::new(["Setting", "Value"])
+            .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))
+            .bottom_margin(1);
+        let rows = self.rows.iter().enumerate().map(|(i, r)| {
+            let value = match (&self.editing, selected == Some(i)) {
+                (Some(e), true) => match r.kind {
+                    FieldKind::Enum(opts) => format!("◀ {} ▶", opts[e.choice]),
+                    _ => format!("{}▏", e.buffer),
+                },
+                _ => display_value(&r.value),
+            };
+            let value_style = match r.kind {
+                FieldKind::Bool => Style::default().fg(Color::Green),
+                FieldKind::Number => Style::default().fg(Color::Magenta),
+                FieldKind::Enum(_) => Style::default().fg(Color::Cyan),
+                FieldKind::Text => Style::default(),
+            };
+            Row::new(vec![Cell::from(r.name.clone()), Cell::from(value).style(value_style)])
+        });
+
+        let title = if self.editing.is_some() { " ⚙️ Settings [editing] " } else { " ⚙️ Settings " };
+        let symbol = crate::ui::style::selection_symbol();
+        let table = Table::new(rows, [Constraint::Percentage(40), Constraint::Percentage(60)])
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .row_highlight_style(crate::ui::style::selection_style())
+            .highlight_symbol(symbol.as_str());
+        f.render_stateful_widget(table, chunks[0], &mut self.state);
+
+        let hint = match self.editing {
+            Some(_) => " Enter to save | Esc to cancel | ←→ or +/- to change ",
+            None => " ↑↓ to move | Enter to edit (toggle for on/off) | changes are saved immediately ",
+        };
+        f.render_widget(Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)), chunks[1]);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        if self.editing.is_some() {
+            return self.handle_edit_keys(key);
+        }
+        let len = self.rows.len();
+        if len == 0 {
+            return false;
+        }
+        let current = self.state.selected().unwrap_or(0);
+        match key.code {
+            KeyCode::Down => {
+                self.state.select(Some((current + 1) % len));
+                true
+            }
+            KeyCode::Up => {
+                self.state.select(Some(current.checked_sub(1).unwrap_or(len - 1)));
+                true
+            }
+            KeyCode::Enter => {
+                self.begin_edit(current);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+impl SettingsComponent {
+    /// 从当前配置重新生成表单行，只保留标量字段
+    fn reload_rows(&mut self) {
+        let value = self
+            .config
+            .try_read()
+            .ok()
+            .and_then(|cfg| serde_json::to_value(&*cfg).ok());
+        let Some(Value::Object(map)) = value else {
+            return;
+        };
+        self.rows = setting_rows(&map);
+        if self.state.selected().is_none() && !self.rows.is_empty() {
+            self.state.select(Some(0));
+        }
+    }
+
+    fn begin_edit(&mut self, idx: usize) {
+        let Some(row) = self.rows.get(idx) else {
+            return;
+        };
+        match row.kind {
+            // 开关类直接切换，无需进入编辑模式
+            FieldKind::Bool => {
+                let toggled = Value::Bool(!row.value.as_bool().unwrap_or(false));
+                self.apply(idx, toggled);
+            }
+            FieldKind::Enum(opts) => {
+                let current = row.value.as_str().unwrap_or_default();
+                let choice = opts.iter().position(|o| *o == current).unwrap_or(0);
+                self.editing = Some(EditState { buffer: String::new(), choice });
+            }
+            FieldKind::Number | FieldKind::Text => {
+                let buffer = match &row.value {
+                    Value::String(s) => s.clone(),
+                    Value::Null => String::new(),
+                    v => v.to_string(),
+                };
+                self.editing = Some(EditState { buffer, choice: 0 });
+            }
+        }
+    }
+
+    fn handle_edit_keys(&mut self, key: KeyEvent) -> bool {
+        let Some(idx) = self.state.selected() else {
+            self.editing = None;
+            return true;
+        };
+        let Some(kind) = self.rows.get(idx).map(|r| r.kind.clone()) else {
+            self.editing = None;
+            return true;
+        };
+        let Some(edit) = self.editing.as_mut() else {
+            return false;
+        };
+        match (key.code, &kind) {
+            (KeyCode::Esc, _) => self.editing = None,
+            (KeyCode::Enter, _) => {
+                let value = match kind {
+                    FieldKind::Enum(opts) => Value::String(opts[edit.choice].to_string()),
+                    FieldKind::Number => match edit.buffer.trim().parse::<serde_json::Number>() {
+                        Ok(n) => Value::Number(n),
+                        Err(_) => {
+                            GlobIO::warn(format!("'{}' is not a number", edit.buffer));
+                            return true;
+                        }
+                    },
+                    // 空输入表示清除可选项 (Option -> null)
+                    _ if edit.buffer.is_empty() && self.rows[idx].value.is_null() => Value::Null,
+                    _ => Value::String(edit.buffer.clone()),
+                };
+                self.editing = None;
+                self.apply(idx, value);
+            }
+            (KeyCode::Left | KeyCode::Char('-'), FieldKind::Enum(opts)) => {
+                edit.choice = edit.choice.checked_sub(1).unwrap_or(opts.len() - 1);
+            }
+            (KeyCode::Right | KeyCode::Char('+'), FieldKind::Enum(opts)) => {
+                edit.choice = (edit.choice + 1) % opts.len();
+            }
+            // 数值微调：整数步进 1，小数步进 0.05
+            (KeyCode::Left | KeyCode::Right | KeyCode::Char('+'), FieldKind::Number)
+            | (KeyCode::Char('-'), FieldKind::Number)
+                if !(key.code == KeyCode::Char('-') && edit.buffer.is_empty()) =>
+            {
+                let up = matches!(key.code, KeyCode::Right | KeyCode::Char('+'));
+                edit.buffer = step_number(&edit.buffer, up);
+            }
+            (KeyCode::Char(c), FieldKind::Number) if c.is_ascii_digit() || c == '.' || c == '-' => {
+                edit.buffer.push(c);
+            }
+            (KeyCode::Char(c), FieldKind::Text) => edit.buffer.push(c),
+            (KeyCode::Backspace, FieldKind::Number | FieldKind::Text) => {
+                edit.buffer.pop();
+            }
+            _ => {}
+        }
+        true
+    }
+
+    /// 把新值写回配置：经 serde 反序列化校验后替换并保存，非法值提示且不修改
+    fn apply(&mut self, idx: usize, value: Value) {
+        let name = self.rows[idx].name.clone();
+        let Ok(mut cfg) = self.config.try_write() else {
+            GlobIO::warn("Config is busy, try again");
+            return;
+        };
+        let Ok(Value::Object(mut map)) = serde_json::to_value(&*cfg) else {
+            return;
+        };
+        map.insert(name.clone(), value);
+        match serde_json::from_value::<Config>(Value::Object(map)) {
+            Ok(mut next) => {
+                if next.validate() {
+                    GlobIO::warn(format!("refresh_rate_ms clamped to {}", next.refresh_rate_ms));
+                }
+                *cfg = next;
+                if let Err(e) = cfg.save() {
+                    GlobIO::error(format!("Failed to save config: {}", e));
+                }
+            }
+            Err(e) => GlobIO::warn(format!("Invalid value for {}: {}", name, e)),
+        }
+        drop(cfg);
+        self.reload_rows();
+    }
+}
+
+fn is_secret(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SECRET_MARKERS.iter().any(|m| name.contains(m))
+}
+
+/// 配置对象中可在表单编辑的字段 (布尔、数字、字符串、null)，按字段名排序；密钥类字段不列出
+fn setting_rows(map: &Map<String, Value>) -> Vec<SettingRow> {
+    let mut rows: Vec<SettingRow> = map
+        .iter()
+        .filter(|(name, _)| !is_secret(name))
+        .filter_map(|(name, value)| {
+            let kind = match value {
+                Value::Bool(_) => FieldKind::Bool,
+                Value::Number(_) => FieldKind::Number,
+                Value::String(_) | Value::Null => ENUM_OPTIONS
+                    .iter()
+                    .find(|(n, _)| n == name)
+                    .map(|(_, opts)| FieldKind::Enum(opts))
+                    .unwrap_or(FieldKind::Text),
+                Value::Array(_) | Value::Object(_) => return None,
+            };
+            Some(SettingRow { name: name.clone(), value: value.clone(), kind })
+        })
+        .collect();
+    rows.sort_by(|a, b| a.name.cmp(&b.name));
+    rows
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::Bool(true) => "[x] on".into(),
+        Value::Bool(false) => "[ ] off".into(),
+        Value::String(s) => s.clone(),
+        Value::Null => "(not set)".into(),
+        v => v.to_string(),
+    }
+}
+
+/// 数值输入的 +/- 步进：含小数点按 0.05，否则按 1
+fn step_number(buffer: &str, up: bool) -> String {
+    let sign = if up { 1.0 } else { -1.0 };
+    if buffer.contains('.') {
+        let v = buffer.parse::<f64>().unwrap_or(0.0) + 0.05 * sign;
+        format!("{:.2}", v)
+    } else {
+        let v = buffer.parse::<i64>().unwrap_or(0) + sign as i64;
+        v.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn form_lists_scalars_and_hides_secrets() {
+        let map = serde_json::json!({
+            "refresh_rate_ms": 8,
+            "theme_color": "Cyan",
+            "welcome_message": null,
+            "save_on_exit": true,
+            "enabled_tabs": ["Info"],
+            "ui_state": {"info_summary": false},
+            "api_token": "hunter2",
+            "webhook_secret": "s3cr3t",
+        });
+        let rows = setting_rows(map.as_object().unwrap());
+        let names: Vec<&str> = rows.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["refresh_rate_ms", "save_on_exit", "theme_color", "welcome_message"]);
+        assert_eq!(rows[0].kind, FieldKind::Number);
+        assert_eq!(rows[1].kind, FieldKind::Bool);
+        assert_eq!(rows[2].kind, FieldKind::Enum(APP_COLORS));
+        assert_eq!(rows[3].kind, FieldKind::Text);
+    }
+
+    #[test]
+    fn every_config_scalar_is_listed_with_a_usable_kind() {
+        let Ok(Value::Object(map)) = serde_json::to_value(Config::default()) else {
+            panic!("Config must serialize to an object");
+        };
+        for row in setting_rows(&map) {
+            // 枚举字段的当前值必须在可选列表中，否则编辑时会被重置为第一项
+            if let FieldKind::Enum(opts) = row.kind {
+                let current = row.value.as_str().unwrap_or_default();
+                assert!(opts.contains(&current), "{} = {} not in options", row.name, current);
+            }
+        }
+    }
+
+    #[test]
+    fn number_stepping() {
+        assert_eq!(step_number("8", true), "9");
+        assert_eq!(step_number("0", false), "-1");
+        assert_eq!(step_number("0.50", true), "0.55");
+        assert_eq!(step_number("", true), "1");
+    }
+
+    #[test]
+    fn editing_rows_through_the_form_changes_the_config() {
+        crate::prelude::AtlasPath::init_for_test();
+        let _file_lock = crate::prelude::AtlasPath::config_file_lock();
+        GlobIO::init_for_test();
+        let config: SharedConfig = std::sync::Arc::new(tokio::sync::RwLock::new(Config::default()));
+        let mut comp = SettingsComponent {
+            config: config.clone(),
+            rows: Vec::new(),
+            state: TableState::default(),
+            editing: None,
+            glob_recv: GlobIO::recv(),
+            reload_pending: false,
+        };
+        comp.reload_rows();
+        let press = |comp: &mut SettingsComponent, code| comp.handle_key(KeyEvent::from(code));
+        let select = |comp: &mut SettingsComponent, name: &str| {
+            let idx = comp.rows.iter().position(|r| r.name == name).unwrap();
+            comp.state.select(Some(idx));
+        };
+
+        // 布尔行：Enter 直接切换
+        let before = config.try_read().unwrap().group_digits;
+        select(&mut comp, "group_digits");
+        press(&mut comp, KeyCode::Enter);
+        assert_eq!(config.try_read().unwrap().group_digits, !before);
+        press(&mut comp, KeyCode::Enter);
+        assert_eq!(config.try_read().unwrap().group_digits, before);
+
+        // 数值行：Enter 进入编辑，→ 加一，Enter 提交
+        let secs = config.try_read().unwrap().db_refresh_secs;
+        select(&mut comp, "db_refresh_secs");
+        press(&mut comp, KeyCode::Enter);
+        press(&mut comp, KeyCode::Right);
+        press(&mut comp, KeyCode::Enter);
+        assert!(comp.editing.is_none());
+        assert_eq!(config.try_read().unwrap().db_refresh_secs, secs + 1);
+        let _ = std::fs::remove_file(crate::prelude::AtlasPath::get_config_path());
+    }
+}
@@ -0,0 +1,208 @@
+use std::sync::{
+    RwLock,
+    atomic::{AtomicBool, AtomicU8, Ordering},
+};
+
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier, Style},
+    symbols::bar,
+    text::Line,
+};
+
+use crate::config::{Config, SparklineStyle};
+
+/// 单色模式：NO_COLOR 环境变量或 Config.force_monochrome
+static MONOCHROME: AtomicBool = AtomicBool::new(false);
+/// Config.focus_marker 的缓存，渲染时无需读锁
+static FOCUS_MARKER: AtomicBool = AtomicBool::new(true);
+
+/// Config.sparkline_style 的缓存 (枚举下标)
+static SPARKLINE_STYLE: AtomicU8 = AtomicU8::new(0);
+
+/// Config.selection_symbol 的缓存
+static SELECTION_SYMBOL: RwLock<String> = RwLock::new(String::new());
+#[cfg(test)]
+thread_local! {
+    // 测试中按线程覆盖选中标记，避免影响并行运行的其它渲染测试
+    static SELECTION_SYMBOL_TEST: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+}
+
+/// 焦点窗格标题前缀
+pub const FOCUS_MARKER_SYMBOL: &str = "▸";
+/// 未初始化或配置为空时的选中标记
+const DEFAULT_SELECTION_SYMBOL: &str = ">> ";
+
+/// 启动及配置热加载后调用，重新计算是否禁用颜色
+pub fn init() {
+    let no_color = no_color_requested(std::env::var_os("NO_COLOR").as_deref());
+    let (forced, marker, symbol, sparkline) = Config::get()
+        .try_read()
+        .map(|cfg| (cfg.force_monochrome, cfg.focus_marker, cfg.selection_symbol.clone(), cfg.sparkline_style))
+        .unwrap_or((false, true, String::new(), SparklineStyle::Blocks));
+    MONOCHROME.store(no_color || forced, Ordering::Relaxed);
+    FOCUS_MARKER.store(marker, Ordering::Relaxed);
+    SPARKLINE_STYLE.store(sparkline as u8, Ordering::Relaxed);
+    if let Ok(mut s) = SELECTION_SYMBOL.write() {
+        *s = symbol;
+    }
+}
+
+/// https://no-color.org : 只要变量存在且非空即生效
+fn no_color_requested(value: Option<&std::ffi::OsStr>) -> bool {
+    value.is_some_and(|v| !v.is_empty())
+}
+
+pub fn is_monochrome() -> bool {
+    MONOCHROME.load(Ordering::Relaxed)
+}
+
+/// 盲文点阵：自下而上逐点填满一个字符格
+const BRAILLE_BARS: bar::Set<'static> = bar::Set {
+    full: "⣿",
+    seven_eighths: "⣷",
+    three_quarters: "⣶",
+    five_eighths: "⣦",
+    half: "⣤",
+    three_eighths: "⣄",
+    one_quarter: "⣀",
+    one_eighth: "⡀",
+    empty: " ",
+};
+
+/// 纯 ASCII：过半为 `#`，不足一半为 `.`
+const ASCII_BARS: bar::Set<'static> = bar::Set {
+    full: "#",
+    seven_eighths: "#",
+    three_quarters: "#",
+    five_eighths: "#",
+    half: "#",
+    three_eighths: ".",
+    one_quarter: ".",
+    one_eighth: ".",
+    empty: " ",
+};
+
+/// 所有 Sparkline 共用的字符集 (Config.sparkline_style)
+pub fn sparkline_bars() -> bar::Set<'static> {
+    let style = match SPARKLINE_STYLE.load(Ordering::Relaxed) {
+        x if x == SparklineStyle::Braille as u8 => SparklineStyle::Braille,
+        x if x == SparklineStyle::Ascii as u8 => SparklineStyle::Ascii,
+        _ => SparklineStyle::Blocks,
+    };
+    bars_for(style)
+}
+
+/// 各样式对应的字符集
+fn bars_for(style: SparklineStyle) -> bar::Set<'static> {
+    match style {
+        SparklineStyle::Blocks => bar::NINE_LEVELS,
+        SparklineStyle::Braille => BRAILLE_BARS,
+        SparklineStyle::Ascii => ASCII_BARS,
+    }
+}
+
+/// 可聚焦窗格的边框样式
+pub fn focus_border(focused: bool) -> Style {
+    if focused {
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Gray)
+    }
+}
+
+/// List/Table 选中行前的标记，调用方先绑定到局部变量再传给 highlight_symbol
+pub fn selection_symbol() -> String {
+    #[cfg(test)]
+    if let Some(symbol) = SELECTION_SYMBOL_TEST.with_borrow(|s| s.clone()) {
+        return symbol;
+    }
+    match SELECTION_SYMBOL.read() {
+        Ok(s) if !s.is_empty() => s.clone(),
+        _ => DEFAULT_SELECTION_SYMBOL.to_string(),
+    }
+}
+
+#[cfg(test)]
+pub fn set_selection_symbol_for_test(symbol: &str) {
+    SELECTION_SYMBOL_TEST.set(Some(symbol.to_string()));
+}
+
+/// List/Table 选中行的统一样式；单色模式下背景色会被去掉，因此同时加 BOLD
+pub fn selection_style() -> Style {
+    Style::default().bg(Color::Rgb(50, 50, 50)).add_modifier(Modifier::BOLD)
+}
+
+/// 可聚焦窗格的标题：开启 focus_marker 时焦点窗格加 `▸` 前缀并反色显示，
+/// 单色模式下颜色被去掉后 REVERSED 依然保留
+pub fn focus_title(title: &str, focused: bool) -> Line<'static> {
+    if focused && FOCUS_MARKER.load(Ordering::Relaxed) {
+        let text = format!("{} {}", FOCUS_MARKER_SYMBOL, title.trim_start());
+        Line::styled(text, Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD))
+    } else {
+        Line::from(title.to_string())
+    }
+}
+
+/// 统一的样式出口：单色模式下去掉整帧的前景/背景色，
+/// 只保留 BOLD / REVERSED 等修饰符作为强调，组件内部无需各自判断
+pub fn apply(buf: &mut Buffer) {
+    if is_monochrome() {
+        strip_colors(buf);
+    }
+}
+
+fn strip_colors(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        cell.set_fg(Color::Reset);
+        cell.set_bg(Color::Reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{layout::Rect, widgets::{Paragraph, Widget}};
+
+    #[test]
+    fn no_color_needs_a_non_empty_value() {
+        assert!(no_color_requested(Some("1".as_ref())));
+        assert!(!no_color_requested(Some("".as_ref())));
+        assert!(!no_color_requested(None));
+    }
+
+    #[test]
+    fn monochrome_frame_carries_no_colors() {
+        let area = Rect::new(0, 0, 12, 2);
+        let mut buf = Buffer::empty(area);
+        let style = selection_style().fg(Color::Red);
+        Paragraph::new(vec![Line::styled("selected", style), focus_title("pane", true)]).render(area, &mut buf);
+        assert!(buf.content.iter().any(|c| c.fg == Color::Red));
+
+        strip_colors(&mut buf);
+        for cell in &buf.content {
+            assert_eq!((cell.fg, cell.bg), (Color::Reset, Color::Reset));
+        }
+        // 强调修饰符保留
+        assert!(buf[(0, 0)].modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn ascii_sparkline_renders_only_ascii() {
+        use ratatui::widgets::Sparkline;
+        let data: Vec<u64> = (0..40).map(|i| (i * 37) % 101).collect();
+        let render = |style| {
+            let area = Rect::new(0, 0, 40, 4);
+            let mut buf = Buffer::empty(area);
+            Sparkline::default().data(&data).max(100).bar_set(bars_for(style)).render(area, &mut buf);
+            buf.content.iter().map(|c| c.symbol().to_string()).collect::<String>()
+        };
+
+        let ascii = render(SparklineStyle::Ascii);
+        assert!(ascii.is_ascii(), "{ascii}");
+        assert!(ascii.contains('#') && ascii.contains('.'));
+        // 默认的方块字符不是 ASCII
+        assert!(!render(SparklineStyle::Blocks).is_ascii());
+        assert!(render(SparklineStyle::Braille).contains('⣿'));
+    }
+}
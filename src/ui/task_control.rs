@@ -1,19 +1,20 @@
-use crate::config::Config;
+use crate::config::{AppColor, Config};
 use crate::prelude::*;
 use crate::constans::{TASK_RAW_JSON};
-use crate::message::{DynamicPayload, GlobalEvent, StatusLevel};
+use crate::message::{DynamicPayload, GlobalEvent, StatusLevel, TaskRequest};
 use crate::{
     config::SharedConfig,
     ui::component::Component,
 };
 use ansi_to_tui::IntoText;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{prelude::*, widgets::*};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use std::process::Stdio;
 use std::sync::Arc;
 use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt as _, BufReader};
 use tokio::sync::broadcast::Sender;
 use tokio::sync::{RwLock as ARwLock, mpsc}; // 引入转换 trait
@@ -40,6 +41,81 @@ pub struct TaskDescriptor {
     pub group: String,
     pub log_limit: Option<usize>,
     pub restart_policy: Option<RestartPolicy>,
+    /// 自动启动前额外等待的毫秒数 (用于等待依赖服务就绪)
+    pub autostart_delay_ms: Option<u64>,
+    /// 调度优先级 (nice 值，-20..19)。普通用户只能降低优先级 (设为正数)，负值需要 root
+    pub nice: Option<i32>,
+    /// IO 调度类 (仅 Linux/Android，ionice -c)：0 none, 1 realtime, 2 best-effort, 3 idle；realtime 需要 root。
+    /// 超出 0..=3 或系统中没有 ionice (例如默认的 Termux) 时忽略
+    pub ionice_class: Option<u8>,
+}
+
+impl TaskDescriptor {
+    /// 实际启动的程序与参数：配置了 nice / ionice_class 时以 `ionice -c N nice -n M cmd ...` 包装
+    /// (无需 libc / pre_exec，子进程继承优先级)
+    fn spawn_argv(&self) -> (String, Vec<String>) {
+        let mut argv: Vec<String> = Vec::new();
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if let Some(class) = self.ionice_class {
+            if class > 3 {
+                log::warn!("Task '{}': ionice_class {} is not 0-3, ignored", self.id, class);
+            } else if !on_path("ionice") {
+                log::warn!("Task '{}': ionice not found, starting without IO priority", self.id);
+            } else {
+                argv.extend(["ionice".into(), "-c".into(), class.to_string()]);
+            }
+        }
+        #[cfg(unix)]
+        if let Some(n) = self.nice {
+            argv.extend(["nice".into(), "-n".into(), n.clamp(-20, 19).to_string()]);
+        }
+        argv.push(self.command.clone());
+        argv.extend(self.args.iter().cloned());
+        let program = argv.remove(0);
+        (program, argv)
+    }
+
+    /// 手动复现用的单行 shell 命令：`cd <cwd> && K=V ... <program> <args>`，
+    /// 所有部分按 POSIX sh 规则加单引号，与实际启动的 argv (含 nice/ionice) 一致
+    pub fn to_shell_command(&self) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if let Some(cwd) = &self.cwd {
+            parts.push(format!("cd {} &&", shell_quote(cwd)));
+        }
+        if let Some(envs) = &self.envs {
+            let mut envs: Vec<_> = envs.iter().collect();
+            envs.sort();
+            parts.extend(envs.into_iter().map(|(k, v)| format!("{}={}", k, shell_quote(v))));
+        }
+        let (program, args) = self.spawn_argv();
+        parts.push(shell_quote(&program));
+        parts.extend(args.iter().map(|a| shell_quote(a)));
+        parts.join(" ")
+    }
+}
+
+/// 脚本旁的元数据文件 (`foo.ts` -> `foo.atlas.json`)
+#[derive(Debug, Default, Deserialize)]
+struct ScriptMeta {
+    /// 覆盖 Config.deno_args，同样支持 `{script}` 占位符
+    args: Option<Vec<String>>,
+}
+
+/// 生成 deno 脚本的启动参数：优先用 .atlas.json 中的 args，其次 Config.deno_args；
+/// 模板中没有 `{script}` 时把脚本路径追加到末尾
+fn deno_script_args(script: &std::path::Path, default_args: &[String]) -> Vec<String> {
+    let meta: ScriptMeta = std::fs::read_to_string(script.with_extension("atlas.json"))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    let template = meta.args.as_deref().unwrap_or(default_args);
+    let script = script.to_string_lossy();
+
+    let mut args: Vec<String> = template.iter().map(|a| a.replace("{script}", &script)).collect();
+    if !template.iter().any(|a| a.contains("{script}")) {
+        args.push(script.into_owned());
+    }
+    args
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -52,18 +128,252 @@ pub enum TaskStatus {
     Failed(String),
 }
 
+/// 任务状态快照 (退出前等待任务停止时查询)
+#[derive(Debug, Clone)]
+pub struct TaskSnapshot {
+    pub id: String,
+    pub name: String,
+    pub group: String,
+    pub status: TaskStatusView,
+}
+
+/// TaskStatus 的快照视图：不持有 Instant，运行时长转为秒数
+#[derive(Debug, Clone)]
+pub enum TaskStatusView {
+    Stopped,
+    Running { pid: u32, uptime_secs: u64 },
+    Failed { reason: String },
+}
+
+impl From<&TaskStatus> for TaskStatusView {
+    fn from(status: &TaskStatus) -> Self {
+        match status {
+            TaskStatus::Stopped => Self::Stopped,
+            TaskStatus::Running { pid, start_time } => Self::Running {
+                pid: *pid,
+                uptime_secs: start_time.elapsed().as_secs(),
+            },
+            TaskStatus::Failed(reason) => Self::Failed {
+                reason: reason.clone(),
+            },
+        }
+    }
+}
+
+/// 日志行来源：决定渲染时的着色方式
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogSource {
+    Stdout,
+    Stderr,
+    /// Atlas 自身写入的行 (例如 stdin 回显)
+    Atlas,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LogLine {
+    pub source: LogSource,
+    pub text: String,
+    /// 全局递增的序号：缓冲区超出 log_limit 从头部丢弃后下标会变，序号不会
+    pub seq: u64,
+}
+
+static LOG_SEQ: AtomicU64 = AtomicU64::new(0);
+
+impl LogLine {
+    pub fn new(source: LogSource, text: impl Into<String>) -> Self {
+        Self {
+            source,
+            text: text.into(),
+            seq: LOG_SEQ.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+}
+
+/// 序号对应的当前下标；该行已被丢弃时返回 None (缓冲区按序号递增排列)
+fn log_index(logs: &VecDeque<LogLine>, seq: u64) -> Option<usize> {
+    logs.binary_search_by_key(&seq, |l| l.seq).ok()
+}
+
+/// 默认日志保留行数 (TaskDescriptor.log_limit 未设置时)
+const DEFAULT_LOG_LIMIT: usize = 1000;
+/// RestartPolicy::Always 失败后重新启动前的等待时间
+const RESTART_DELAY_MS: u64 = 1000;
+/// 'f' 把文件写入 stdin 时每行之间的间隔，避免一次塞满管道
+const PIPE_LINE_DELAY_MS: u64 = 5;
+/// flapping 报错中附带的 stderr 末尾行数
+const FLAPPING_STDERR_TAIL: usize = 3;
+/// 日志视图在可见行之外额外解析的行数
+const LOG_RENDER_MARGIN: usize = 8;
+
 /// 2. 运行时任务对象
 pub struct TaskRuntime {
     pub desc: TaskDescriptor,
     // 状态必须是可跨线程修改的，否则 render 永远看不到后台的更新
     pub status: Arc<RwLock<TaskStatus>>,
-    pub logs: Arc<RwLock<VecDeque<String>>>,
+    pub logs: Arc<RwLock<VecDeque<LogLine>>>,
     pub control_tx: Option<mpsc::Sender<TaskControlMsg>>,
+    // 连续"秒退"次数，运行超过 flapping_window_ms 后清零，手动启动时也清零
+    pub quick_exits: Arc<AtomicU32>,
+    // 'R' 重启：已发出 Stop，等后台协程把状态改为非 Running (子进程已回收) 后再启动
+    pub restart_pending: bool,
+    // 累计运行统计，后台协程在启动/退出时更新并写入 task_stats 表
+    pub stats: Arc<RwLock<TaskStats>>,
+}
+
+/// 任务的累计运行统计 (跨重启保留，按任务 id 存储)
+#[derive(Debug, Clone, Default)]
+pub struct TaskStats {
+    pub runs: u64,
+    pub failures: u64,
+    pub uptime_secs: u64, // 已结束的运行时长之和，不含当前这次
+    pub last_exit: Option<i32>, // None: 被信号结束
+    pub last_exit_at: Option<String>, // RFC3339
+}
+
+/// 表中的历史统计合并进内存之前不写库，避免启动初期的更新覆盖旧数据
+static TASK_STATS_LOADED: AtomicBool = AtomicBool::new(false);
+
+impl TaskStats {
+    async fn init_table() -> Result<(), String> {
+        crate::db::Database::setup_table(
+            r#"
+            CREATE TABLE IF NOT EXISTS task_stats (
+                id TEXT PRIMARY KEY,
+                runs INTEGER NOT NULL DEFAULT 0,
+                failures INTEGER NOT NULL DEFAULT 0,
+                uptime_secs INTEGER NOT NULL DEFAULT 0,
+                last_exit INTEGER,
+                last_exit_at TEXT
+            );
+        "#,
+        )
+        .await
+    }
+
+    /// 启动时加载并累加到内存中的统计 (加载完成前可能已有自动启动的任务)，然后整体写回
+    fn spawn_load(tasks: Vec<(String, Arc<RwLock<TaskStats>>)>) {
+        tokio::spawn(async move {
+            let Some(pool) = crate::db::Database::try_pool() else {
+                return;
+            };
+            if let Err(e) = Self::init_table().await {
+                log::error!("task_stats table init error: {}", e);
+                return;
+            }
+            let rows = sqlx::query("SELECT id, runs, failures, uptime_secs, last_exit, last_exit_at FROM task_stats")
+                .fetch_all(pool)
+                .await
+                .unwrap_or_default();
+            let mut stored: HashMap<String, TaskStats> = rows
+                .into_iter()
+                .map(|row: sqlx::sqlite::SqliteRow| {
+                    use sqlx::Row;
+                    let stats = TaskStats {
+                        runs: row.get::<i64, _>("runs").max(0) as u64,
+                        failures: row.get::<i64, _>("failures").max(0) as u64,
+                        uptime_secs: row.get::<i64, _>("uptime_secs").max(0) as u64,
+                        last_exit: row.get("last_exit"),
+                        last_exit_at: row.get("last_exit_at"),
+                    };
+                    (row.get("id"), stats)
+                })
+                .collect();
+            for (id, stats) in &tasks {
+                if let Some(old) = stored.remove(id)
+                    && let Ok(mut s) = stats.write()
+                {
+                    s.runs += old.runs;
+                    s.failures += old.failures;
+                    s.uptime_secs += old.uptime_secs;
+                    if s.last_exit_at.is_none() {
+                        s.last_exit = old.last_exit;
+                        s.last_exit_at = old.last_exit_at;
+                    }
+                }
+            }
+            TASK_STATS_LOADED.store(true, Ordering::Relaxed);
+            for (id, stats) in &tasks {
+                Self::persist(id, stats).await;
+            }
+        });
+    }
+
+    /// 后台写库，不阻塞调用方 (状态切换必须先于写库完成，见 start_or_stop_task)
+    fn spawn_persist(id: String, stats: Arc<RwLock<TaskStats>>) {
+        tokio::spawn(async move { Self::persist(&id, &stats).await });
+    }
+
+    /// 写入当前统计；数据库不可用或只读时只保留在内存中
+    async fn persist(id: &str, stats: &Arc<RwLock<TaskStats>>) {
+        if !TASK_STATS_LOADED.load(Ordering::Relaxed) || crate::db::Database::is_read_only() {
+            return;
+        }
+        let Some(pool) = crate::db::Database::try_pool() else {
+            return;
+        };
+        let Some(s) = stats.read().ok().map(|s| s.clone()) else {
+            return;
+        };
+        let res = sqlx::query(
+            "INSERT INTO task_stats (id, runs, failures, uptime_secs, last_exit, last_exit_at) VALUES (?, ?, ?, ?, ?, ?)
+             ON CONFLICT(id) DO UPDATE SET runs = excluded.runs, failures = excluded.failures,
+             uptime_secs = excluded.uptime_secs, last_exit = excluded.last_exit, last_exit_at = excluded.last_exit_at",
+        )
+        .bind(id)
+        .bind(s.runs as i64)
+        .bind(s.failures as i64)
+        .bind(s.uptime_secs as i64)
+        .bind(s.last_exit)
+        .bind(&s.last_exit_at)
+        .execute(pool)
+        .await;
+        if let Err(e) = res {
+            log::warn!("Failed to save task stats for {}: {}", id, e);
+        }
+    }
+
+    /// 一次启动 (包括启动失败)
+    fn record_start(&mut self) {
+        self.runs += 1;
+    }
+
+    /// 一次运行结束：累计时长并记录退出码，failed 时计入失败次数
+    fn record_exit(&mut self, ran: std::time::Duration, code: Option<i32>, failed: bool) {
+        self.uptime_secs += ran.as_secs();
+        self.last_exit = code;
+        self.last_exit_at = Some(chrono::Local::now().to_rfc3339());
+        if failed {
+            self.failures += 1;
+        }
+    }
+
+    /// 详情面板中的一行摘要；running 为当前这次已运行的时长
+    fn summary(&self, running: Option<std::time::Duration>) -> String {
+        let last = match (&self.last_exit_at, self.last_exit) {
+            (Some(at), code) => {
+                let at = chrono::DateTime::parse_from_rfc3339(at)
+                    .map(|t| t.with_timezone(&chrono::Local).format("%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|_| at.clone());
+                let code = code.map(|c| c.to_string()).unwrap_or_else(|| "signal".into());
+                format!("{} at {}", code, at)
+            }
+            (None, _) => "-".into(),
+        };
+        let total = std::time::Duration::from_secs(self.uptime_secs) + running.unwrap_or_default();
+        format!(
+            "Runs: {}, Failures: {}, Last exit: {}, Total uptime: {}",
+            self.runs,
+            self.failures,
+            last,
+            crate::version::format_uptime(total)
+        )
+    }
 }
 pub struct _TaskRuntime {
     pub desc: TaskDescriptor,
     pub status: TaskStatus,
-    pub logs: Arc<RwLock<VecDeque<String>>>,
+    pub logs: Arc<RwLock<VecDeque<LogLine>>>,
     // 用于向后台协程发送控制指令（停止、输入）
     pub control_tx: Option<mpsc::Sender<TaskControlMsg>>,
 }
@@ -78,6 +388,8 @@ pub struct TaskControlComponent {
     config: SharedConfig,
     tasks: Vec<TaskRuntime>,
     selected_idx: usize,
+    // 任务列表可见窗口的首行下标，渲染时只为窗口内的任务构建 ListItem
+    list_offset: usize,
 
     // UI 状态
     view_mode: ViewMode,
@@ -86,12 +398,35 @@ pub struct TaskControlComponent {
     glob_recv: GlobRecv,
 
     input: String,
+    // 日志视图中按 'f' 后输入框用于填写文件路径，Enter 把文件逐行写入 stdin
+    pipe_prompt: bool,
+    search: Option<LogSearch>,
+    // 列表中按 '/' 后输入名称前缀跳转 (type-to-jump)，超过 JUMP_TIMEOUT_MS 无输入后退出
+    jump_buffer: String,
+    jump_at: std::time::Instant,
+    jumping: bool,
+}
+
+/// 'C' 生成的手动复现脚本文件名 (位于缓存目录)
+const RERUN_SCRIPT: &str = "rerun.sh";
+
+/// type-to-jump 连续输入的超时时间
+const JUMP_TIMEOUT_MS: u64 = 1000;
+
+/// 日志视图内的搜索状态 ('/' 打开)
+#[derive(Default)]
+struct LogSearch {
+    query: String,
+    editing: bool,       // 正在输入关键字
+    matches: Vec<u64>, // 命中的日志行序号 (LogLine.seq)
+    current: usize,
 }
 
 #[derive(PartialEq)]
 enum ViewMode {
     List, // 列表 + 详情模式
     Log,  // 全屏日志模式
+    Edit, // 详情面板内编辑命令行
 }
 
 impl Component for TaskControlComponent {
@@ -102,8 +437,14 @@ impl Component for TaskControlComponent {
             serde_json::from_str(&AtlasPath::read_task_json().unwrap_or_default()).unwrap_or_default();
 
         // --- 新增：扫描 scripts 目录 ---
+        let (deno_args, group_order) = Config::get()
+            .try_read()
+            .map(|c| (c.deno_args.clone(), c.task_group_order.clone()))
+            .unwrap_or_else(|_| (Config::default().deno_args, Vec::new()));
         let script_dir = AtlasPath::get_script_dir();
-        if let Ok(entries) = std::fs::read_dir(&script_dir) {
+        // 安全模式下不发现 Deno 脚本任务
+        let scripts = if Config::safe_mode() { None } else { std::fs::read_dir(&script_dir).ok() };
+        if let Some(entries) = scripts {
             for entry in entries.flatten() {
                 let path = entry.path();
                 // 逻辑：必须是文件，且后缀是 .ts
@@ -118,49 +459,30 @@ impl Component for TaskControlComponent {
                         id: format!("deno_{}", file_stem),
                         name: format!("🦕 {}", file_stem), // 增加图标区分
                         command: "deno".to_string(),
-                        // 常用参数：-A (全权限), run, 脚本路径
-                        args: vec![
-                            "run".into(),
-                            "-A".into(),
-                            "--unstable-kv".into(),
-                            "--unstable-cron".into(),
-                            path.to_string_lossy().into_owned(),
-                        ],
+                        // 参数来自 Config.deno_args，可被同名 .atlas.json 覆盖
+                        args: deno_script_args(&path, &deno_args),
                         cwd: Some(script_dir.to_string_lossy().to_string()),
                         envs: None,
                         autostart: false, // 脚本任务建议手动触发
                         group: "Scripts".to_string(),
                         log_limit: Some(1000),
                         restart_policy: Some(RestartPolicy::Never),
+                        autostart_delay_ms: None,
+                        nice: None,
+                        ionice_class: None,
                     };
                     descs.push(deno_task);
                 }
             }
         }
 
-        let mut tasks = Vec::new();
-        for d in descs {
-            let runtime = TaskRuntime {
-                desc: d,
-                status: Arc::new(RwLock::new(TaskStatus::Stopped)),
-                //TaskStatus::Stopped,
-                logs: Arc::new(RwLock::new(VecDeque::with_capacity(1000))),
-                control_tx: None,
-            };
-            tasks.push(runtime);
-        }
+        sort_by_group_order(&mut descs, &group_order);
 
-        let mut component = Self {
-            config:Config::get(),
-            tasks,
-            selected_idx: 0,
-            view_mode: ViewMode::List,
-            log_scroll: 0,
-            glob_send:GlobIO::send(),
-            glob_recv:GlobIO::recv(),
-            input: Default::default(),
-        };
+        let mut component = Self::with_tasks(Config::get(), descs);
 
+        TaskStats::spawn_load(
+            component.tasks.iter().map(|t| (t.desc.id.clone(), t.stats.clone())).collect(),
+        );
         // 处理自动启动
         component.auto_start_tasks();
 
@@ -168,17 +490,31 @@ impl Component for TaskControlComponent {
     }
 
     fn update(&mut self) -> bool {
+        false
+    }
+
+    // 任务控制请求和重启在后台时也必须处理
+    fn drain(&mut self) -> bool {
+        let mut changed = false;
         // 假设 self.glob_recv 是 App 自己的消息订阅端
-        while let Ok(event) = self.glob_recv.try_recv() {
+        while let Some(event) = GlobIO::try_recv(&mut self.glob_recv) {
             match event {
                 // 只有当收到 Data 且 key 为 "rend" 时才标记需要重绘
                 GlobalEvent::Data { key, .. } if key == "rend" => {
-                    return true;
+                    changed = true;
+                }
+                // 延迟启动、自动重启、退出前停止等任务控制请求
+                GlobalEvent::Task(req) => {
+                    self.handle_task_request(req);
+                    changed = true;
                 }
                 _ => {} // ... 处理其他全局事件
             }
         }
-        false
+        if self.finish_restarts() {
+            changed = true;
+        }
+        changed
     }
 
     fn render(&mut self, f: &mut Frame, area: Rect) {
@@ -193,6 +529,7 @@ impl Component for TaskControlComponent {
         match self.view_mode {
             ViewMode::List => self.handle_list_keys(key),
             ViewMode::Log => self.handle_log_keys(key),
+            ViewMode::Edit => self.handle_edit_keys(key),
         }
     }
 }
@@ -200,11 +537,175 @@ impl Component for TaskControlComponent {
 //3. 任务启动逻辑 (Tokio Backend)
 // 实现 TaskStatus 同步和 stdout 管道监听的核心逻辑。
 impl TaskControlComponent {
+    /// 由任务描述符构建组件 (不加载统计、不自动启动)
+    fn with_tasks(config: SharedConfig, descs: Vec<TaskDescriptor>) -> Self {
+        let mut tasks = Vec::new();
+        for d in descs {
+            let runtime = TaskRuntime {
+                desc: d,
+                status: Arc::new(RwLock::new(TaskStatus::Stopped)),
+                //TaskStatus::Stopped,
+                logs: Arc::new(RwLock::new(VecDeque::with_capacity(DEFAULT_LOG_LIMIT))),
+                control_tx: None,
+                quick_exits: Arc::new(AtomicU32::new(0)),
+                restart_pending: false,
+                stats: Arc::new(RwLock::new(TaskStats::default())),
+            };
+            tasks.push(runtime);
+        }
+
+        Self {
+            config,
+            tasks,
+            selected_idx: 0,
+            list_offset: 0,
+            view_mode: ViewMode::List,
+            log_scroll: 0,
+            glob_send:GlobIO::send(),
+            glob_recv:GlobIO::recv(),
+            input: Default::default(),
+            pipe_prompt: false,
+            search: None,
+            jump_buffer: String::new(),
+            jump_at: std::time::Instant::now(),
+            jumping: false,
+        }
+    }
+
+    fn find_task(&self, id: &str) -> Option<usize> {
+        self.tasks.iter().position(|t| t.desc.id == id)
+    }
+
+    fn is_running(&self, idx: usize) -> bool {
+        matches!(*self.tasks[idx].status.read().unwrap(), TaskStatus::Running { .. })
+    }
+
+    pub fn snapshot(&self) -> Vec<TaskSnapshot> {
+        self.tasks
+            .iter()
+            .map(|t| TaskSnapshot {
+                id: t.desc.id.clone(),
+                name: t.desc.name.clone(),
+                group: t.desc.group.clone(),
+                status: TaskStatusView::from(&*t.status.read().unwrap()),
+            })
+            .collect()
+    }
+
+    /// 处理来自 GlobIO 的外部控制请求，Start/Stop 是幂等的
+    fn handle_task_request(&mut self, req: TaskRequest) {
+        match req {
+            TaskRequest::Start { id, focus } => match self.find_task(&id) {
+                Some(idx) if !self.is_running(idx) => {
+                    self.start_or_stop_task(idx);
+                    if focus {
+                        self.focus_started(idx);
+                    }
+                }
+                Some(_) => {}
+                None => GlobIO::warn(format!("Unknown task: {}", id)),
+            },
+            TaskRequest::Stop(id) => match self.find_task(&id) {
+                Some(idx) if self.is_running(idx) => self.start_or_stop_task(idx),
+                Some(_) => {}
+                None => GlobIO::warn(format!("Unknown task: {}", id)),
+            },
+            TaskRequest::List(reply) => reply.send(self.snapshot()),
+            TaskRequest::StopAll => {
+                for idx in 0..self.tasks.len() {
+                    if self.is_running(idx) {
+                        self.start_or_stop_task(idx);
+                    }
+                }
+            }
+            TaskRequest::Logs { id, tail, reply } => {
+                if let Some(idx) = self.find_task(&id) {
+                    let logs = self.tasks[idx].logs.read().unwrap();
+                    reply.send(
+                        logs.iter()
+                            .skip(logs.len().saturating_sub(tail))
+                            .map(|l| LogLine::new(l.source, strip_ansi(&l.text)))
+                            .collect(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// 'R'：运行中的任务先停止，退出完成后由 finish_restarts 重新启动；未运行的直接启动
+    fn restart_task(&mut self, idx: usize) {
+        let task = &mut self.tasks[idx];
+        if task.restart_pending {
+            return;
+        }
+        task.quick_exits.store(0, Ordering::Relaxed);
+        if !self.is_running(idx) {
+            self.start_or_stop_task(idx);
+            return;
+        }
+        self.tasks[idx].restart_pending = true;
+        GlobIO::info(format!("Restarting '{}'…", self.tasks[idx].desc.name));
+        self.start_or_stop_task(idx);
+    }
+
+    /// 状态只在 child.wait() 返回后才离开 Running，因此此时启动不会出现两个实例
+    fn finish_restarts(&mut self) -> bool {
+        let ready: Vec<usize> = (0..self.tasks.len())
+            .filter(|&i| self.tasks[i].restart_pending && !self.is_running(i))
+            .collect();
+        for &idx in &ready {
+            self.tasks[idx].restart_pending = false;
+            self.start_or_stop_task(idx);
+        }
+        !ready.is_empty()
+    }
+
+    /// 第 n 个自动启动任务在 n * autostart_stagger_ms + autostart_delay_ms 后启动，
+    /// 延迟启动通过 GlobIO 发送 TaskRequest::Start，不阻塞 UI
     fn auto_start_tasks(&mut self) {
-        for i in 0..self.tasks.len() {
-            if self.tasks[i].desc.autostart {
+        if Config::safe_mode() {
+            return;
+        }
+        let stagger = self
+            .config
+            .try_read()
+            .map(|cfg| cfg.autostart_stagger_ms)
+            .unwrap_or(0);
+
+        let autostart: Vec<usize> = (0..self.tasks.len())
+            .filter(|&i| self.tasks[i].desc.autostart)
+            .collect();
+        for (n, i) in autostart.into_iter().enumerate() {
+            let delay = stagger * n as u64 + self.tasks[i].desc.autostart_delay_ms.unwrap_or(0);
+            if delay == 0 {
                 self.start_or_stop_task(i);
+                continue;
             }
+            let id = self.tasks[i].desc.id.clone();
+            let glob_send = self.glob_send.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+                let _ = glob_send.send(GlobalEvent::Task(TaskRequest::Start { id, focus: false }));
+            });
+        }
+    }
+
+    /// focus_on_start：选中刚启动的任务 (idx 为 self.tasks 下标，列表按此顺序显示)，
+    /// log_on_start 时直接进入日志视图；正在编辑或查看其它日志时不打断
+    fn focus_started(&mut self, idx: usize) {
+        let (focus, log) = self
+            .config
+            .try_read()
+            .map(|cfg| (cfg.focus_on_start, cfg.log_on_start))
+            .unwrap_or((true, false));
+        if !focus || self.view_mode != ViewMode::List || idx >= self.tasks.len() {
+            return;
+        }
+        self.selected_idx = idx;
+        self.jumping = false;
+        if log {
+            self.view_mode = ViewMode::Log;
+            self.search = None;
         }
     }
 
@@ -224,28 +725,101 @@ impl TaskControlComponent {
             return;
         }
 
-        // 2. 准备启动
+        // 2. 准备启动 (安全模式下禁止启动任何进程)
+        if Config::safe_mode() {
+            GlobIO::warn(format!("Safe mode: not starting '{}'", task.desc.name));
+            return;
+        }
         let desc = task.desc.clone();
         let logs = task.logs.clone();
+        let log_limit = task.desc.log_limit.unwrap_or(DEFAULT_LOG_LIMIT);
         let status_lock = task.status.clone(); // 克隆状态锁给后台
         let (tx, mut rx) = mpsc::channel::<TaskControlMsg>(32);
         task.control_tx = Some(tx);
+        let quick_exits = task.quick_exits.clone();
+        let stats = task.stats.clone();
         let glob_send = self.glob_send.clone();
+        let (flap_window, flap_threshold, merge_output) = self
+            .config
+            .try_read()
+            .map(|cfg| (cfg.flapping_window_ms, cfg.flapping_threshold, cfg.merge_task_output))
+            .unwrap_or((2000, 3, false));
 
         tokio::spawn(async move {
-            let mut cmd = tokio::process::Command::new(&desc.command);
-            cmd.args(&desc.args)
+            let started = std::time::Instant::now();
+            let (tail_logs, restart_send) = (logs.clone(), glob_send.clone());
+            // 失败退出 (或无法启动) 时调用：统计秒退次数，达到阈值判定 flapping 并停止自动重启
+            let on_failure = |reason: String| -> TaskStatus {
+                let quick = started.elapsed().as_millis() < flap_window as u128;
+                let n = if quick {
+                    quick_exits.fetch_add(1, Ordering::Relaxed) + 1
+                } else {
+                    quick_exits.store(0, Ordering::Relaxed);
+                    0
+                };
+                if flap_threshold > 0 && n >= flap_threshold {
+                    let tail: Vec<String> = tail_logs
+                        .read()
+                        .map(|l| {
+                            let mut t: Vec<String> = l
+                                .iter()
+                                .rev()
+                                .filter(|x| x.source == LogSource::Stderr)
+                                .take(FLAPPING_STDERR_TAIL)
+                                .map(|x| strip_ansi(&x.text))
+                                .collect();
+                            t.reverse();
+                            t
+                        })
+                        .unwrap_or_default();
+                    let mut msg = format!(
+                        "Task '{}' is flapping: exited {} times within {}ms of starting ({})",
+                        desc.name, n, flap_window, reason
+                    );
+                    if !tail.is_empty() {
+                        msg = format!("{} | stderr: {}", msg, tail.join(" / "));
+                    }
+                    GlobIO::error(msg);
+                    return TaskStatus::Failed(format!("Flapping: {}", reason));
+                }
+                // 只有在非手动停止且配置了 Always 时才重启
+                if let Some(RestartPolicy::Always) = desc.restart_policy {
+                    let id = desc.id.clone();
+                    let restart_send = restart_send.clone();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(std::time::Duration::from_millis(RESTART_DELAY_MS)).await;
+                        let _ = restart_send.send(GlobalEvent::Task(TaskRequest::Start { id, focus: false }));
+                    });
+                }
+                TaskStatus::Failed(reason)
+            };
+
+            let (program, args) = desc.spawn_argv();
+            let mut cmd = tokio::process::Command::new(&program);
+            cmd.args(&args)
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped())
-                .stdin(Stdio::piped());
+                .stdin(Stdio::piped())
+                // 兜底：运行时退出时仍未结束的子进程随之被杀掉，避免遗留孤儿进程
+                .kill_on_drop(true);
+            // 成功时覆盖上面的 stdout/stderr 设置，失败则保持两个独立管道
+            let merged = if merge_output { merge_stderr_into_stdout(&mut cmd) } else { None };
 
             if let Some(cwd) = &desc.cwd {
                 cmd.current_dir(cwd);
             }
+            // 放入独立进程组，停止时整组结束，`sh -c` 之类派生的子进程不会成为孤儿
+            #[cfg(unix)]
+            cmd.process_group(0);
 
-            match cmd.spawn() {
+            let spawned = cmd.spawn();
+            // 合并模式下 cmd 持有管道写端的副本，必须释放，否则读端永远等不到 EOF
+            drop(cmd);
+            match spawned {
                 Ok(mut child) => {
                     let pid = child.id().expect("Failed to get PID");
+                    // 先标记 Running 再写库：写库期间的 'x' / 自动重启必须看到任务已在运行，
+                    // 否则会再启动一个子进程并覆盖 control_tx
                     {
                         let mut s = status_lock.write().unwrap();
                         *s = TaskStatus::Running {
@@ -253,40 +827,59 @@ impl TaskControlComponent {
                             start_time: std::time::Instant::now(),
                         };
                     }
+                    if let Ok(mut s) = stats.write() {
+                        s.record_start();
+                    }
+                    TaskStats::spawn_persist(desc.id.clone(), stats.clone());
 
-                    let stdout = child.stdout.take().unwrap();
-                    let stderr = child.stderr.take().unwrap(); // 也要捕获错误输出，否则看不到报错
+                    let stdout = child.stdout.take();
+                    let stderr = child.stderr.take(); // 也要捕获错误输出，否则看不到报错
                     let mut stdin = child.stdin.take().unwrap(); // 获取 stdin 句柄
 
                     // --- 1. 日志读取协程 (继续保留，因为它只读管道) ---
                     let logs_for_io = logs.clone();
                     let glob_for_io = glob_send.clone();
                     tokio::spawn(async move {
-                        // use tokio::io::AsyncReadExt as _;
-                        let mut out_reader = BufReader::new(stdout).lines();
-                        let mut err_reader = BufReader::new(stderr).lines();
-                        loop {
-                            let glob_send_a = glob_send.clone();
-                            let glob_send_b = glob_send.clone();
+                        // 合并模式只有一个按写入顺序读取的管道，全部记为 stdout
+                        if let Some(pipe) = merged {
+                            let mut reader = BufReader::new(pipe);
+                            let mut buf = Vec::new();
+                            while let Some(l) = next_line_lossy(&mut reader, &mut buf).await {
+                                append_log(&logs, LogLine::new(LogSource::Stdout, l), log_limit, glob_send.clone());
+                            }
+                            return;
+                        }
+                        let (Some(stdout), Some(stderr)) = (stdout, stderr) else {
+                            return;
+                        };
+                        let mut out_reader = BufReader::new(stdout);
+                        let mut err_reader = BufReader::new(stderr);
+                        let (mut out_buf, mut err_buf) = (Vec::new(), Vec::new());
+                        // 一个管道关闭后继续读另一个，直到两个都结束
+                        let (mut out_open, mut err_open) = (true, true);
+                        while out_open || err_open {
                             tokio::select! {
-                                line = out_reader.next_line() => {
-                                    if let Ok(Some(l)) = line { append_log(&logs, l ,glob_send_a); } else { break; }
-                                }
-                                line = err_reader.next_line() => {
-                                    if let Ok(Some(l)) = line { append_log(&logs, format!("[ERR] {}", l),glob_send_b); } else { break; }
-                                }
+                                line = next_line_lossy(&mut out_reader, &mut out_buf), if out_open => match line {
+                                    Some(l) => append_log(&logs, LogLine::new(LogSource::Stdout, l), log_limit, glob_send.clone()),
+                                    None => out_open = false,
+                                },
+                                line = next_line_lossy(&mut err_reader, &mut err_buf), if err_open => match line {
+                                    Some(l) => append_log(&logs, LogLine::new(LogSource::Stderr, l), log_limit, glob_send.clone()),
+                                    None => err_open = false,
+                                },
                             }
                         }
                     });
                     // 辅助函数
                     fn append_log(
-                        logs: &Arc<RwLock<VecDeque<String>>>,
-                        line: String,
+                        logs: &Arc<RwLock<VecDeque<LogLine>>>,
+                        line: LogLine,
+                        limit: usize,
                         glob_send: Sender<GlobalEvent>,
                     ) {
                         if let Ok(mut l) = logs.write() {
                             l.push_back(line);
-                            if l.len() > 1000 {
+                            while l.len() > limit {
                                 l.pop_front();
                             }
                             let _ = glob_send.send(GlobalEvent::Data {
@@ -314,6 +907,7 @@ impl TaskControlComponent {
                                     }
                                     TaskControlMsg::Stop => {
                                         is_manual_stop = true;
+                                        kill_process_group(pid).await;
                                         let _ = child.kill().await;
                                         // 继续循环，等待 child.wait() 在下一轮被触发以回收资源
                                     }
@@ -324,34 +918,31 @@ impl TaskControlComponent {
 
 
 
-                    let mut s = status_lock.write().unwrap();
-                    match exit_result {
-                        Ok(status) => {
-                            if is_manual_stop || status.success() {
-                                // 手动停止或正常退出 (exit code 0)
-                                *s = TaskStatus::Stopped;
-                            } else {
-                                // 非正常退出
-                                let code = status
-                                    .code()
-                                    .map(|c| c.to_string())
-                                    .unwrap_or_else(|| "Killed by signal".into());
-                                *s = TaskStatus::Failed(format!("Exit Code: {}", code));
-
-                                // 只有在非手动停止且配置了 Always 时才重启
-                                if let Some(RestartPolicy::Always) = desc.restart_policy {
-                                    // 这里触发重启逻辑...
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            *s = TaskStatus::Failed(e.to_string());
-                        }
+                    let code = exit_result.as_ref().ok().and_then(|s| s.code());
+                    let next = match exit_result {
+                        // 手动停止或正常退出 (exit code 0)
+                        Ok(status) if is_manual_stop || status.success() => TaskStatus::Stopped,
+                        // 非正常退出
+                        Ok(status) => on_failure(describe_exit(&status)),
+                        Err(e) => TaskStatus::Failed(e.to_string()),
+                    };
+                    if let Ok(mut s) = stats.write() {
+                        s.record_exit(started.elapsed(), code, matches!(next, TaskStatus::Failed(_)));
                     }
+                    *status_lock.write().unwrap() = next;
+                    TaskStats::spawn_persist(desc.id.clone(), stats.clone());
+                    // 通知 UI 状态已变化 (等待中的重启依赖这次 update)
+                    let _ = restart_send.send(GlobalEvent::Data { key: "rend", data: DynamicPayload(Arc::new(())) });
                 }
                 Err(e) => {
-                    let mut s = status_lock.write().unwrap();
-                    *s = TaskStatus::Failed(e.to_string());
+                    // 命令写错等无法启动的情况同样计入秒退
+                    let next = on_failure(e.to_string());
+                    if let Ok(mut s) = stats.write() {
+                        s.record_start();
+                        s.record_exit(std::time::Duration::ZERO, None, true);
+                    }
+                    *status_lock.write().unwrap() = next;
+                    TaskStats::spawn_persist(desc.id.clone(), stats.clone());
                 }
             }
         });
@@ -372,19 +963,25 @@ impl TaskControlComponent {
             Constraint::Percentage(50), // 下方详情面板
         ])
         .split(area);
+        // 任务很多 (脚本目录有上百个文件) 时只构建可见的一屏，与 render_disk_list 的做法一致
+        let visible = chunks[0].height.saturating_sub(2) as usize;
+        self.list_offset = visible_window(self.selected_idx, self.list_offset, visible, self.tasks.len());
         let mut chunks = chunks.into_iter();
 
         // 1. 任务列表
         let items: Vec<ListItem> = self
             .tasks
             .iter()
-            .enumerate()
-            .map(|(i, t)| {
-                let is_selected = i == self.selected_idx;
-
+            .skip(self.list_offset)
+            .take(visible)
+            .map(|t| {
                 // 状态文字化
                 let status_guard = t.status.read().unwrap(); // 获取当前状态快照
                 let (status_text, status_style) = match &*status_guard {
+                    _ if t.restart_pending => (
+                        " RESTART ",
+                        Style::default().bg(Color::Yellow).fg(Color::Black),
+                    ),
                     TaskStatus::Running { .. } => (
                         " RUNNING ",
                         Style::default().bg(Color::Green).fg(Color::Black),
@@ -399,36 +996,52 @@ impl TaskControlComponent {
                     ),
                 };
 
-                let mut line = Line::from(vec![
+                // 运行时长单独一列并右对齐
+                let uptime = match &*status_guard {
+                    TaskStatus::Running { start_time, .. } => crate::version::format_uptime(start_time.elapsed()),
+                    _ => String::new(),
+                };
+                let line = Line::from(vec![
                     Span::styled(status_text, status_style),
                     Span::raw(format!(" {:<20}", t.desc.name)),
+                    Span::styled(format!("{:>7}", uptime), Style::default().fg(Color::Green)),
                     Span::styled(
                         format!(" [{}]", t.desc.group),
                         Style::default().fg(Color::DarkGray),
                     ),
                 ]);
-
-                if is_selected {
-                    line = line.patch_style(
-                        Style::default()
-                            .add_modifier(Modifier::REVERSED)
-                            .fg(Color::Yellow),
-                    );
-                }
                 ListItem::new(line)
             })
             .collect();
 
         if let Some(a) = chunks.next() {
-            f.render_widget(
+            let symbol = crate::ui::style::selection_symbol();
+            let mut state = ListState::default().with_selected(Some(self.selected_idx - self.list_offset));
+            let position = if self.tasks.len() > visible {
+                format!("[{}/{}] ", self.selected_idx + 1, self.tasks.len())
+            } else {
+                String::new()
+            };
+            f.render_stateful_widget(
                 List::new(items)
                     .block(
                         Block::default()
                             .borders(Borders::ALL)
-                            .title(" ⚙️ Task Manager "),
+                            .title(if self.jump_active() {
+                                format!(" ⚙️ Task Manager {}[jump: {}] ", position, self.jump_buffer)
+                            } else {
+                                format!(" ⚙️ Task Manager {}", position)
+                            })
+                            .title_bottom(if Config::safe_mode() {
+                                Line::from(" 🔒 safe mode: execution disabled ").red()
+                            } else {
+                                Line::default()
+                            }),
                     )
-                    .highlight_symbol(">> "),
+                    .highlight_style(crate::ui::style::selection_style())
+                    .highlight_symbol(symbol.as_str()),
                 *a,
+                &mut state,
             );
         }
 
@@ -446,7 +1059,17 @@ impl TaskControlComponent {
                     TaskStatus::Stopped => "Inactive / Stopped".to_string(),
                 };
 
-                let details = vec![
+                let children = match &*status_guard {
+                    TaskStatus::Running { pid, .. } => descendant_pids(*pid),
+                    _ => Vec::new(),
+                };
+                let running_for = match &*status_guard {
+                    TaskStatus::Running { start_time, .. } => Some(start_time.elapsed()),
+                    _ => None,
+                };
+                let stats_str = task.stats.read().map(|s| s.summary(running_for)).unwrap_or_default();
+
+                let mut details = vec![
                     Line::from(vec![
                         Span::styled("● NAME:    ", Style::default().fg(Color::Cyan)),
                         Span::raw(&task.desc.name),
@@ -463,26 +1086,67 @@ impl TaskControlComponent {
                         Span::styled("● ARGS:    ", Style::default().fg(Color::Cyan)),
                         Span::raw(format!("{:?}", task.desc.args)),
                     ]),
+                    Line::from(vec![
+                        Span::styled("● STATS:   ", Style::default().fg(Color::Cyan)),
+                        Span::raw(stats_str),
+                    ]),
+                ];
+                if !children.is_empty() {
+                    let list: Vec<String> = children.iter().map(|p| p.to_string()).collect();
+                    details.push(Line::from(vec![
+                        Span::styled("● CHILDREN:", Style::default().fg(Color::Cyan)),
+                        Span::raw(format!(" {}", list.join(", "))),
+                    ]));
+                }
+                details.extend([
                     Line::from(""),
                     Line::from(Span::styled(
-                        " [x] Start/Stop   [Enter] View Logs   [↑/↓] Navigate ",
+                        " [x] Start/Stop   [R] Restart   [Enter] View Logs   [Ctrl+E] Edit   [C] Rerun script   [/] Jump   [↑/↓] Navigate ",
                         Style::default().bg(Color::Blue).fg(Color::White),
                     )),
-                ];
+                ]);
+
+                // 编辑模式下在详情面板底部显示输入框
+                let (detail_area, edit_area) = if self.view_mode == ViewMode::Edit {
+                    let c = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).split(*a);
+                    (c[0], Some(c[1]))
+                } else {
+                    (*a, None)
+                };
                 f.render_widget(
                     Paragraph::new(details).block(
                         Block::default()
                             .borders(Borders::ALL)
                             .title(" 📋 Task Detail "),
                     ),
-                    *a,
+                    detail_area,
                 );
+                if let Some(e) = edit_area {
+                    f.render_widget(
+                        Paragraph::new(self.input.as_str())
+                            .style(Style::default().fg(Color::Yellow))
+                            .block(
+                                Block::default()
+                                    .borders(Borders::ALL)
+                                    .title(" Edit Command (Enter to Apply, Esc to Cancel) "),
+                            ),
+                        e,
+                    );
+                    f.set_cursor_position((e.x + self.input.chars().count() as u16 + 1, e.y + 1));
+                }
             }
         }
     }
 
     // --- 操作修改：按键映射 ---
     fn handle_list_keys(&mut self, key: KeyEvent) -> bool {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('e') {
+            self.begin_edit();
+            return true;
+        }
+        if self.handle_jump_key(key) {
+            return true;
+        }
         match key.code {
             KeyCode::Char('j') | KeyCode::Down => {
                 self.selected_idx = (self.selected_idx + 1) % self.tasks.len();
@@ -497,7 +1161,27 @@ impl TaskControlComponent {
             }
             // 修改：按下 x 启动或终止
             KeyCode::Char('x') => {
-                self.start_or_stop_task(self.selected_idx);
+                // 停止运行中的任务属于破坏性操作，经 App 统一确认
+                if self.is_running(self.selected_idx) {
+                    let desc = &self.tasks[self.selected_idx].desc;
+                    GlobIO::confirm(
+                        format!("Stop task '{}'?", desc.name),
+                        GlobalEvent::Task(TaskRequest::Stop(desc.id.clone())),
+                    );
+                } else {
+                    let idx = self.selected_idx;
+                    // 手动启动视为重新开始，清除 flapping 计数
+                    self.tasks[idx].quick_exits.store(0, Ordering::Relaxed);
+                    self.start_or_stop_task(idx);
+                    self.focus_started(idx);
+                }
+                true
+            }
+            KeyCode::Char('R') => {
+                let idx = self.selected_idx;
+                if idx < self.tasks.len() {
+                    self.restart_task(idx);
+                }
                 true
             }
             // 修改：按下 Enter 查看日志
@@ -505,10 +1189,79 @@ impl TaskControlComponent {
                 self.view_mode = ViewMode::Log;
                 true
             }
+            // 导出可手动执行的命令 (cd + env + 参数引用)
+            KeyCode::Char('C') => {
+                if let Some(task) = self.tasks.get(self.selected_idx) {
+                    match write_rerun_script(&task.desc) {
+                        Ok(path) => GlobIO::success(format!("Command written to {}", path.display())),
+                        Err(e) => GlobIO::error(format!("Failed to write {}: {}", RERUN_SCRIPT, e)),
+                    }
+                }
+                true
+            }
             _ => false,
         }
     }
-    fn handle_log_keys(&mut self, key: KeyEvent) -> bool {
+    fn jump_active(&self) -> bool {
+        self.jumping && self.jump_at.elapsed().as_millis() < JUMP_TIMEOUT_MS as u128
+    }
+
+    /// type-to-jump：'/' 开始，之后输入的字符按名称前缀 (忽略开头的图标，不区分大小写) 移动选中项，
+    /// 不过滤列表。不带前缀键的字母照常作为快捷键 (j/k/x 以及全局的 n/h 等)；
+    /// Enter / Esc 或 JUMP_TIMEOUT_MS 内无输入时退出
+    fn handle_jump_key(&mut self, key: KeyEvent) -> bool {
+        let plain = key.modifiers.difference(KeyModifiers::SHIFT).is_empty();
+        if !self.jump_active() {
+            if plain && key.code == KeyCode::Char('/') {
+                self.jumping = true;
+                self.jump_buffer.clear();
+                self.jump_at = std::time::Instant::now();
+                return true;
+            }
+            self.jumping = false;
+            return false;
+        }
+        self.jump_at = std::time::Instant::now();
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter => self.jumping = false,
+            KeyCode::Backspace => {
+                self.jump_buffer.pop();
+            }
+            KeyCode::Char(c) if plain && !c.is_control() => {
+                self.jump_buffer.push(c);
+                // 没有匹配时保持当前选中
+                if let Some(idx) = find_by_prefix(&self.tasks, &self.jump_buffer) {
+                    self.selected_idx = idx;
+                }
+            }
+            // 其它按键 (方向键等) 结束跳转后照常处理
+            _ => {
+                self.jumping = false;
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Ctrl+E：把选中任务的 command + args 载入输入缓冲区，运行中的任务必须先停止
+    fn begin_edit(&mut self) {
+        if self.tasks.is_empty() {
+            return;
+        }
+        if self.is_running(self.selected_idx) {
+            GlobIO::warn("Stop the task before editing its command");
+            return;
+        }
+        let desc = &self.tasks[self.selected_idx].desc;
+        self.input = std::iter::once(&desc.command)
+            .chain(desc.args.iter())
+            .map(|s| quote_arg(s))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.view_mode = ViewMode::Edit;
+    }
+
+    fn handle_edit_keys(&mut self, key: KeyEvent) -> bool {
         match key.code {
             KeyCode::Esc => {
                 self.view_mode = ViewMode::List;
@@ -516,18 +1269,180 @@ impl TaskControlComponent {
                 true
             }
             KeyCode::Enter => {
-                if !self.input.is_empty() {
-                    if let Some(task) = self.tasks.get(self.selected_idx) {
-                        if let Some(tx) = &task.control_tx {
-                            // 发送给进程
-                            let _ = tx.try_send(TaskControlMsg::Stdin(self.input.clone()));
-                            // 同时把输入的内容也显示在日志里，方便确认
-                            if let Ok(mut l) = task.logs.write() {
-                                l.push_back(format!(">>> {}", self.input));
-                            }
+                // 编辑期间任务可能被自动重启或延迟启动
+                if self.is_running(self.selected_idx) {
+                    GlobIO::warn("Task is running, edit discarded");
+                } else {
+                    let mut parts = split_args(&self.input).into_iter();
+                    match parts.next() {
+                        Some(command) => {
+                            let desc = &mut self.tasks[self.selected_idx].desc;
+                            desc.command = command;
+                            desc.args = parts.collect();
+                            GlobIO::info(format!("Command updated: {}", desc.name));
                         }
+                        None => GlobIO::warn("Command cannot be empty"),
                     }
-                    self.input.clear();
+                }
+                self.view_mode = ViewMode::List;
+                self.input.clear();
+                true
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                true
+            }
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// 大小写不敏感的子串搜索，返回命中的日志行序号
+    fn find_log_matches(&self, query: &str) -> Vec<u64> {
+        let Some(task) = self.tasks.get(self.selected_idx) else {
+            return vec![];
+        };
+        let query = query.to_lowercase();
+        if query.is_empty() {
+            return vec![];
+        }
+        task.logs
+            .read()
+            .map(|logs| {
+                logs.iter()
+                    .filter(|l| strip_ansi(&l.text).to_lowercase().contains(&query))
+                    .map(|l| l.seq)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 去掉已经随日志截断而丢弃的匹配，current 跟随原来的那一项
+    fn prune_matches(&mut self) {
+        let (Some(search), Some(task)) = (self.search.as_mut(), self.tasks.get(self.selected_idx)) else {
+            return;
+        };
+        let Some(first) = task.logs.read().ok().and_then(|l| l.front().map(|l| l.seq)) else {
+            return;
+        };
+        let dropped = search.matches.partition_point(|&seq| seq < first);
+        if dropped > 0 {
+            search.matches.drain(..dropped);
+            search.current = search.current.saturating_sub(dropped);
+        }
+    }
+
+    /// 跳到第 n 个匹配 (循环)，并把对应行滚动到顶部
+    fn jump_to_match(&mut self, forward: bool) {
+        self.prune_matches();
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        if search.matches.is_empty() {
+            return;
+        }
+        let len = search.matches.len();
+        search.current = if forward {
+            (search.current + 1) % len
+        } else {
+            (search.current + len - 1) % len
+        };
+        let seq = search.matches[search.current];
+        let idx = self
+            .tasks
+            .get(self.selected_idx)
+            .and_then(|t| t.logs.read().ok().and_then(|l| log_index(&l, seq)));
+        if let Some(idx) = idx {
+            self.log_scroll = idx.min(u16::MAX as usize) as u16;
+        }
+    }
+
+    fn handle_search_keys(&mut self, key: KeyEvent) -> bool {
+        let query = match &self.search {
+            Some(s) => s.query.clone(),
+            None => return false,
+        };
+        match key.code {
+            KeyCode::Esc => self.search = None,
+            KeyCode::Enter => {
+                let matches = self.find_log_matches(&query);
+                if matches.is_empty() {
+                    GlobIO::info(format!("No match for '{}'", query));
+                }
+                // current 置于末尾，jump_to_match 循环后正好落在第一个匹配
+                let current = matches.len().saturating_sub(1);
+                self.search = Some(LogSearch { query, editing: false, matches, current });
+                self.jump_to_match(true);
+            }
+            KeyCode::Backspace => {
+                if let Some(s) = self.search.as_mut() {
+                    s.query.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(s) = self.search.as_mut() {
+                    s.query.push(c);
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    fn handle_log_keys(&mut self, key: KeyEvent) -> bool {
+        if self.search.as_ref().is_some_and(|s| s.editing) {
+            return self.handle_search_keys(key);
+        }
+        if self.pipe_prompt {
+            return self.handle_pipe_keys(key);
+        }
+        // stdin 输入框为空时 '/' 'n' 'N' 'f' 作为功能按键，否则照常输入
+        if self.input.is_empty() {
+            match key.code {
+                KeyCode::Char('f') => {
+                    self.pipe_prompt = true;
+                    return true;
+                }
+                KeyCode::Char('/') => {
+                    self.search = Some(LogSearch { editing: true, ..Default::default() });
+                    return true;
+                }
+                KeyCode::Char('n') if self.search.is_some() => {
+                    self.jump_to_match(true);
+                    return true;
+                }
+                KeyCode::Char('N') if self.search.is_some() => {
+                    self.jump_to_match(false);
+                    return true;
+                }
+                _ => {}
+            }
+        }
+        match key.code {
+            KeyCode::Esc => {
+                // 先清除搜索，再退出日志视图
+                if self.search.take().is_none() {
+                    self.view_mode = ViewMode::List;
+                    self.input.clear();
+                }
+                true
+            }
+            KeyCode::Enter => {
+                if !self.input.is_empty() {
+                    if let Some(task) = self.tasks.get(self.selected_idx) {
+                        if let Some(tx) = &task.control_tx {
+                            // 发送给进程
+                            let _ = tx.try_send(TaskControlMsg::Stdin(self.input.clone()));
+                            // 同时把输入的内容也显示在日志里，方便确认
+                            if let Ok(mut l) = task.logs.write() {
+                                l.push_back(LogLine::new(LogSource::Atlas, format!(">>> {}", self.input)));
+                            }
+                        }
+                    }
+                    self.input.clear();
                 }
                 true
             }
@@ -551,43 +1466,124 @@ impl TaskControlComponent {
             _ => false,
         }
     }
+    /// 'f' 打开的文件路径输入：Enter 开始写入，Esc 取消
+    fn handle_pipe_keys(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Esc => {
+                self.pipe_prompt = false;
+                self.input.clear();
+            }
+            KeyCode::Enter => {
+                let path = std::mem::take(&mut self.input);
+                self.pipe_prompt = false;
+                if let Some(task) = self.tasks.get(self.selected_idx) {
+                    pipe_file_to_stdin(task, path.trim());
+                }
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Char(c) => self.input.push(c),
+            _ => return false,
+        }
+        true
+    }
+
     fn render_full_log(&mut self, f: &mut Frame, area: Rect) {
         // 划分布局：上方是日志，下方是 3 行高度的输入框
         let chunks = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).split(area);
 
+        // 滚动位置不能超过日志末尾
+        let log_len = self
+            .tasks
+            .get(self.selected_idx)
+            .and_then(|t| t.logs.read().ok().map(|l| l.len()))
+            .unwrap_or(0);
+        self.log_scroll = self.log_scroll.min(log_len.saturating_sub(1).min(u16::MAX as usize) as u16);
+        // 匹配数 "n/m" 只统计仍在缓冲区中的行
+        self.prune_matches();
+
         if let Some(task) = self.tasks.get(self.selected_idx) {
             // 1. 渲染日志 (上方)
             if let Ok(logs) = task.logs.read() {
-                let all_logs = logs.iter().cloned().collect::<Vec<_>>().join("\n");
+                let (stderr_color, plain) = self
+                    .config
+                    .try_read()
+                    .map(|c| (c.stderr_color, c.strip_ansi))
+                    .unwrap_or((AppColor::Red, false));
+                let stderr_color = stderr_color.to_ratatui_color();
+
+                let query = self
+                    .search
+                    .as_ref()
+                    .filter(|s| !s.matches.is_empty())
+                    .map(|s| s.query.to_lowercase());
+                let current = self
+                    .search
+                    .as_ref()
+                    .and_then(|s| s.matches.get(s.current).copied());
 
-                // 使用 ansi_to_tui 将其解析为 Ratatui 的 Text 对象
-                // 如果解析失败，回退到普通字符串显示
-                let text = all_logs.into_text().unwrap_or_else(|_| Text::raw(all_logs));
+                // 只解析可见窗口 (外加少量余量) 内的行，避免每帧对整个缓冲区做 ANSI 解析；
+                // log_scroll 是完整缓冲区中的下标
+                let start = self.log_scroll as usize;
+                let window = chunks[0].height.saturating_sub(2) as usize + LOG_RENDER_MARGIN;
+                let mut lines: Vec<Line> = Vec::with_capacity(window);
+                for log in logs.iter().skip(start).take(window) {
+                    // 命中搜索的行：去掉原有颜色，只高亮匹配部分
+                    if let Some(q) = &query
+                        && let Some(line) = highlight_match(&strip_ansi(&log.text), q, current == Some(log.seq))
+                    {
+                        lines.push(line);
+                        continue;
+                    }
+                    match log.source {
+                        LogSource::Stdout => lines.extend(stdout_lines(&log.text, plain)),
+                        // stderr 无论自带什么颜色，一律用统一颜色标出
+                        LogSource::Stderr => lines.push(Line::styled(
+                            strip_ansi(&log.text),
+                            Style::default().fg(stderr_color),
+                        )),
+                        LogSource::Atlas => lines.push(Line::styled(
+                            log.text.clone(),
+                            Style::default().fg(Color::Yellow),
+                        )),
+                    }
+                }
+                let text = Text::from(lines);
 
                 f.render_widget(
                     Paragraph::new(text)
                         .block(
                             Block::default()
                                 .borders(Borders::ALL)
-                                .title(format!(" Logs: {} ", task.desc.name)),
-                        )
-                        .scroll((self.log_scroll, 0)),
+                                .title(format!(" Logs: {}{} ", task.desc.name, self.search_title())),
+                        ),
                     chunks[0],
                 );
             }
 
-            // 2. 渲染输入框 (下方)
-            let input_block = Paragraph::new(self.input.as_str())
+            // 2. 渲染输入框 (下方)，搜索输入时复用同一位置
+            let (text, title) = match &self.search {
+                Some(s) if s.editing => (format!("/{}", s.query), " Search (Enter to Find, Esc to Cancel) "),
+                _ if self.pipe_prompt => (self.input.clone(), " Pipe file to stdin: path (Enter to Send, Esc to Cancel) "),
+                _ => (self.input.clone(), " Stdin (Press Enter to Send, 'f' to pipe a file) "),
+            };
+            let input_block = Paragraph::new(text.as_str())
                 .style(Style::default().fg(Color::Yellow))
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title(" Stdin (Press Enter to Send) "),
-                );
+                .block(Block::default().borders(Borders::ALL).title(title));
             f.render_widget(input_block, chunks[1]);
 
             // 设置光标位置，使其看起来像个真正的输入框
-            f.set_cursor_position((chunks[1].x + self.input.len() as u16 + 1, chunks[1].y + 1));
+            f.set_cursor_position((chunks[1].x + text.chars().count() as u16 + 1, chunks[1].y + 1));
+        }
+    }
+
+    fn search_title(&self) -> String {
+        match &self.search {
+            None => String::new(),
+            Some(s) if s.editing || s.query.is_empty() => String::new(),
+            Some(s) if s.matches.is_empty() => format!(" | /{} no match", s.query),
+            Some(s) => format!(" | /{} match {}/{} (n/N)", s.query, s.current + 1, s.matches.len()),
         }
     }
 
@@ -609,3 +1605,1025 @@ impl TaskControlComponent {
         }
     }
 }
+
+/// 按字节读取一行并有损转换为 UTF-8：非法字节变成 U+FFFD 而不是让 lines() 报错终止读取。
+/// 读到 EOF 或管道出错时返回 None。
+/// buf 只在取出完整一行后清空：select! 取消时已读到的半行留在 buf 中，下次继续拼接
+async fn next_line_lossy<R: tokio::io::AsyncBufRead + Unpin>(reader: &mut R, buf: &mut Vec<u8>) -> Option<String> {
+    match reader.read_until(b'\n', buf).await {
+        Ok(0) | Err(_) if buf.is_empty() => None,
+        _ => {
+            let line = buf.strip_suffix(b"\n").unwrap_or(buf);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            let line = String::from_utf8_lossy(line).into_owned();
+            buf.clear();
+            Some(line)
+        }
+    }
+}
+
+/// stdout 保留程序自身的 ANSI 颜色：用 ansi_to_tui 解析为 Ratatui 的行，
+/// 解析失败或 plain (Config.strip_ansi) 时去掉转义序列显示纯文本
+fn stdout_lines(text: &str, plain: bool) -> Vec<Line<'static>> {
+    if !plain && let Ok(t) = text.into_text() {
+        return t.lines;
+    }
+    vec![Line::raw(strip_ansi(text))]
+}
+
+/// PATH 中是否存在该程序
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn on_path(program: &str) -> bool {
+    std::env::var_os("PATH").is_some_and(|paths| std::env::split_paths(&paths).any(|d| d.join(program).is_file()))
+}
+
+/// 去除 ANSI 转义序列，只保留文字内容。直接扫描而不经过 ansi_to_tui 解析，
+/// 残缺或不认识的序列也能去掉 (CSI 到结束字节为止，OSC 到 BEL/ST 为止，其余 ESC 连同下一个字符)
+fn strip_ansi(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                // 参数与中间字节之后以 0x40..=0x7E 结束
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' || (c == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            // nF 序列 (如 tput sgr0 输出的 ESC ( B)：中间字节 0x20..=0x2F 之后还有一个结束字节
+            Some('\x20'..='\x2f') => {
+                while chars.next_if(|c| ('\x20'..='\x2f').contains(c)).is_some() {}
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// POSIX sh 引用：只含安全字符时原样返回，否则整体单引号包裹，内部的 ' 写成 '\''
+fn shell_quote(s: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "-_./=:,+@%".contains(c);
+    if !s.is_empty() && s.chars().all(safe) {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+/// 后台读取文件并逐行发送给任务的 stdin，完成后在日志中记录 `>>> [piped N lines from path]`；
+/// 非 UTF-8 内容 (二进制文件) 直接拒绝
+fn pipe_file_to_stdin(task: &TaskRuntime, path: &str) {
+    let Some(tx) = task.control_tx.clone().filter(|tx| !tx.is_closed()) else {
+        GlobIO::warn(format!("Task '{}' is not running", task.desc.name));
+        return;
+    };
+    let path = match path.strip_prefix("~/") {
+        Some(rest) => AtlasPath::get().home_dir.join(rest),
+        None => std::path::PathBuf::from(path),
+    };
+    let logs = task.logs.clone();
+    tokio::spawn(async move {
+        let text = match tokio::fs::read(&path).await {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(t) => t,
+                Err(_) => return GlobIO::warn(format!("{} is not UTF-8 text, not piped", path.display())),
+            },
+            Err(e) => return GlobIO::warn(format!("Cannot read {}: {}", path.display(), e)),
+        };
+        let mut sent = 0;
+        for line in text.lines() {
+            if tx.send(TaskControlMsg::Stdin(line.to_string())).await.is_err() {
+                break; // 任务中途退出
+            }
+            sent += 1;
+            tokio::time::sleep(std::time::Duration::from_millis(PIPE_LINE_DELAY_MS)).await;
+        }
+        if let Ok(mut l) = logs.write() {
+            l.push_back(LogLine::new(
+                LogSource::Atlas,
+                format!(">>> [piped {} lines from {}]", sent, path.display()),
+            ));
+        }
+        let _ = GlobIO::send().send(GlobalEvent::Data { key: "rend", data: DynamicPayload(Arc::new(())) });
+    });
+}
+
+/// 列表窗口的首行：选中项移出窗口时最少量地滚动，使其刚好可见；
+/// 任务减少或窗口变高时收回多余的空白
+fn visible_window(selected: usize, offset: usize, height: usize, len: usize) -> usize {
+    if height == 0 || len == 0 {
+        return 0;
+    }
+    let offset = if selected < offset {
+        selected
+    } else if selected >= offset + height {
+        selected + 1 - height
+    } else {
+        offset
+    };
+    offset.min(len.saturating_sub(height))
+}
+
+/// 按 Config.task_group_order 稳定排序 (列表下标即 selected_idx，因此在加载时排好而不是渲染时)；
+/// 未列出的分组排在后面并按字母序，配置为空时不改变顺序
+fn sort_by_group_order(descs: &mut [TaskDescriptor], order: &[String]) {
+    if order.is_empty() {
+        return;
+    }
+    descs.sort_by_cached_key(|d| {
+        match order.iter().position(|g| g.eq_ignore_ascii_case(&d.group)) {
+            Some(pos) => (pos, String::new()),
+            None => (order.len(), d.group.to_lowercase()),
+        }
+    });
+}
+
+/// 'C'：写出 <cache>/rerun.sh 供手动执行 (无剪贴板支持)，返回文件路径
+fn write_rerun_script(desc: &TaskDescriptor) -> std::io::Result<std::path::PathBuf> {
+    let dir = &AtlasPath::get().cache_dir;
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(RERUN_SCRIPT);
+    let body = format!("#!/bin/sh\n# {} ({})\n{}\n", desc.name, desc.id, desc.to_shell_command());
+    std::fs::write(&path, body)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(path)
+}
+
+/// 含空白、引号或反斜杠的参数加双引号 (内部的 `"` `\` 转义)，便于在单行输入框里编辑；
+/// split_args 可原样还原
+fn quote_arg(s: &str) -> String {
+    if !s.is_empty() && !s.chars().any(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '\\')) {
+        s.to_string()
+    } else {
+        format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+/// 按空白拆分命令行，支持单/双引号包裹的参数。
+/// 双引号内 `\"` `\\` 为转义，引号外反斜杠可转义引号、反斜杠和空白，其它情况按字面保留 (如 `C:\dir`)；
+/// 单引号内不转义
+fn split_args(line: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut cur = String::new();
+    let mut quote: Option<char> = None;
+    let mut has_token = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\\' && quote != Some('\'') {
+            let escapable = |n: &char| match quote {
+                Some(_) => matches!(n, '"' | '\\'),
+                None => matches!(n, '"' | '\'' | '\\') || n.is_whitespace(),
+            };
+            cur.push(chars.next_if(escapable).unwrap_or(c));
+            has_token = true;
+            continue;
+        }
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => cur.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                has_token = true;
+            }
+            None if c.is_whitespace() => {
+                if has_token {
+                    out.push(std::mem::take(&mut cur));
+                    has_token = false;
+                }
+            }
+            None => {
+                cur.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        out.push(cur);
+    }
+    out
+}
+
+#[cfg(unix)]
+type MergedOutput = tokio::net::unix::pipe::Receiver;
+#[cfg(not(unix))]
+type MergedOutput = tokio::process::ChildStdout;
+
+/// 让子进程的 stdout 和 stderr 写入同一个管道 (2>&1 语义)，由内核保证写入顺序；
+/// 返回读端，创建失败时返回 None 且不修改 cmd
+#[cfg(unix)]
+fn merge_stderr_into_stdout(cmd: &mut tokio::process::Command) -> Option<MergedOutput> {
+    let pipe = std::io::pipe().and_then(|(reader, writer)| {
+        let writer_err = writer.try_clone()?;
+        let reader = tokio::net::unix::pipe::Receiver::from_owned_fd(reader.into())?;
+        Ok((reader, writer, writer_err))
+    });
+    match pipe {
+        Ok((reader, writer, writer_err)) => {
+            cmd.stdout(Stdio::from(writer)).stderr(Stdio::from(writer_err));
+            Some(reader)
+        }
+        Err(e) => {
+            log::warn!("Failed to create merged output pipe, keeping separate streams: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn merge_stderr_into_stdout(_cmd: &mut tokio::process::Command) -> Option<MergedOutput> {
+    None
+}
+
+/// 结束整个进程组 (任务以 process_group(0) 启动，pgid == pid)。
+/// 无 libc 依赖，借助系统 kill 命令发送 SIGKILL
+async fn kill_process_group(pid: u32) {
+    #[cfg(unix)]
+    {
+        let _ = tokio::process::Command::new("kill")
+            .args(["-KILL", "--", &format!("-{pid}")])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await;
+    }
+    #[cfg(not(unix))]
+    let _ = pid;
+}
+
+/// 递归列出进程的所有后代 PID (读取 /proc/<pid>/task/*/children，仅 Linux/Android)
+fn descendant_pids(pid: u32) -> Vec<u32> {
+    let mut out = Vec::new();
+    let mut stack = vec![pid];
+    while let Some(p) = stack.pop() {
+        let Ok(tasks) = std::fs::read_dir(format!("/proc/{p}/task")) else { continue };
+        for t in tasks.flatten() {
+            let Ok(s) = std::fs::read_to_string(t.path().join("children")) else { continue };
+            for child in s.split_whitespace().filter_map(|c| c.parse::<u32>().ok()) {
+                if !out.contains(&child) {
+                    out.push(child);
+                    stack.push(child);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// 第一个名称以 query 开头的任务 (跳过名称开头的图标等非字母数字字符，不区分大小写)
+fn find_by_prefix(tasks: &[TaskRuntime], query: &str) -> Option<usize> {
+    let query = query.to_lowercase();
+    tasks.iter().position(|t| {
+        t.desc
+            .name
+            .trim_start_matches(|c: char| !c.is_alphanumeric())
+            .to_lowercase()
+            .starts_with(&query)
+    })
+}
+
+/// 非正常退出的描述：区分退出码与信号 (SIGKILL 多为 OOM，SIGSEGV 为崩溃)
+fn describe_exit(status: &std::process::ExitStatus) -> String {
+    if let Some(code) = status.code() {
+        return format!("Exit Code: {}", code);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if let Some(sig) = status.signal() {
+            return match signal_name(sig) {
+                Some(name) => format!("Killed by {} ({})", name, sig),
+                None => format!("Killed by signal {}", sig),
+            };
+        }
+    }
+    "Killed by signal".into()
+}
+
+#[cfg(unix)]
+fn signal_name(sig: i32) -> Option<&'static str> {
+    Some(match sig {
+        1 => "SIGHUP",
+        2 => "SIGINT",
+        3 => "SIGQUIT",
+        4 => "SIGILL",
+        5 => "SIGTRAP",
+        6 => "SIGABRT",
+        7 => "SIGBUS",
+        8 => "SIGFPE",
+        9 => "SIGKILL",
+        11 => "SIGSEGV",
+        13 => "SIGPIPE",
+        14 => "SIGALRM",
+        15 => "SIGTERM",
+        _ => return None,
+    })
+}
+
+/// 高亮 text 中所有 (小写) query 的出现位置；未命中返回 None
+fn highlight_match(text: &str, query: &str, is_current: bool) -> Option<Line<'static>> {
+    let lower = text.to_lowercase();
+    if !lower.contains(query) {
+        return None;
+    }
+    let hit = Style::default().bg(Color::Yellow).fg(Color::Black);
+    // 小写化改变了字节长度 (少数 Unicode 字符) 时无法对齐下标，整行高亮
+    if lower.len() != text.len() {
+        return Some(Line::styled(text.to_string(), hit));
+    }
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for (start, m) in lower.match_indices(query) {
+        spans.push(Span::raw(text[pos..start].to_string()));
+        spans.push(Span::styled(text[start..start + m.len()].to_string(), hit));
+        pos = start + m.len();
+    }
+    spans.push(Span::raw(text[pos..].to_string()));
+    let line = Line::from(spans);
+    Some(if is_current {
+        line.patch_style(Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED))
+    } else {
+        line
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn plain_text(lines: &[Line]) -> String {
+        lines.iter().flat_map(|l| l.spans.iter().map(|s| s.content.as_ref())).collect()
+    }
+
+    #[test]
+    fn strip_ansi_removes_csi_osc_and_nf_sequences() {
+        assert_eq!(strip_ansi("\x1b[1;31mred\x1b[0m"), "red");
+        assert_eq!(strip_ansi("\x1b]0;title\x07text"), "text");
+        assert_eq!(strip_ansi("\x1b]8;;http://x\x1b\\link\x1b]8;;\x1b\\"), "link");
+        // tput sgr0 = ESC ( B ESC [ m
+        assert_eq!(strip_ansi("\x1b[32mok\x1b(B\x1b[m done"), "ok done");
+        assert_eq!(strip_ansi("a\x1b=b"), "ab");
+    }
+
+    #[test]
+    fn malformed_ansi_falls_back_to_plain_text() {
+        for raw in ["\x1b[31mred\x1b[", "\x1b[38;5mbad\x1b]unterminated", "ok\x1b"] {
+            let text = plain_text(&stdout_lines(raw, false));
+            assert!(!text.contains('\x1b'), "escape leaked for {:?}: {:?}", raw, text);
+        }
+        assert_eq!(plain_text(&stdout_lines("\x1b[31mred\x1b[0m", true)), "red");
+    }
+
+    fn desc(id: &str, command: &str, args: &[&str]) -> TaskDescriptor {
+        TaskDescriptor {
+            id: id.to_string(),
+            name: id.to_string(),
+            command: command.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            cwd: None,
+            envs: None,
+            autostart: false,
+            group: "Test".to_string(),
+            log_limit: None,
+            restart_policy: Some(RestartPolicy::Never),
+            autostart_delay_ms: None,
+            nice: None,
+            ionice_class: None,
+        }
+    }
+
+    fn component(descs: Vec<TaskDescriptor>) -> TaskControlComponent {
+        component_with(Config::default(), descs)
+    }
+
+    fn component_with(config: Config, descs: Vec<TaskDescriptor>) -> TaskControlComponent {
+        GlobIO::init_for_test();
+        TaskControlComponent::with_tasks(Arc::new(ARwLock::new(config)), descs)
+    }
+
+    /// 轮询直到条件成立，最多约 3 秒
+    async fn wait_until(mut done: impl FnMut() -> bool) -> bool {
+        for _ in 0..150 {
+            if done() {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn safe_mode_start_spawns_nothing() {
+        Config::set_safe_mode_for_test(true);
+        let marker = std::env::temp_dir().join(format!("atlas_safe_mode_{}", std::process::id()));
+        let _ = std::fs::remove_file(&marker);
+        let touch = format!("touch '{}'", marker.display());
+        let mut comp = component(vec![desc("t", "sh", &["-c", &touch]), desc("auto", "sh", &["-c", &touch])]);
+        comp.tasks[1].desc.autostart = true;
+
+        comp.start_or_stop_task(0);
+        comp.handle_task_request(TaskRequest::Start { id: "t".into(), focus: true });
+        comp.auto_start_tasks();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+
+        for task in &comp.tasks {
+            assert!(matches!(*task.status.read().unwrap(), TaskStatus::Stopped));
+            assert!(task.control_tx.is_none());
+        }
+        assert!(!marker.exists(), "safe mode must not spawn a child");
+    }
+
+    #[tokio::test]
+    async fn staggered_autostart_does_not_start_tasks_together() {
+        let config = Config { autostart_stagger_ms: 400, ..Default::default() };
+        let mut comp = component_with(config, vec![desc("stagger_a", "sleep", &["5"]), desc("stagger_b", "sleep", &["5"])]);
+        for task in comp.tasks.iter_mut() {
+            task.desc.autostart = true;
+        }
+
+        comp.auto_start_tasks();
+        assert!(wait_until(|| comp.is_running(0)).await);
+        comp.drain();
+        assert!(!comp.is_running(1), "second task must wait for the stagger delay");
+        assert!(
+            wait_until(|| {
+                comp.drain();
+                comp.is_running(1)
+            })
+            .await
+        );
+    }
+
+    #[test]
+    fn deno_args_come_from_config_or_script_meta() {
+        let dir = std::env::temp_dir().join(format!("atlas_deno_args_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("hello.ts");
+        let path = script.display().to_string();
+        let flags = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            deno_script_args(&script, &Config::default().deno_args),
+            ["run", "-A", "--unstable-kv", "--unstable-cron", path.as_str()]
+        );
+        // 模板没有 {script} 时脚本路径追加在末尾
+        assert_eq!(deno_script_args(&script, &flags(&["run", "--allow-net"])), ["run", "--allow-net", path.as_str()]);
+        assert_eq!(
+            deno_script_args(&script, &flags(&["run", "{script}", "--port=8080"])),
+            ["run", path.as_str(), "--port=8080"]
+        );
+
+        // 同名 .atlas.json 优先于配置
+        std::fs::write(dir.join("hello.atlas.json"), r#"{"args": ["run", "--allow-read", "{script}"]}"#).unwrap();
+        assert_eq!(deno_script_args(&script, &flags(&["run", "-A"])), ["run", "--allow-read", path.as_str()]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[tokio::test]
+    async fn stopping_a_task_reaps_its_background_children() {
+        // 僵尸进程已经退出，只是等待父进程回收
+        let alive = |pid: u32| {
+            std::fs::read_to_string(format!("/proc/{}/stat", pid))
+                .is_ok_and(|s| s.rsplit(") ").next().is_some_and(|rest| !rest.starts_with('Z')))
+        };
+        let mut comp = component(vec![desc("tree", "sh", &["-c", "sleep 30 & echo $!; wait"])]);
+        comp.start_or_stop_task(0);
+        let logs = comp.tasks[0].logs.clone();
+        assert!(wait_until(|| !logs.read().unwrap().is_empty()).await);
+        let child: u32 = logs.read().unwrap()[0].text.trim().parse().expect("background pid");
+        assert!(alive(child));
+        // 内核提供 /proc/<pid>/task/*/children 时，子进程树中能看到它
+        if std::path::Path::new("/proc/self/task").join(std::process::id().to_string()).join("children").exists() {
+            let TaskStatus::Running { pid, .. } = *comp.tasks[0].status.read().unwrap() else { panic!("not running") };
+            assert!(descendant_pids(pid).contains(&child));
+        }
+
+        comp.start_or_stop_task(0);
+        assert!(wait_until(|| !comp.is_running(0)).await);
+        assert!(wait_until(|| !alive(child)).await, "background child {} survived the stop", child);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn signal_exit_names_the_signal() {
+        let run = |script: &str| std::process::Command::new("sh").args(["-c", script]).status().expect("run sh");
+        assert_eq!(describe_exit(&run("kill -KILL $$")), "Killed by SIGKILL (9)");
+        assert_eq!(describe_exit(&run("kill -TERM $$")), "Killed by SIGTERM (15)");
+        // 表外的信号只显示编号 (wait status 低 7 位即信号值)
+        use std::os::unix::process::ExitStatusExt;
+        assert_eq!(describe_exit(&std::process::ExitStatus::from_raw(40)), "Killed by signal 40");
+        assert_eq!(describe_exit(&run("exit 3")), "Exit Code: 3");
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[test]
+    fn invalid_ionice_class_is_dropped() {
+        let mut d = desc("io", "true", &[]);
+        d.ionice_class = Some(7);
+        assert_eq!(d.spawn_argv(), ("true".to_string(), vec![]));
+        d.ionice_class = Some(3);
+        let (program, args) = d.spawn_argv();
+        if on_path("ionice") {
+            assert_eq!((program.as_str(), args), ("ionice", vec!["-c".into(), "3".into(), "true".into()]));
+        } else {
+            assert_eq!(program, "true");
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[tokio::test]
+    async fn niced_task_runs_with_lowered_priority() {
+        let mut d = desc("niced", "sleep", &["2"]);
+        d.nice = Some(7);
+        let (program, args) = d.spawn_argv();
+        let mut child = tokio::process::Command::new(program).args(args).spawn().expect("spawn nice");
+        let pid = child.id().expect("pid");
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).expect("read stat");
+        let _ = child.kill().await;
+        // comm 可能含空格，从最后一个 ')' 之后按字段取：第 19 个字段是 nice
+        let fields: Vec<&str> = stat[stat.rfind(')').unwrap() + 2..].split(' ').collect();
+        let base = std::fs::read_to_string("/proc/self/stat").unwrap();
+        let own: Vec<&str> = base[base.rfind(')').unwrap() + 2..].split(' ').collect();
+        let expected = (own[16].parse::<i32>().unwrap() + 7).min(19);
+        assert_eq!(fields[16].parse::<i32>().unwrap(), expected);
+    }
+
+    #[test]
+    fn search_matches_survive_log_trimming() {
+        let mut comp = component(vec![desc("t", "true", &[])]);
+        comp.view_mode = ViewMode::Log;
+        let logs = comp.tasks[0].logs.clone();
+        let push = |text: &str| logs.write().unwrap().push_back(LogLine::new(LogSource::Stdout, text));
+        for text in ["a", "hit 1", "b", "hit 2", "c"] {
+            push(text);
+        }
+        comp.search = Some(LogSearch { query: "hit".into(), editing: true, ..Default::default() });
+        comp.handle_search_keys(KeyEvent::from(KeyCode::Enter));
+        assert_eq!(comp.log_scroll, 1);
+
+        // 超出 log_limit 后头部两行被丢弃，第一个匹配随之消失，第二个匹配的下标前移
+        {
+            let mut l = logs.write().unwrap();
+            l.pop_front();
+            l.pop_front();
+        }
+        push("d");
+        comp.jump_to_match(true);
+        let search = comp.search.as_ref().unwrap();
+        assert_eq!(search.matches.len(), 1);
+        assert_eq!(comp.log_scroll, 1);
+        assert_eq!(logs.read().unwrap()[comp.log_scroll as usize].text, "hit 2");
+    }
+
+    #[test]
+    fn jump_needs_prefix_key_and_follows_typed_prefix() {
+        let names = ["alpha", "beta", "🦕 bravo", "news"];
+        let mut comp = component(names.iter().map(|n| desc(n, "true", &[])).collect());
+        let press = |comp: &mut TaskControlComponent, c: KeyCode| comp.handle_list_keys(KeyEvent::from(c));
+
+        // 没有前缀键时字母不参与跳转 ('n' 留给 App 的通知历史)
+        assert!(!press(&mut comp, KeyCode::Char('n')));
+        assert_eq!(comp.selected_idx, 0);
+
+        assert!(press(&mut comp, KeyCode::Char('/')));
+        press(&mut comp, KeyCode::Char('b'));
+        assert_eq!(comp.selected_idx, 1);
+        press(&mut comp, KeyCode::Char('r'));
+        assert_eq!(comp.selected_idx, 2);
+        // 没有匹配时保持当前选中
+        press(&mut comp, KeyCode::Char('z'));
+        assert_eq!(comp.selected_idx, 2);
+        press(&mut comp, KeyCode::Backspace);
+        assert_eq!(comp.jump_buffer, "br");
+
+        // Esc 退出跳转后，j 恢复为向下移动
+        assert!(press(&mut comp, KeyCode::Esc));
+        assert!(!comp.jump_active());
+        press(&mut comp, KeyCode::Char('j'));
+        assert_eq!(comp.selected_idx, 3);
+    }
+
+    #[test]
+    fn edit_line_round_trips_any_argument() {
+        let args = [
+            "plain",
+            "with space",
+            "it's",
+            r#"say "hi""#,
+            r#"both ' and ""#,
+            r"C:\dir\file",
+            r"trailing\",
+            "",
+            "tab\there",
+        ];
+        let line = args.iter().map(|a| quote_arg(a)).collect::<Vec<_>>().join(" ");
+        assert_eq!(split_args(&line), args);
+        // 手写的输入：单引号内不转义，引号外的普通反斜杠按字面保留
+        assert_eq!(split_args(r"sh -c 'echo \n' C:\x a\ b"), ["sh", "-c", r"echo \n", r"C:\x", "a b"]);
+    }
+
+    #[test]
+    fn edited_args_change_what_is_spawned() {
+        GlobIO::init_for_test();
+        let mut comp = component(vec![desc("t", "echo", &["old"])]);
+        comp.handle_list_keys(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL));
+        assert!(comp.view_mode == ViewMode::Edit);
+        assert_eq!(comp.input, "echo old");
+
+        comp.input = r#"printf "%s|" "it's a \"test\"""#.to_string();
+        comp.handle_edit_keys(KeyEvent::from(KeyCode::Enter));
+        let (program, args) = comp.tasks[0].desc.spawn_argv();
+        assert_eq!(program, "printf");
+        assert_eq!(args, ["%s|", r#"it's a "test""#]);
+    }
+
+    #[test]
+    fn shell_quote_cases() {
+        assert_eq!(shell_quote("plain-arg_1.txt"), "plain-arg_1.txt");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("with space"), "'with space'");
+        assert_eq!(shell_quote("$HOME"), "'$HOME'");
+        assert_eq!(shell_quote(r#"say "hi""#), r#"'say "hi"'"#);
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn rerun_command_quotes_cwd_env_and_sh_c_script() {
+        let mut d = desc(
+            "backup",
+            "sh",
+            &["-c", "proot-distro backup archlinux --output ~/backup_$(date +%Y_%m_%d).tar"],
+        );
+        d.cwd = Some("/data/my dir".into());
+        d.envs = Some(HashMap::from([("B".into(), "x y".into()), ("A".into(), "1".into())]));
+        assert_eq!(
+            d.to_shell_command(),
+            "cd '/data/my dir' && A=1 B='x y' sh -c \
+             'proot-distro backup archlinux --output ~/backup_$(date +%Y_%m_%d).tar'"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn quoted_args_reach_the_program_unchanged() {
+        let args = ["a b", "$HOME", "it's", r#"say "hi""#, "`id`", "semi;colon", ""];
+        let quoted: Vec<String> = args.iter().map(|a| shell_quote(a)).collect();
+        let out = std::process::Command::new("sh")
+            .args(["-c", &format!("printf '%s\\n' {}", quoted.join(" "))])
+            .output()
+            .expect("run sh");
+        let printed = String::from_utf8(out.stdout).unwrap();
+        assert_eq!(printed.lines().collect::<Vec<_>>(), args);
+    }
+
+    #[test]
+    fn task_stats_accumulate_over_start_fail_stop_cycles() {
+        let mut stats = TaskStats::default();
+        // 运行 10 秒后失败退出
+        stats.record_start();
+        stats.record_exit(Duration::from_secs(10), Some(1), true);
+        // 无法启动
+        stats.record_start();
+        stats.record_exit(Duration::ZERO, None, true);
+        // 运行 5 秒后手动停止 (被信号结束，不算失败)
+        stats.record_start();
+        stats.record_exit(Duration::from_secs(5), None, false);
+        // 正常退出
+        stats.record_start();
+        stats.record_exit(Duration::from_secs(1), Some(0), false);
+
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.failures, 2);
+        assert_eq!(stats.uptime_secs, 16);
+        assert_eq!(stats.last_exit, Some(0));
+        assert!(stats.last_exit_at.is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn merged_output_keeps_emission_order() {
+        let script = "for i in 1 2 3 4 5; do echo out$i; echo err$i >&2; done";
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.args(["-c", script]).stdin(Stdio::null());
+        let reader = merge_stderr_into_stdout(&mut cmd).expect("merged pipe");
+        let mut child = cmd.spawn().expect("spawn sh");
+        // 父进程持有的写端必须随 Command 一起关闭，否则读端永远等不到 EOF
+        drop(cmd);
+        let mut lines = BufReader::new(reader).lines();
+        let mut got = Vec::new();
+        while let Some(line) = tokio::time::timeout(Duration::from_secs(3), lines.next_line())
+            .await
+            .expect("merged output timed out")
+            .expect("read merged output")
+        {
+            got.push(line);
+        }
+        child.wait().await.expect("wait sh");
+        let expected: Vec<String> = (1..=5).flat_map(|i| [format!("out{}", i), format!("err{}", i)]).collect();
+        assert_eq!(got, expected);
+    }
+
+    #[tokio::test]
+    async fn starting_a_task_selects_it_when_focus_on_start() {
+        let tasks = || vec![desc("a", "true", &[]), desc("b", "true", &[]), desc("c", "true", &[])];
+
+        let mut comp = component_with(Config { focus_on_start: true, ..Default::default() }, tasks());
+        comp.handle_task_request(TaskRequest::Start { id: "c".into(), focus: true });
+        assert_eq!(comp.selected_idx, 2);
+        assert!(comp.view_mode == ViewMode::List);
+        // 非用户发起的启动 (自动重启、计划任务) 不抢焦点
+        comp.handle_task_request(TaskRequest::Start { id: "b".into(), focus: false });
+        assert_eq!(comp.selected_idx, 2);
+
+        let mut comp = component_with(Config { focus_on_start: true, log_on_start: true, ..Default::default() }, tasks());
+        comp.handle_task_request(TaskRequest::Start { id: "b".into(), focus: true });
+        assert_eq!(comp.selected_idx, 1);
+        assert!(comp.view_mode == ViewMode::Log);
+
+        let mut comp = component_with(Config { focus_on_start: false, ..Default::default() }, tasks());
+        comp.handle_task_request(TaskRequest::Start { id: "c".into(), focus: true });
+        assert_eq!(comp.selected_idx, 0);
+    }
+
+    #[tokio::test]
+    async fn instant_exits_are_flagged_as_flapping_after_the_threshold() {
+        let mut flappy = desc("flappy", "sh", &["-c", "echo boom >&2; exit 3"]);
+        flappy.restart_policy = Some(RestartPolicy::Always);
+        let config = Config { flapping_window_ms: 5_000, flapping_threshold: 2, ..Default::default() };
+        let mut comp = component_with(config, vec![flappy]);
+        let failure = |comp: &TaskControlComponent| match &*comp.tasks[0].status.read().unwrap() {
+            TaskStatus::Failed(reason) => Some(reason.clone()),
+            _ => None,
+        };
+
+        comp.start_or_stop_task(0);
+        // 第一次秒退只计数并按 Always 重启 (重启请求经总线回到 drain)
+        assert!(wait_until(|| failure(&comp).is_some()).await);
+        assert!(!failure(&comp).unwrap().starts_with("Flapping"));
+        assert!(wait_until(|| {
+            comp.drain();
+            failure(&comp).is_some_and(|r| r.starts_with("Flapping"))
+        })
+        .await);
+        assert_eq!(comp.tasks[0].quick_exits.load(Ordering::Relaxed), 2);
+
+        // 判定为 flapping 后不再自动重启
+        tokio::time::sleep(Duration::from_millis(RESTART_DELAY_MS + 300)).await;
+        comp.drain();
+        assert!(!comp.is_running(0));
+        assert_eq!(comp.tasks[0].quick_exits.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn piped_file_lines_reach_the_child() {
+        let dir = std::env::temp_dir().join(format!("atlas_pipe_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("input.txt");
+        std::fs::write(&input, "alpha\nbeta gamma\n\ndelta\n").unwrap();
+        let binary = dir.join("blob.bin");
+        std::fs::write(&binary, [0xff, 0xfe, b'\n', 0x00]).unwrap();
+
+        let mut comp = component(vec![desc("echo", "sh", &["-c", r#"while read -r l; do echo "got:$l"; done"#])]);
+        comp.start_or_stop_task(0);
+        assert!(wait_until(|| comp.is_running(0)).await);
+        let logs = comp.tasks[0].logs.clone();
+        let stdout = || -> Vec<String> {
+            let l = logs.read().unwrap();
+            l.iter().filter(|x| x.source == LogSource::Stdout).map(|x| x.text.clone()).collect()
+        };
+
+        pipe_file_to_stdin(&comp.tasks[0], &binary.display().to_string());
+        pipe_file_to_stdin(&comp.tasks[0], &input.display().to_string());
+        let marker = format!(">>> [piped 4 lines from {}]", input.display());
+        assert!(wait_until(|| logs.read().unwrap().iter().any(|x| x.text == marker)).await);
+        assert!(wait_until(|| stdout().len() == 4).await, "{:?}", stdout());
+        assert_eq!(stdout(), ["got:alpha", "got:beta gamma", "got:", "got:delta"]);
+
+        comp.start_or_stop_task(0);
+        assert!(wait_until(|| !comp.is_running(0)).await);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn group_order_sorts_stably_and_unlisted_groups_go_last() {
+        let grouped = |id: &str, group: &str| TaskDescriptor { group: group.into(), ..desc(id, "true", &[]) };
+        let mut descs = vec![
+            grouped("api", "Services"),
+            grouped("hello", "Scripts"),
+            grouped("backup", "Maintenance"),
+            grouped("db", "Services"),
+            grouped("cron", "Zeta"),
+            grouped("lint", "scripts"),
+            grouped("vacuum", "Maintenance"),
+        ];
+        let ids = |descs: &[TaskDescriptor]| descs.iter().map(|d| d.id.clone()).collect::<Vec<_>>();
+
+        sort_by_group_order(&mut descs, &[]);
+        assert_eq!(ids(&descs), ["api", "hello", "backup", "db", "cron", "lint", "vacuum"]);
+
+        // 分组名不区分大小写，组内保持原顺序，未列出的分组按字母序排在后面
+        sort_by_group_order(&mut descs, &["SCRIPTS".to_string(), "Services".to_string()]);
+        assert_eq!(ids(&descs), ["hello", "lint", "api", "db", "backup", "vacuum", "cron"]);
+        let again = descs.clone();
+        sort_by_group_order(&mut descs, &["Scripts".to_string(), "Services".to_string()]);
+        assert_eq!(ids(&descs), ids(&again));
+    }
+
+    #[test]
+    fn configured_selection_symbol_marks_the_selected_row() {
+        crate::ui::style::set_selection_symbol_for_test("→ ");
+        let mut comp = component(vec![desc("alpha", "true", &[]), desc("bravo", "true", &[]), desc("charlie", "true", &[])]);
+        comp.selected_idx = 1;
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 12)).unwrap();
+        terminal.draw(|f| comp.render(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        let rows: Vec<String> = (0..12).map(|y| (0..80).map(|x| buf[(x, y)].symbol()).collect()).collect();
+
+        let marked: Vec<&String> = rows.iter().filter(|r| r.contains("→ ")).collect();
+        assert_eq!(marked.len(), 1, "{rows:#?}");
+        assert!(marked[0].contains("bravo"));
+        assert!(!rows.iter().any(|r| r.contains(">> ")));
+    }
+
+    #[tokio::test]
+    async fn restart_ends_running_with_a_single_child() {
+        let mut comp = component(vec![desc("svc", "sh", &["-c", "echo $$; exec sleep 30"])]);
+        let logs = comp.tasks[0].logs.clone();
+        let pids = || -> Vec<u32> {
+            let l = logs.read().unwrap();
+            l.iter().filter(|x| x.source == LogSource::Stdout).filter_map(|x| x.text.trim().parse().ok()).collect()
+        };
+        comp.start_or_stop_task(0);
+        assert!(wait_until(|| comp.is_running(0) && pids().len() == 1).await);
+        let first = pids()[0];
+
+        // 连按两次 R 只会重启一次
+        comp.restart_task(0);
+        comp.restart_task(0);
+        assert!(comp.tasks[0].restart_pending);
+        assert!(wait_until(|| {
+            comp.drain();
+            !comp.tasks[0].restart_pending && comp.is_running(0) && pids().len() == 2
+        })
+        .await);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        comp.drain();
+        assert_eq!(pids().len(), 2, "{:?}", pids());
+        assert!(!std::path::Path::new(&format!("/proc/{}", first)).exists(), "old child must be gone");
+        assert!(comp.tasks[0].control_tx.as_ref().is_some_and(|tx| !tx.is_closed()));
+        assert!(comp.is_running(0));
+
+        comp.start_or_stop_task(0);
+        assert!(wait_until(|| !comp.is_running(0)).await);
+    }
+
+    #[tokio::test]
+    async fn invalid_utf8_becomes_a_replacement_line_and_reading_continues() {
+        let mut reader: &[u8] = b"ok\n\xff\xfebad\r\nlast";
+        let mut buf = Vec::new();
+        let mut lines = Vec::new();
+        while let Some(l) = next_line_lossy(&mut reader, &mut buf).await {
+            lines.push(l);
+        }
+        // 非法字节替换为 U+FFFD，之后的行照常读出 (末行没有换行符也不丢)
+        assert_eq!(lines, vec!["ok", "\u{FFFD}\u{FFFD}bad", "last"]);
+    }
+
+    #[tokio::test]
+    async fn log_tail_request_returns_the_last_lines() {
+        let mut comp = component(vec![desc("api_logs_tail", "true", &[])]);
+        {
+            let mut logs = comp.tasks[0].logs.write().unwrap();
+            for i in 1..=5 {
+                logs.push_back(LogLine::new(LogSource::Stdout, format!("\x1b[32mline {i}\x1b[0m")));
+            }
+        }
+
+        // 经总线请求，组件在 drain 中回复
+        let request = tokio::spawn(crate::api::task_logs("api_logs_tail", Some(2)));
+        assert!(wait_until(|| { comp.drain(); request.is_finished() }).await);
+        let lines = request.await.unwrap().unwrap();
+        let texts: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+        // 只取最后两行，ANSI 转义已去除
+        assert_eq!(texts, ["line 4", "line 5"]);
+        assert_eq!(serde_json::to_value(&lines[0]).unwrap()["source"], "stdout");
+
+        // 超过缓冲区行数时返回全部
+        let request = tokio::spawn(crate::api::task_logs("api_logs_tail", Some(crate::api::LOG_TAIL_MAX + 1)));
+        assert!(wait_until(|| { comp.drain(); request.is_finished() }).await);
+        assert_eq!(request.await.unwrap().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn log_window_starts_at_scroll_in_a_large_buffer() {
+        let mut comp = component(vec![desc("big", "true", &[])]);
+        comp.view_mode = ViewMode::Log;
+        {
+            let mut l = comp.tasks[0].logs.write().unwrap();
+            for i in 0..4096 {
+                l.push_back(LogLine::new(LogSource::Stdout, format!("\x1b[32mline {i}\x1b[0m")));
+            }
+        }
+        let (width, height) = (60, 20);
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        let mut draw = |comp: &mut TaskControlComponent| -> Vec<String> {
+            terminal.draw(|f| comp.render(f, f.area())).unwrap();
+            let buf = terminal.backend().buffer();
+            (0..height).map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect::<String>()).collect()
+        };
+
+        // 窗口首行就是完整缓冲区中下标为 log_scroll 的那一行
+        comp.log_scroll = 2000;
+        let rows = draw(&mut comp);
+        assert!(rows[1].contains("line 2000 "), "{rows:#?}");
+        assert!(rows[2].contains("line 2001 "), "{rows:#?}");
+        assert!(!rows.iter().any(|r| r.contains("line 1999 ")));
+
+        // 滚动越过末尾时收回到最后一行，最新的日志仍然可见
+        comp.log_scroll = u16::MAX;
+        let rows = draw(&mut comp);
+        assert_eq!(comp.log_scroll, 4095);
+        assert!(rows[1].contains("line 4095 "), "{rows:#?}");
+
+        // 新日志追加后可以继续向下滚动到它
+        comp.tasks[0].logs.write().unwrap().push_back(LogLine::new(LogSource::Stdout, "line 4096"));
+        comp.log_scroll = u16::MAX;
+        let rows = draw(&mut comp);
+        assert_eq!(comp.log_scroll, 4096);
+        assert!(rows[1].contains("line 4096 "), "{rows:#?}");
+    }
+
+    #[test]
+    fn visible_window_scrolls_only_as_far_as_needed() {
+        // 选中项在顶部、中间、底部
+        assert_eq!(visible_window(0, 0, 10, 50), 0);
+        assert_eq!(visible_window(5, 0, 10, 50), 0);
+        assert_eq!(visible_window(9, 0, 10, 50), 0);
+        assert_eq!(visible_window(10, 0, 10, 50), 1);
+        assert_eq!(visible_window(49, 0, 10, 50), 40);
+        // 向上移出窗口时窗口首行就是选中项
+        assert_eq!(visible_window(3, 20, 10, 50), 3);
+        // 选中项仍在窗口内时不滚动
+        assert_eq!(visible_window(25, 20, 10, 50), 20);
+
+        // 任务减少：收回末尾的空白
+        assert_eq!(visible_window(5, 40, 10, 12), 2);
+        assert_eq!(visible_window(2, 40, 10, 3), 0);
+        // 空列表或零高度
+        assert_eq!(visible_window(0, 7, 10, 0), 0);
+        assert_eq!(visible_window(4, 7, 0, 10), 0);
+    }
+
+    #[test]
+    fn five_hundred_tasks_keep_the_selection_on_screen() {
+        let names: Vec<String> = (0..500).map(|i| format!("task{i:03}")).collect();
+        let mut comp = component(names.iter().map(|n| desc(n, "true", &[])).collect());
+        let (width, height) = (60, 24);
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        let mut draw = |comp: &mut TaskControlComponent| -> Vec<String> {
+            terminal.draw(|f| comp.render(f, f.area())).unwrap();
+            let buf = terminal.backend().buffer();
+            (0..height).map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect::<String>()).collect()
+        };
+        // 列表占上半屏，去掉边框后的可见行数
+        let visible = (height / 2 - 2) as usize;
+
+        for i in 0..500 {
+            let rows = draw(&mut comp);
+            assert_eq!(comp.selected_idx, i);
+            assert!(comp.list_offset <= i && i < comp.list_offset + visible, "{i}: offset {}", comp.list_offset);
+            assert!(rows.iter().any(|r| r.contains(&names[i])), "{i}: {rows:#?}");
+            comp.handle_list_keys(KeyEvent::from(KeyCode::Down));
+        }
+        assert_eq!(comp.list_offset, 500 - visible);
+
+        // 列表缩短后窗口随之收回，不留空白
+        comp.tasks.truncate(5);
+        comp.selected_idx = 4;
+        let rows = draw(&mut comp);
+        assert_eq!(comp.list_offset, 0);
+        assert!(rows.iter().any(|r| r.contains("task000")), "{rows:#?}");
+    }
+}
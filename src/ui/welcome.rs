@@ -8,9 +8,49 @@ pub struct WelcomeComponent {
     // 可以在这里记录程序启动时间
     show_help: bool,
     help_scroll: u16,
+
+    // Logo：Config.custom_logo_path 指定的文本文件，读取失败时使用内置 ART_LOGO
+    logo: Vec<String>,
 }
 
 impl WelcomeComponent {
+    /// 以给定配置构造组件并加载 logo
+    fn with_config(config: SharedConfig) -> Self {
+        let logo_path = config
+            .try_read()
+            .ok()
+            .and_then(|c| c.custom_logo_path.clone());
+        Self {
+            config,
+            show_help: false,
+            help_scroll: 0,
+            logo: Self::load_logo(logo_path.as_deref()),
+        }
+    }
+
+    /// 读取自定义 logo，只保留非空行；文件不存在/不可读/为空时回退到内置 logo
+    fn load_logo(path: Option<&str>) -> Vec<String> {
+        let lines = |s: &str| -> Vec<String> {
+            s.lines().filter(|l| !l.is_empty()).map(String::from).collect()
+        };
+        path.and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|s| lines(&s))
+            .filter(|l| !l.is_empty())
+            .unwrap_or_else(|| lines(ART_LOGO))
+    }
+
+    /// logo 高度由实际行数决定，不再依赖写死的常量
+    fn logo_height(&self) -> u16 {
+        self.logo.len().min(u16::MAX as usize) as u16
+    }
+
+    fn logo_lines(&self) -> Vec<Line<'_>> {
+        self.logo
+            .iter()
+            .map(|l| Line::from(Span::styled(l.as_str(), Style::default().fg(Color::Cyan))))
+            .collect()
+    }
+
     fn is_fullscreen(&self) -> bool {
         self.show_help // 当显示帮助时，请求全屏
     }
@@ -21,11 +61,7 @@ impl Component for WelcomeComponent {
     where
         Self: Sized,
     {
-        Self {
-            config:Config::get(),
-            show_help: false,
-            help_scroll: 0,
-        }
+        Self::with_config(Config::get())
     }
 
     fn update(&mut self) -> bool {
@@ -41,18 +77,14 @@ impl Component for WelcomeComponent {
 
             // 这里的 area 已经是 App 传过来的全屏 Rect（因为 is_fullscreen 返回了 true）
             let chunks = Layout::vertical([
-                Constraint::Length(crate::constans::ART_LOGO_HEIGHT), // 顶部固定高度给 Logo
+                Constraint::Length(self.logo_height()), // 顶部固定高度给 Logo
                 Constraint::Length(1),                                 // 留一行空行作为装饰
                 Constraint::Min(0),                                    // 剩余空间全给帮助内容
             ])
             .split(area);
 
             // --- 渲染置顶 Logo ---
-            let logo_lines: Vec<Line> = crate::constans::ART_LOGO
-                .lines()
-                .filter(|l| !l.is_empty())
-                .map(|l| Line::from(Span::styled(l, Style::default().fg(Color::Cyan))))
-                .collect();
+            let logo_lines = self.logo_lines();
 
             f.render_widget(
                 Paragraph::new(logo_lines).alignment(Alignment::Center),
@@ -82,7 +114,9 @@ impl Component for WelcomeComponent {
             // ==========================================
 
             let chunks = Layout::vertical([
-                Constraint::Percentage(crate::constans::GOLDEN_RATIO_PC),
+                Constraint::Percentage(
+                    self.config.try_read().map(|c| c.golden_ratio()).unwrap_or(crate::constans::GOLDEN_RATIO_PC),
+                ),
                 Constraint::Min(0),
             ])
             .split(area);
@@ -90,15 +124,11 @@ impl Component for WelcomeComponent {
             // Logo 弹簧布局：推至黄金分割线上方
             let logo_layout = Layout::vertical([
                 Constraint::Min(0),
-                Constraint::Length(crate::constans::ART_LOGO_HEIGHT),
+                Constraint::Length(self.logo_height()),
             ])
             .split(chunks[0]);
 
-            let logo_lines: Vec<Line> = crate::constans::ART_LOGO
-                .lines()
-                .filter(|l| !l.is_empty())
-                .map(|l| Line::from(Span::styled(l, Style::default().fg(Color::Cyan))))
-                .collect();
+            let logo_lines = self.logo_lines();
 
             f.render_widget(
                 Paragraph::new(logo_lines).alignment(Alignment::Center),
@@ -113,19 +143,39 @@ impl Component for WelcomeComponent {
             ])
             .split(chunks[1]);
 
+            // 可由配置覆盖的欢迎词/提示，超出宽度时截断
+            let (welcome, prompt) = match self.config.try_read() {
+                Ok(c) => (c.welcome_message.clone(), c.help_prompt.clone()),
+                Err(_) => (None, None),
+            };
+            let width = area.width as usize;
+            let welcome = fit_width(welcome.as_deref().unwrap_or(crate::constans::WELCOME_MSG), width);
+            let prompt = fit_width(prompt.as_deref().unwrap_or(crate::constans::HELP_PROMPT), width);
+
             f.render_widget(
-                Paragraph::new(crate::constans::WELCOME_MSG)
+                Paragraph::new(welcome)
                     .alignment(Alignment::Center)
                     .style(Style::default().add_modifier(Modifier::BOLD)),
                 sub_chunks[0],
             );
 
             f.render_widget(
-                Paragraph::new(crate::constans::HELP_PROMPT)
+                Paragraph::new(prompt)
                     .alignment(Alignment::Center)
                     .style(Style::default().fg(Color::DarkGray)),
                 sub_chunks[2],
             );
+
+            // 页脚：版本号，方便报告问题时说明所用版本
+            if area.height > 4 {
+                let footer = Rect { y: area.bottom() - 1, height: 1, ..area };
+                f.render_widget(
+                    Paragraph::new(format!("Atlas {} ", crate::version::version_string()))
+                        .alignment(Alignment::Right)
+                        .style(Style::default().fg(Color::DarkGray)),
+                    footer,
+                );
+            }
         }
     }
 
@@ -148,3 +198,66 @@ impl Component for WelcomeComponent {
         }
     }
 }
+
+/// 单行文本超出宽度时截断并追加省略号
+fn fit_width(s: &str, width: usize) -> String {
+    let s = s.lines().next().unwrap_or("");
+    if s.chars().count() <= width {
+        s.to_string()
+    } else {
+        let mut out: String = s.chars().take(width.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(config: Config) -> WelcomeComponent {
+        WelcomeComponent::with_config(std::sync::Arc::new(tokio::sync::RwLock::new(config)))
+    }
+
+    #[test]
+    fn custom_logo_sets_height() {
+        let path = std::env::temp_dir().join(format!("atlas_logo_{}.txt", std::process::id()));
+        std::fs::write(&path, "  /\\\n\n /  \\\n/____\\\n").unwrap();
+        let comp = component(Config { custom_logo_path: Some(path.display().to_string()), ..Default::default() });
+        // 空行不计入高度
+        assert_eq!(comp.logo_height(), 3);
+        assert_eq!(comp.logo[2], "/____\\");
+        let _ = std::fs::remove_file(&path);
+
+        // 文件不存在时回退到内置 logo
+        let comp = component(Config { custom_logo_path: Some(path.display().to_string()), ..Default::default() });
+        assert_eq!(comp.logo_height() as usize, ART_LOGO.lines().filter(|l| !l.is_empty()).count());
+    }
+
+    fn screen(comp: &mut WelcomeComponent, width: u16, height: u16) -> String {
+        let mut terminal = Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| comp.render(f, f.area())).unwrap();
+        let buf = terminal.backend().buffer();
+        (0..height)
+            .map(|y| (0..width).map(|x| buf[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn configured_welcome_text_replaces_default() {
+        let mut comp = component(Config::default());
+        let text = screen(&mut comp, 80, 30);
+        assert!(text.contains(crate::constans::WELCOME_MSG.trim()));
+
+        let mut comp = component(Config {
+            welcome_message: Some("Hello, pocket server".into()),
+            help_prompt: Some("Press ? for keys".into()),
+            ..Default::default()
+        });
+        let text = screen(&mut comp, 80, 30);
+        assert!(text.contains("Hello, pocket server"));
+        assert!(text.contains("Press ? for keys"));
+        assert!(!text.contains(crate::constans::WELCOME_MSG.trim()));
+    }
+}
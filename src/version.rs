@@ -0,0 +1,55 @@
+//! 编译进程序的版本信息与运行时长，Welcome 页、Info 页脚和 `/api/status` 共用
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+static STARTED: OnceLock<Instant> = OnceLock::new();
+
+/// 例如 "0.1.0 (a1b2c3d)"；不在 git 仓库中构建时没有哈希部分
+pub fn version_string() -> String {
+    match option_env!("ATLAS_GIT_HASH") {
+        Some(hash) if !hash.is_empty() => format!("{} ({})", env!("CARGO_PKG_VERSION"), hash),
+        _ => env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+/// main 启动时调用一次，作为 uptime 的起点
+pub fn mark_start() {
+    STARTED.get_or_init(Instant::now);
+}
+
+pub fn uptime() -> Duration {
+    STARTED.get_or_init(Instant::now).elapsed()
+}
+
+/// 紧凑的时长显示：45s / 12m / 3h05m / 2d04h
+pub fn format_uptime(d: Duration) -> String {
+    let s = d.as_secs();
+    match s {
+        0..60 => format!("{}s", s),
+        60..3600 => format!("{}m", s / 60),
+        3600..86400 => format!("{}h{:02}m", s / 3600, s % 3600 / 60),
+        _ => format!("{}d{:02}h", s / 86400, s % 86400 / 3600),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_string_starts_with_the_package_version() {
+        let version = version_string();
+        assert!(!version.trim().is_empty());
+        assert!(version.starts_with(env!("CARGO_PKG_VERSION")), "{version}");
+    }
+
+    #[test]
+    fn uptime_is_formatted_compactly() {
+        let fmt = |secs| format_uptime(Duration::from_secs(secs));
+        assert_eq!(fmt(45), "45s");
+        assert_eq!(fmt(12 * 60 + 5), "12m");
+        assert_eq!(fmt(3 * 3600 + 5 * 60), "3h05m");
+        assert_eq!(fmt(2 * 86400 + 4 * 3600), "2d04h");
+    }
+}
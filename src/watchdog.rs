@@ -0,0 +1,104 @@
+//! UI 看门狗：主循环每一拍调用 `beat()`，独立线程发现心跳长时间不前进 (例如渲染时在 RwLock 上死锁)
+//! 就把诊断写入日志，并按配置以 panic 方式退出，让 crash.log 留下记录，而不是无声地卡死。
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::constans::WATCHDOG_POLL_MS;
+
+static HEARTBEAT: AtomicU64 = AtomicU64::new(0);
+static STOPPED: AtomicBool = AtomicBool::new(false);
+
+/// 主循环每完成一拍 (update + 可能的 draw) 调用一次
+pub fn beat() {
+    HEARTBEAT.fetch_add(1, Ordering::Relaxed);
+}
+
+/// 主循环正常退出后调用，之后的清理 (停止任务、落盘) 不再受监控
+pub fn stop() {
+    STOPPED.store(true, Ordering::Relaxed);
+}
+
+/// 心跳判定，与线程和时钟解耦
+struct Monitor {
+    last: u64,
+    since: Instant,
+    reported: bool,
+}
+
+impl Monitor {
+    fn new(now: Instant) -> Self {
+        Self { last: HEARTBEAT.load(Ordering::Relaxed), since: now, reported: false }
+    }
+
+    /// 心跳超过 timeout 没有前进时返回 true (同一次停顿只报告一次)
+    fn check(&mut self, beat: u64, now: Instant, timeout: Duration) -> bool {
+        if beat != self.last {
+            self.last = beat;
+            self.since = now;
+            self.reported = false;
+            return false;
+        }
+        if self.reported || now.duration_since(self.since) < timeout {
+            return false;
+        }
+        self.reported = true;
+        true
+    }
+}
+
+/// timeout_secs 为 0 时不启动；abort 为 false 时只写日志
+pub fn spawn(timeout_secs: u64, abort: bool) {
+    if timeout_secs == 0 {
+        return;
+    }
+    let timeout = Duration::from_secs(timeout_secs);
+    let _ = std::thread::Builder::new().name("atlas-watchdog".into()).spawn(move || {
+        let mut monitor = Monitor::new(Instant::now());
+        while !STOPPED.load(Ordering::Relaxed) {
+            std::thread::sleep(Duration::from_millis(WATCHDOG_POLL_MS));
+            let beat = HEARTBEAT.load(Ordering::Relaxed);
+            if STOPPED.load(Ordering::Relaxed) || !monitor.check(beat, Instant::now(), timeout) {
+                continue;
+            }
+            let msg = format!(
+                "UI watchdog: no frame for {}s (heartbeat stuck at {}); a render or update is probably blocked on a lock",
+                timeout_secs, beat
+            );
+            log::error!("{}", msg);
+            log::logger().flush();
+            if abort {
+                // panic hook 负责恢复终端并写 crash.log；主线程仍卡住，因此随后直接退出进程
+                let _ = std::panic::catch_unwind(|| panic!("{}", msg));
+                std::process::exit(101);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stalled_heartbeat_is_reported_once_per_stall() {
+        let timeout = Duration::from_secs(5);
+        let t0 = Instant::now();
+        let at = |secs: u64| t0 + Duration::from_secs(secs);
+        let mut monitor = Monitor { last: 7, since: t0, reported: false };
+
+        // 心跳持续前进时从不报告
+        for (beat, secs) in [(8, 3), (9, 7), (10, 11)] {
+            assert!(!monitor.check(beat, at(secs), timeout));
+        }
+        // 停顿未到超时
+        assert!(!monitor.check(10, at(15), timeout));
+        // 超时只报告一次
+        assert!(monitor.check(10, at(16), timeout));
+        assert!(!monitor.check(10, at(30), timeout));
+        // 恢复后重新计时，下一次停顿再次报告
+        assert!(!monitor.check(11, at(31), timeout));
+        assert!(!monitor.check(11, at(35), timeout));
+        assert!(monitor.check(11, at(36), timeout));
+    }
+}